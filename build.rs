@@ -6,6 +6,7 @@ fn build_macos() {
     println!("cargo:rustc-link-lib=framework=CoreVideo");
     println!("cargo:rustc-link-search=framework=/System/Library/PrivateFrameworks");
     println!("cargo:rustc-link-lib=framework=DisplayServices");
+    println!("cargo:rustc-link-lib=framework=CoreDisplay");
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
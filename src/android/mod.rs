@@ -40,7 +40,9 @@ pub struct AndroidMemoryReadout {
 
 pub struct AndroidProductReadout;
 pub struct AndroidPackageReadout;
-pub struct AndroidNetworkReadout;
+pub struct AndroidNetworkReadout {
+    default_interface: shared::DefaultInterface,
+}
 
 impl BatteryReadout for AndroidBatteryReadout {
     fn new() -> Self {
@@ -80,7 +82,59 @@ impl BatteryReadout for AndroidBatteryReadout {
     }
 
     fn health(&self) -> Result<u8, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let bat_path = Path::new("/sys/class/power_supply/battery");
+        let full_path = bat_path.join("charge_full");
+        let design_path = bat_path.join("charge_full_design");
+
+        // Not every kernel exposes the charge counters, in which case
+        // health_status() is all there is to go by.
+        if !full_path.exists() || !design_path.exists() {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        let full = read_battery_value(&full_path)?;
+        let design = read_battery_value(&design_path)?;
+
+        if design == 0 {
+            return Err(ReadoutError::Other(
+                "Error calculating battery health.".to_string(),
+            ));
+        }
+
+        Ok((full.min(design) as f64 / design as f64 * 100.0).ceil() as u8)
+    }
+
+    fn health_status(&self) -> Result<String, ReadoutError> {
+        let bat_path = Path::new("/sys/class/power_supply/battery/health");
+        let health_text = fs::read_to_string(bat_path).map_err(|e| {
+            ReadoutError::Other(format!(
+                "Could not read {}: {}",
+                bat_path.to_str().unwrap_or_default(),
+                e
+            ))
+        })?;
+
+        match health_text.trim() {
+            "" | "Unknown" => Err(ReadoutError::MetricNotAvailable),
+            health => Ok(health.to_owned()),
+        }
+    }
+
+    fn temperature(&self) -> Result<f32, ReadoutError> {
+        let bat_path = Path::new("/sys/class/power_supply/battery/temp");
+        let temp_text = extra::pop_newline(fs::read_to_string(bat_path)?);
+
+        // The temperature is expressed in tenths of a degree Celsius.
+        match temp_text.parse::<i32>() {
+            Ok(t) => Ok(t as f32 / 10.0),
+            Err(e) => Err(ReadoutError::Other(format!(
+                "Could not parse the value '{}' of {} into a \
+            digit: {:?}",
+                temp_text,
+                bat_path.to_str().unwrap_or_default(),
+                e
+            ))),
+        }
     }
 }
 
@@ -119,6 +173,18 @@ impl KernelReadout for AndroidKernelReadout {
             Err(ReadoutError::Other(String::from("Failed to get os_type")))
         }
     }
+
+    fn architecture(&self) -> Result<String, ReadoutError> {
+        if let Some(utsname) = self.utsname {
+            Ok(unsafe { CStr::from_ptr(utsname.machine.as_ptr()) }
+                .to_string_lossy()
+                .into_owned())
+        } else {
+            Err(ReadoutError::Other(String::from(
+                "Failed to get architecture",
+            )))
+        }
+    }
 }
 
 impl GeneralReadout for AndroidGeneralReadout {
@@ -129,27 +195,105 @@ impl GeneralReadout for AndroidGeneralReadout {
     }
 
     fn backlight(&self) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        // Most vendors expose the panel's backlight as an LED rather than a backlight device.
+        if let Some(brightness) = read_brightness(Path::new("/sys/class/leds/lcd-backlight")) {
+            return Ok(brightness);
+        }
+
+        if let Ok(entries) = fs::read_dir("/sys/class/backlight") {
+            let mut devices: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+            devices.sort();
+
+            if let Some(brightness) = devices.first().and_then(|device| read_brightness(device)) {
+                return Ok(brightness);
+            }
+        }
+
+        // The brightness setting ranges from 0 to 255.
+        if extra::which("settings") {
+            let output = shared::command_output(
+                Path::new("settings"),
+                &["get", "system", "screen_brightness"],
+            )?;
+
+            if let Ok(brightness) = output.trim().parse::<f64>() {
+                return Ok(((brightness / 255.0 * 100.0).round() as usize).min(100));
+            }
+        }
+
+        Err(ReadoutError::MetricNotAvailable)
     }
 
     fn resolution(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        if let Some(resolution) = drm_resolution(Path::new("/sys/class/drm")) {
+            return Ok(resolution);
+        }
+
+        // The virtual size is written as "<width>,<height>".
+        if let Ok(virtual_size) = fs::read_to_string("/sys/class/graphics/fb0/virtual_size") {
+            if let Some((width, height)) = virtual_size.trim().split_once(',') {
+                if let (Ok(width), Ok(height)) = (width.parse::<u32>(), height.parse::<u32>()) {
+                    return Ok(format!("{width}x{height}"));
+                }
+            }
+        }
+
+        // The window manager can only be queried by the shell user (adb) and root, which
+        // Termux isn't running as.
+        let uid = unsafe { libc::getuid() };
+        if uid == 0 || uid == 2000 {
+            let output = shared::command_output(Path::new("wm"), &["size"])?;
+            if let Some(resolution) = parse_wm_size(&output) {
+                return Ok(resolution);
+            }
+        }
+
+        Err(ReadoutError::MetricNotAvailable)
     }
 
     fn machine(&self) -> Result<String, ReadoutError> {
         let product_readout = AndroidProductReadout::new();
 
+        // Properties holding the device's marketing name, e.g. "Galaxy S23 Ultra",
+        // which vendors have scattered around.
+        let market_name = [
+            "ro.product.marketname",
+            "ro.config.marketing_name",
+            "ro.product.vendor.marketname",
+            "ro.oppo.market.name",
+        ]
+        .into_iter()
+        .filter_map(getprop)
+        .map(|name| name.trim().to_string())
+        .find(|name| !name.is_empty());
+
+        if let Some(market_name) = market_name {
+            return Ok(match product_readout.vendor() {
+                Ok(vendor) => with_brand(&capitalize(&vendor), &market_name),
+                Err(_) => market_name,
+            });
+        }
+
         let family = product_readout.family()?;
-        let vendor = product_readout.vendor()?;
+        let vendor = capitalize(&product_readout.vendor()?);
         let product = product_readout.product()?;
 
         let new_product = format!("{vendor} {family} {product}");
 
-        if product.is_empty() || product.len() <= 15 {
-            return Ok(new_product.split_whitespace().unique().join(" "));
-        }
+        let machine = if product.is_empty() || product.len() <= 15 {
+            new_product.split_whitespace().unique().join(" ")
+        } else {
+            product
+        };
 
-        Ok(product)
+        // Model numbers alone are rather cryptic, the SoC tells a bit more about the device.
+        match soc() {
+            Some((soc, marketing_name)) => Ok(format!(
+                "{machine} ({})",
+                marketing_name.map_or(soc, String::from)
+            )),
+            None => Ok(machine),
+        }
     }
 
     fn username(&self) -> Result<String, ReadoutError> {
@@ -168,7 +312,7 @@ impl GeneralReadout for AndroidGeneralReadout {
     }
 
     fn distribution(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        Ok(format!("Android {}", android_release()?))
     }
 
     fn desktop_environment(&self) -> Result<String, ReadoutError> {
@@ -184,16 +328,33 @@ impl GeneralReadout for AndroidGeneralReadout {
     }
 
     fn terminal(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        if let Some(version) = termux_version() {
+            return Ok(format!("Termux {version}"));
+        }
+
+        match std::env::var("TERM") {
+            Ok(term) if !term.is_empty() => Ok(term),
+            _ => Err(ReadoutError::MetricNotAvailable),
+        }
     }
 
     fn shell(&self, format: ShellFormat, kind: ShellKind) -> Result<String, ReadoutError> {
-        if let Some(shell) = std::env::var_os("SHELL") {
-            if let Some(relative) = PathBuf::from(shell).file_name() {
-                if let Some(str) = relative.to_str() {
-                    return Ok(str.to_owned());
-                }
-            }
+        let format_path = |path: PathBuf| match format {
+            ShellFormat::Relative => path.file_name()?.to_str().map(str::to_owned),
+            ShellFormat::Absolute => path.to_str().map(str::to_owned),
+        };
+
+        if let Some(shell) = std::env::var_os("SHELL")
+            .map(PathBuf::from)
+            .and_then(format_path)
+        {
+            return Ok(shell);
+        }
+
+        // Termux's login script execs ~/.termux/shell (set through chsh)
+        // and falls back to bash, then to the system sh.
+        if let Some(shell) = termux_shell().and_then(format_path) {
+            return Ok(shell);
         }
 
         shared::shell(format, kind)
@@ -201,6 +362,14 @@ impl GeneralReadout for AndroidGeneralReadout {
 
     fn cpu_model_name(&self) -> Result<String, ReadoutError> {
         use std::io::{BufRead, BufReader};
+
+        if let Some((soc, marketing_name)) = soc() {
+            return Ok(match marketing_name {
+                Some(marketing_name) => format!("{soc} ({marketing_name})"),
+                None => soc,
+            });
+        }
+
         let file = fs::File::open("/proc/cpuinfo");
         let mut model: Option<String> = None;
         let mut hardware: Option<String> = None;
@@ -264,6 +433,24 @@ impl GeneralReadout for AndroidGeneralReadout {
         }
     }
 
+    fn load_average(&self) -> Result<(f64, f64, f64), ReadoutError> {
+        let mut info = self.sysinfo;
+        let info_ptr: *mut sysinfo = &mut info;
+        let ret = unsafe { sysinfo(info_ptr) };
+        if ret != -1 {
+            let f_load = 1f64 / (1 << libc::SI_LOAD_SHIFT) as f64;
+            Ok((
+                info.loads[0] as f64 * f_load,
+                info.loads[1] as f64 * f_load,
+                info.loads[2] as f64 * f_load,
+            ))
+        } else {
+            Err(ReadoutError::Other(
+                "Failed to get system statistics".to_string(),
+            ))
+        }
+    }
+
     fn uptime(&self) -> Result<usize, ReadoutError> {
         let mut info = self.sysinfo;
         let info_ptr: *mut sysinfo = &mut info;
@@ -278,21 +465,58 @@ impl GeneralReadout for AndroidGeneralReadout {
     }
 
     fn os_name(&self) -> Result<String, ReadoutError> {
-        match getprop("ro.build.version.release") {
-            Some(version) => Ok("Android ".to_string() + &version),
-            None => Err(ReadoutError::Other(
-                "Failed to get Android version".to_string(),
-            )),
+        let mut details: Vec<String> = Vec::new();
+
+        if let Some(sdk) = getprop("ro.build.version.sdk").filter(|sdk| !sdk.is_empty()) {
+            details.push(format!("API {sdk}"));
+        }
+
+        if let Some(skin) = vendor_skin() {
+            details.push(skin);
+        }
+
+        let name = format!("Android {}", android_release()?);
+
+        if details.is_empty() {
+            Ok(name)
+        } else {
+            Ok(format!("{name} ({})", details.join(", ")))
         }
     }
 
     fn disk_space(&self, path: &Path) -> Result<(u64, u64), ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = CString::new(path.as_os_str().as_bytes()).map_err(|_| {
+            ReadoutError::Other(format!("The provided path is not valid: {:?}", path))
+        })?;
+
+        let mut s: std::mem::MaybeUninit<libc::statvfs> = std::mem::MaybeUninit::uninit();
+
+        // App sandboxing can keep us from looking at paths such as /data, in
+        // which case statvfs fails with EACCES.
+        if unsafe { libc::statvfs(c_path.as_ptr(), s.as_mut_ptr()) } != 0 {
+            return Err(ReadoutError::Other(format!(
+                "Could not query disk usage of {}: {}",
+                path.display(),
+                std::io::Error::last_os_error()
+            )));
+        }
+
+        let stats = unsafe { s.assume_init() };
+        let total = stats.f_blocks as u64 * stats.f_frsize as u64;
+        let free = stats.f_bavail as u64 * stats.f_frsize as u64;
+
+        Ok((total.saturating_sub(free), total))
     }
 
     fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
         Err(ReadoutError::NotImplemented)
     }
+
+    fn locale(&self) -> Result<String, ReadoutError> {
+        shared::locale()
+    }
 }
 
 impl MemoryReadout for AndroidMemoryReadout {
@@ -372,6 +596,214 @@ impl MemoryReadout for AndroidMemoryReadout {
     }
 }
 
+/// Returns the manufacturer and model of the SoC, _e.g._ `Qualcomm SM8550`, along with its
+/// marketing name if it's known, _e.g._ `Snapdragon 8 Gen 2`.
+///
+/// The properties holding them were introduced in Android 12.
+fn soc() -> Option<(String, Option<&'static str>)> {
+    let model = getprop("ro.soc.model")
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty())?;
+
+    let marketing_name = soc_marketing_name(&model);
+
+    let soc = match getprop("ro.soc.manufacturer").as_deref().map(str::trim) {
+        Some("QTI") | Some("Qualcomm") => format!("Qualcomm {model}"),
+        Some("Mediatek") | Some("MediaTek") => format!("MediaTek {model}"),
+        Some(manufacturer) if !manufacturer.is_empty() => format!("{manufacturer} {model}"),
+        _ => model,
+    };
+
+    Some((soc, marketing_name))
+}
+
+fn soc_marketing_name(model: &str) -> Option<&'static str> {
+    let name = match model {
+        "SM8250" => "Snapdragon 865",
+        "SM8350" => "Snapdragon 888",
+        "SM8450" => "Snapdragon 8 Gen 1",
+        "SM8475" => "Snapdragon 8+ Gen 1",
+        "SM8550" => "Snapdragon 8 Gen 2",
+        "SM8650" => "Snapdragon 8 Gen 3",
+        "SM7325" => "Snapdragon 778G",
+        "SM7450" => "Snapdragon 7 Gen 1",
+        "SM6375" => "Snapdragon 695",
+        "MT6983" => "Dimensity 9000",
+        "MT6985" => "Dimensity 9200",
+        "MT6989" => "Dimensity 9300",
+        "GS101" => "Tensor",
+        "GS201" => "Tensor G2",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+/// Returns the version of Android, _e.g._ `14`.
+fn android_release() -> Result<String, ReadoutError> {
+    getprop("ro.build.version.release")
+        .filter(|release| !release.is_empty())
+        .ok_or_else(|| ReadoutError::Other("Failed to get Android version".to_string()))
+}
+
+/// Returns the name and version of the skin the vendor put on top of Android, if any,
+/// _e.g._ `One UI 6.1`.
+fn vendor_skin() -> Option<String> {
+    // Samsung encodes the version as a number, e.g. 60100 for 6.1.
+    if let Some(version) = getprop("ro.build.version.oneui").and_then(|v| v.parse::<u32>().ok()) {
+        return Some(format!(
+            "One UI {}.{}",
+            version / 10000,
+            version / 100 % 100
+        ));
+    }
+
+    // Xiaomi prefixes the version with a "V", e.g. V14.
+    getprop("ro.miui.ui.version.name")
+        .filter(|version| !version.is_empty())
+        .map(|version| format!("MIUI {}", version.trim_start_matches('V')))
+}
+
+/// Reads a numeric value from a file under `/sys/class/power_supply/battery`.
+fn read_battery_value(path: &Path) -> Result<u64, ReadoutError> {
+    let text = fs::read_to_string(path).map_err(|e| {
+        ReadoutError::Other(format!(
+            "Could not read {}: {}",
+            path.to_str().unwrap_or_default(),
+            e
+        ))
+    })?;
+
+    text.trim().parse::<u64>().map_err(|e| {
+        ReadoutError::Other(format!(
+            "Could not parse the value '{}' of {} into a digit: {:?}",
+            text.trim(),
+            path.to_str().unwrap_or_default(),
+            e
+        ))
+    })
+}
+
+/// Returns the version of the Termux app, if running inside of it.
+fn termux_version() -> Option<String> {
+    ["TERMUX_VERSION", "TERMUX_APP__APP_VERSION_NAME"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|version| !version.is_empty())
+}
+
+/// Returns the path of the shell Termux's `login` would start.
+fn termux_shell() -> Option<PathBuf> {
+    let prefix = std::env::var_os("PREFIX")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/data/data/com.termux/files/usr"));
+
+    if !prefix.join("bin/login").exists() {
+        return None;
+    }
+
+    std::env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".termux/shell"))
+        .and_then(|link| fs::canonicalize(link).ok())
+        .or_else(|| {
+            let bash = prefix.join("bin/bash");
+            bash.exists().then_some(bash)
+        })
+}
+
+/// Reads the brightness of a backlight or LED device as a percentage of its maximum.
+fn read_brightness(device: &Path) -> Option<usize> {
+    let read = |file: &str| -> Option<usize> {
+        fs::read_to_string(device.join(file))
+            .ok()?
+            .trim()
+            .parse::<usize>()
+            .ok()
+    };
+
+    let (current, max) = (read("brightness")?, read("max_brightness")?);
+    if max == 0 {
+        return None;
+    }
+
+    Some((current as f64 / max as f64 * 100.0).round() as usize)
+}
+
+/// Returns the preferred mode of the first connected connector listed under `drm`,
+/// _e.g._ `/sys/class/drm/card0-DSI-1`.
+fn drm_resolution(drm: &Path) -> Option<String> {
+    let mut connectors: Vec<PathBuf> = fs::read_dir(drm)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("card") && name.contains('-'))
+        })
+        .collect();
+    connectors.sort();
+
+    connectors.iter().find_map(|connector| {
+        let status = fs::read_to_string(connector.join("status")).ok()?;
+        if status.trim() != "connected" {
+            return None;
+        }
+
+        // The first mode is the preferred one, e.g. "1080x2400".
+        let modes = fs::read_to_string(connector.join("modes")).ok()?;
+        let mode = modes.lines().next()?.trim();
+        let (width, height) = mode.split_once('x')?;
+        let height = height.trim_end_matches(|c: char| !c.is_ascii_digit());
+
+        Some(format!(
+            "{}x{}",
+            width.parse::<u32>().ok()?,
+            height.parse::<u32>().ok()?
+        ))
+    })
+}
+
+/// Parses the output of `wm size`, where an override size, if any, follows the physical one:
+///
+/// ```text
+/// Physical size: 1080x2400
+/// Override size: 720x1600
+/// ```
+fn parse_wm_size(output: &str) -> Option<String> {
+    let size = |prefix: &str| {
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(prefix))
+            .map(|size| size.trim().to_string())
+            .filter(|size| !size.is_empty())
+    };
+
+    size("Override size:").or_else(|| size("Physical size:"))
+}
+
+/// Uppercases the first letter of a brand, _e.g._ `samsung` becomes `Samsung`.
+fn capitalize(brand: &str) -> String {
+    let mut chars = brand.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Prepends the brand to a marketing name, unless the name already starts with it.
+fn with_brand(brand: &str, market_name: &str) -> String {
+    let starts_with_brand = market_name
+        .get(..brand.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(brand));
+
+    if brand.is_empty() || starts_with_brand {
+        market_name.to_string()
+    } else {
+        format!("{brand} {market_name}")
+    }
+}
+
 impl ProductReadout for AndroidProductReadout {
     fn new() -> Self {
         AndroidProductReadout
@@ -402,8 +834,9 @@ impl PackageReadout for AndroidPackageReadout {
     fn count_pkgs(&self) -> Vec<(PackageManager, usize)> {
         let mut packages = Vec::new();
         // Since the target is Android we can assume that pm is available
-        if let Some(c) = AndroidPackageReadout::count_pm() {
-            packages.push((PackageManager::Android, c));
+        if let Some((user, system)) = AndroidPackageReadout::count_pm() {
+            packages.push((PackageManager::Android, user));
+            packages.push((PackageManager::AndroidSystem, system));
         }
 
         if extra::which("dpkg") {
@@ -423,19 +856,27 @@ impl PackageReadout for AndroidPackageReadout {
 }
 
 impl AndroidPackageReadout {
-    /// Returns the number of installed apps for the system
-    /// Includes all apps ( user + system )
-    fn count_pm() -> Option<usize> {
+    /// Returns the number of installed user and system apps, in that order.
+    fn count_pm() -> Option<(usize, usize)> {
+        // Each line reads "package:<path to the APK>=<package name>", and user
+        // apps are the ones installed under /data, e.g. /data/app.
         let pm_output = Command::new("pm")
-            .args(["list", "packages"])
+            .args(["list", "packages", "-f"])
             .stdout(Stdio::piped())
             .output()
-            .unwrap();
+            .ok()?;
 
-        extra::count_lines(
-            String::from_utf8(pm_output.stdout)
-                .expect("ERROR: \"pm list packages\" output was not valid UTF-8"),
-        )
+        let stdout = String::from_utf8_lossy(&pm_output.stdout);
+        let (user, system): (Vec<_>, Vec<_>) = stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("package:"))
+            .partition(|path| path.starts_with("/data/"));
+
+        if user.is_empty() && system.is_empty() {
+            return None;
+        }
+
+        Some((user.len(), system.len()))
     }
     /// Return the number of installed packages for systems
     /// that have `dpkg` installed.
@@ -465,30 +906,32 @@ impl AndroidPackageReadout {
 
 impl NetworkReadout for AndroidNetworkReadout {
     fn new() -> Self {
-        AndroidNetworkReadout
+        AndroidNetworkReadout {
+            default_interface: shared::DefaultInterface::default(),
+        }
     }
 
-    fn tx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "tx_bytes")
     }
 
-    fn tx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "tx_packets")
     }
 
-    fn rx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "rx_bytes")
     }
 
-    fn rx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "rx_packets")
     }
 
     fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
-        shared::logical_address(interface)
+        shared::logical_address(Some(self.default_interface.resolve(interface)?))
     }
 
-    fn physical_address(&self, _: Option<&str>) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn physical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        shared::interface_address(self.default_interface.resolve(interface)?)
     }
 }
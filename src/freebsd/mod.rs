@@ -1,5 +1,4 @@
 #![allow(clippy::unnecessary_cast)]
-use crate::extra;
 use crate::shared;
 use crate::traits::*;
 use crate::winman;
@@ -26,18 +25,28 @@ pub struct FreeBSDKernelReadout {
 pub struct FreeBSDGeneralReadout {
     hostname_ctl: Option<Ctl>,
     model_ctl: Option<Ctl>,
+    boot_time_ctl: Option<Ctl>,
 }
 
 pub struct FreeBSDMemoryReadout {
     // available memory
     physmem_ctl: Option<Ctl>,
-    // used memory
-    usermem_ctl: Option<Ctl>,
+    // page counts, see vm.stats.vm
+    page_size_ctl: Option<Ctl>,
+    free_count_ctl: Option<Ctl>,
+    inactive_count_ctl: Option<Ctl>,
+    cache_count_ctl: Option<Ctl>,
+    active_count_ctl: Option<Ctl>,
+    wire_count_ctl: Option<Ctl>,
+    // buffer cache size, in bytes
+    bufspace_ctl: Option<Ctl>,
 }
 
 pub struct FreeBSDProductReadout;
 pub struct FreeBSDPackageReadout;
-pub struct FreeBSDNetworkReadout;
+pub struct FreeBSDNetworkReadout {
+    default_interface: shared::DefaultInterface,
+}
 
 impl BatteryReadout for FreeBSDBatteryReadout {
     fn new() -> Self {
@@ -48,11 +57,20 @@ impl BatteryReadout for FreeBSDBatteryReadout {
     }
 
     fn percentage(&self) -> Result<u8, ReadoutError> {
-        if let Some(ctl) = &self.battery_life_ctl {
-            if let Ok(val) = ctl.value_string() {
-                if let Ok(to_int) = val.parse::<u8>() {
-                    return Ok(to_int);
+        let ctl = self.battery_life_ctl.as_ref().ok_or_else(|| {
+            ReadoutError::MetricUnavailableForHardware(String::from("No batteries detected."))
+        })?;
+
+        if let Ok(val) = ctl.value_string() {
+            // The kernel reports a battery life of -1 when no battery units are present.
+            match val.parse::<i32>() {
+                Ok(life) if life < 0 => {
+                    return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+                        "No batteries detected.",
+                    )))
                 }
+                Ok(life) => return Ok(life as u8),
+                Err(_) => (),
             }
         }
 
@@ -60,20 +78,13 @@ impl BatteryReadout for FreeBSDBatteryReadout {
     }
 
     fn status(&self) -> Result<BatteryState, ReadoutError> {
-        if let Some(ctl) = &self.battery_state_ctl {
-            if let Ok(val) = ctl.value_string() {
-                if let Ok(to_int) = val.parse::<u8>() {
-                    match to_int {
-                        // https://lists.freebsd.org/pipermail/freebsd-acpi/2019-October/009753.html
-                        1 => return Ok(BatteryState::Discharging),
-                        2 => return Ok(BatteryState::Charging),
-                        _ => {
-                            return Err(ReadoutError::Other(
-                                "An unsupported battery state was reported.".to_string(),
-                            ))
-                        }
-                    };
-                }
+        let ctl = self.battery_state_ctl.as_ref().ok_or_else(|| {
+            ReadoutError::MetricUnavailableForHardware(String::from("No batteries detected."))
+        })?;
+
+        if let Ok(val) = ctl.value_string() {
+            if let Ok(state) = val.parse::<i32>() {
+                return battery_state(state);
             }
         }
 
@@ -81,8 +92,133 @@ impl BatteryReadout for FreeBSDBatteryReadout {
     }
 
     fn health(&self) -> Result<u8, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let units = Ctl::new("hw.acpi.battery.units")
+            .ok()
+            .and_then(|ctl| ctl.value_string().ok())
+            .and_then(|units| units.parse::<i32>().ok())
+            .unwrap_or(1);
+
+        let (full, design) = (0..units)
+            .try_fold((0.0, 0.0), |(total_full, total_design), unit| {
+                let (full, design) = acpi_battery_capacity(unit)?;
+                Some((total_full + full, total_design + design))
+            })
+            .ok_or_else(|| ReadoutError::Other("Error calculating battery health.".to_string()))?;
+
+        if design == 0.0 {
+            return Err(ReadoutError::Other(
+                "Error calculating battery health.".to_string(),
+            ));
+        }
+
+        Ok((full.min(design) / design * 100.0).ceil() as u8)
+    }
+}
+
+const ACPI_BATT_STAT_DISCHARG: i32 = 0x0001;
+const ACPI_BATT_STAT_CHARGING: i32 = 0x0002;
+const ACPI_BATT_STAT_CRITICAL: i32 = 0x0004;
+const ACPI_BATT_STAT_INVALID: i32 = ACPI_BATT_STAT_DISCHARG | ACPI_BATT_STAT_CHARGING;
+const ACPI_BATT_STAT_NOT_PRESENT: i32 =
+    ACPI_BATT_STAT_DISCHARG | ACPI_BATT_STAT_CHARGING | ACPI_BATT_STAT_CRITICAL;
+
+/// Maps the `hw.acpi.battery.state` bitfield, as declared in `dev/acpica/acpiio.h`,
+/// to a [BatteryState].
+///
+/// A state of 0 means the battery is neither charging nor discharging, _e.g._ because
+/// it's full, and is reported as discharging. The critical bit doesn't affect the result.
+fn battery_state(state: i32) -> Result<BatteryState, ReadoutError> {
+    if state < 0 || state & ACPI_BATT_STAT_NOT_PRESENT == ACPI_BATT_STAT_NOT_PRESENT {
+        return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+            "No batteries detected.",
+        )));
+    }
+
+    if state & ACPI_BATT_STAT_INVALID == ACPI_BATT_STAT_INVALID {
+        return Err(ReadoutError::Other(
+            "An invalid battery state was reported.".to_string(),
+        ));
+    }
+
+    if state & ACPI_BATT_STAT_CHARGING != 0 {
+        return Ok(BatteryState::Charging);
+    }
+
+    Ok(BatteryState::Discharging)
+}
+
+/// Mirrors `struct acpi_bif`, declared in `dev/acpica/acpiio.h`.
+#[allow(non_camel_case_types, dead_code)]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct acpi_bif {
+    units: u32,
+    dcap: u32,
+    lfcap: u32,
+    btech: u32,
+    dvol: u32,
+    wcap: u32,
+    lcap: u32,
+    gra1: u32,
+    gra2: u32,
+    model: [libc::c_char; 32],
+    serial: [libc::c_char; 32],
+    type_: [libc::c_char; 32],
+    oeminfo: [libc::c_char; 32],
+}
+
+/// Mirrors `union acpi_battery_ioctl_arg`, as passed to `ACPIIO_BATT_GET_BIF`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+union acpi_battery_ioctl_arg {
+    unit: libc::c_int,
+    bif: acpi_bif,
+}
+
+/// `_IOWR('B', 0x11, union acpi_battery_ioctl_arg)`
+const ACPIIO_BATT_GET_BIF: libc::c_ulong = 0xC0A4_4211;
+
+/// Returns the last full and design capacities of a battery unit, querying `/dev/acpi`
+/// and falling back to `acpiconf -i <unit>`.
+fn acpi_battery_capacity(unit: i32) -> Option<(f64, f64)> {
+    use std::os::unix::io::AsRawFd;
+
+    if let Ok(acpi) = fs::File::open("/dev/acpi") {
+        let mut arg = acpi_battery_ioctl_arg { unit };
+
+        if unsafe { libc::ioctl(acpi.as_raw_fd(), ACPIIO_BATT_GET_BIF, &mut arg) } == 0 {
+            let bif = unsafe { arg.bif };
+
+            // Capacities are unknown when set to 0xffffffff.
+            if bif.dcap != u32::MAX && bif.lfcap != u32::MAX {
+                return Some((bif.lfcap as f64, bif.dcap as f64));
+            }
+        }
     }
+
+    let output = shared::command_output(Path::new("acpiconf"), &["-i", &unit.to_string()]).ok()?;
+
+    parse_acpiconf_capacity(&output)
+}
+
+/// Parses the last full and design capacities from the output of `acpiconf -i`, _e.g._
+/// `Design capacity: 5800 mWh`.
+fn parse_acpiconf_capacity(output: &str) -> Option<(f64, f64)> {
+    let value_of = |field: &str| {
+        output.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if key.trim() != field {
+                return None;
+            }
+
+            value.split_whitespace().next()?.parse::<f64>().ok()
+        })
+    };
+
+    Some((
+        value_of("Last full capacity")?,
+        value_of("Design capacity")?,
+    ))
 }
 
 impl KernelReadout for FreeBSDKernelReadout {
@@ -112,6 +248,10 @@ impl KernelReadout for FreeBSDKernelReadout {
     fn pretty_kernel(&self) -> Result<String, ReadoutError> {
         Err(ReadoutError::MetricNotAvailable)
     }
+
+    fn architecture(&self) -> Result<String, ReadoutError> {
+        shared::architecture()
+    }
 }
 
 impl GeneralReadout for FreeBSDGeneralReadout {
@@ -119,6 +259,7 @@ impl GeneralReadout for FreeBSDGeneralReadout {
         FreeBSDGeneralReadout {
             hostname_ctl: Ctl::new("kern.hostname").ok(),
             model_ctl: Ctl::new("hw.model").ok(),
+            boot_time_ctl: Ctl::new("kern.boottime").ok(),
         }
     }
 
@@ -126,12 +267,23 @@ impl GeneralReadout for FreeBSDGeneralReadout {
         shared::resolution()
     }
 
+    fn displays(&self) -> Result<Vec<Display>, ReadoutError> {
+        shared::x11_displays()
+    }
+
     fn backlight(&self) -> Result<usize, ReadoutError> {
         Err(ReadoutError::MetricNotAvailable)
     }
 
     fn machine(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::MetricNotAvailable)
+        let product_readout = FreeBSDProductReadout::new();
+
+        let vendor = product_readout.vendor()?;
+        let family = product_readout.family().unwrap_or_default();
+        let product = product_readout.product()?;
+        let version = kenv_get("smbios.system.version").unwrap_or_default();
+
+        shared::machine_name(&vendor, &family, &product, &version)
     }
 
     fn username(&self) -> Result<String, ReadoutError> {
@@ -154,6 +306,10 @@ impl GeneralReadout for FreeBSDGeneralReadout {
         shared::desktop_environment()
     }
 
+    fn desktop_environment_version(&self) -> Result<String, ReadoutError> {
+        shared::desktop_environment_version()
+    }
+
     fn session(&self) -> Result<String, ReadoutError> {
         shared::session()
     }
@@ -163,62 +319,9 @@ impl GeneralReadout for FreeBSDGeneralReadout {
     }
 
     fn terminal(&self) -> Result<String, ReadoutError> {
-        // This function returns the PPID of a given PID:
-        //  - The file used to extract this data: /proc/<pid>/status
-        //  - The format of the file is: command_name command_pid command_ppid ...
-        fn get_parent(pid: i32) -> i32 {
-            let process_path = PathBuf::from("/proc").join(pid.to_string()).join("status");
-            if let Ok(content) = fs::read_to_string(process_path) {
-                if let Some(val) = content.split_whitespace().nth(2) {
-                    if let Ok(c) = val.parse::<i32>() {
-                        return c;
-                    }
-                }
-
-                return -1;
-            }
-
-            -1
-        }
-
-        // This function returns the name associated with a given PPID
-        fn terminal_name() -> String {
-            let mut terminal_pid = get_parent(unsafe { libc::getppid() });
-
-            let path = PathBuf::from("/proc")
-                .join(terminal_pid.to_string())
-                .join("status");
-
-            // The below loop will traverse /proc to find the
-            // terminal inside of which the user is operating
-            if let Ok(mut terminal_name) = fs::read_to_string(path) {
-                terminal_name = terminal_name.split_whitespace().next().unwrap().to_owned();
-
-                // Any command_name we find that matches
-                // one of the elements in common_shells()
-                // is effectively ignored
-                while extra::common_shells().contains(&terminal_name.as_str()) {
-                    let ppid = get_parent(terminal_pid);
-                    terminal_pid = ppid;
-
-                    let path = PathBuf::from("/proc")
-                        .join(terminal_pid.to_string())
-                        .join("status");
-
-                    if let Ok(status) = fs::read_to_string(path) {
-                        if let Some(name) = status.split_whitespace().next() {
-                            terminal_name = name.to_string();
-                        }
-                    }
-                }
-
-                return terminal_name;
-            }
-
-            String::new()
-        }
-
-        let terminal = terminal_name();
+        let terminal = terminal_process()
+            .map(|terminal| terminal.to_string())
+            .unwrap_or_default();
 
         if terminal.is_empty() {
             return Err(ReadoutError::Other(
@@ -229,6 +332,18 @@ impl GeneralReadout for FreeBSDGeneralReadout {
         Ok(terminal)
     }
 
+    fn terminal_version(&self) -> Result<String, ReadoutError> {
+        let terminal = terminal_process().ok_or(ReadoutError::MetricNotAvailable)?;
+        let exe = fs::read_link(
+            PathBuf::from("/proc")
+                .join(terminal.pid.to_string())
+                .join("file"),
+        )
+        .ok();
+
+        shared::terminal_version(&terminal.name, exe.as_deref())
+    }
+
     fn shell(&self, shorthand: ShellFormat, kind: ShellKind) -> Result<String, ReadoutError> {
         shared::shell(shorthand, kind)
     }
@@ -249,19 +364,25 @@ impl GeneralReadout for FreeBSDGeneralReadout {
         shared::cpu_physical_cores()
     }
 
+    #[cfg(target_arch = "x86_64")]
+    fn cpu_flags(&self) -> Result<Vec<String>, ReadoutError> {
+        Ok(cpuid_flags())
+    }
+
     fn cpu_usage(&self) -> Result<usize, ReadoutError> {
         shared::cpu_usage()
     }
 
+    fn load_average(&self) -> Result<(f64, f64, f64), ReadoutError> {
+        shared::load_average()
+    }
+
     fn uptime(&self) -> Result<usize, ReadoutError> {
-        let ctl = match sysctl::Ctl::new("kern.boottime") {
-            Ok(ctl) => ctl,
-            Err(_) => {
-                return Err(ReadoutError::Other(
-                    "Could not get sysctl: kern.boottime".to_string(),
-                ));
-            }
-        };
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        let ctl = self.boot_time_ctl.as_ref().ok_or_else(|| {
+            ReadoutError::Other("Could not get sysctl: kern.boottime".to_string())
+        })?;
         let boot_time = match ctl.value_as::<libc::timeval>() {
             Ok(boot_time) => boot_time,
             Err(_) => {
@@ -271,8 +392,11 @@ impl GeneralReadout for FreeBSDGeneralReadout {
             }
         };
 
-        match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
-            Ok(unix_epoch) => Ok(unix_epoch.as_secs() as usize - boot_time.tv_sec as usize),
+        let bootup_timestamp =
+            UNIX_EPOCH + Duration::new(boot_time.tv_sec as u64, (boot_time.tv_usec * 1000) as u32);
+
+        match SystemTime::now().duration_since(bootup_timestamp) {
+            Ok(duration) => Ok(duration.as_secs() as usize),
             Err(_) => Err(ReadoutError::MetricNotAvailable),
         }
     }
@@ -297,46 +421,78 @@ impl GeneralReadout for FreeBSDGeneralReadout {
     fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
         Err(ReadoutError::NotImplemented)
     }
+
+    fn disk_spaces(&self) -> Result<Vec<DiskSpace>, ReadoutError> {
+        shared::disk_spaces()
+    }
+
+    fn disk_io(&self, all: bool) -> Result<Vec<DiskIo>, ReadoutError> {
+        const DEVSTAT_READ: usize = 1;
+        const DEVSTAT_WRITE: usize = 2;
+        const DEVSTAT_TYPE_PASS: u32 = 0x100;
+
+        Ok(devstat_all()?
+            .into_iter()
+            // Pass-through devices duplicate the statistics of the disks behind them.
+            .filter(|stats| all || stats.device_type & DEVSTAT_TYPE_PASS == 0)
+            .map(|stats| {
+                let name = unsafe { std::ffi::CStr::from_ptr(stats.device_name.as_ptr()) };
+
+                DiskIo {
+                    name: format!("{}{}", name.to_string_lossy(), stats.unit_number),
+                    bytes_read: stats.bytes[DEVSTAT_READ],
+                    bytes_written: stats.bytes[DEVSTAT_WRITE],
+                }
+            })
+            .collect())
+    }
+
+    fn locale(&self) -> Result<String, ReadoutError> {
+        shared::locale()
+    }
+
+    fn init_system(&self) -> Result<String, ReadoutError> {
+        Ok(String::from("init"))
+    }
 }
 
 impl MemoryReadout for FreeBSDMemoryReadout {
     fn new() -> Self {
         FreeBSDMemoryReadout {
             physmem_ctl: Ctl::new("hw.physmem").ok(),
-            usermem_ctl: Ctl::new("hw.usermem").ok(),
+            page_size_ctl: Ctl::new("vm.stats.vm.v_page_size").ok(),
+            free_count_ctl: Ctl::new("vm.stats.vm.v_free_count").ok(),
+            inactive_count_ctl: Ctl::new("vm.stats.vm.v_inactive_count").ok(),
+            cache_count_ctl: Ctl::new("vm.stats.vm.v_cache_count").ok(),
+            active_count_ctl: Ctl::new("vm.stats.vm.v_active_count").ok(),
+            wire_count_ctl: Ctl::new("vm.stats.vm.v_wire_count").ok(),
+            bufspace_ctl: Ctl::new("vfs.bufspace").ok(),
         }
     }
 
     fn total(&self) -> Result<u64, ReadoutError> {
-        Ok(self
-            .physmem_ctl
-            .as_ref()
-            .ok_or(ReadoutError::MetricNotAvailable)?
-            .value_string()
-            .unwrap()
-            .parse::<u64>()
-            .unwrap()
-            / 1024)
+        Ok(ctl_value(&self.physmem_ctl)? / 1024)
     }
 
     fn free(&self) -> Result<u64, ReadoutError> {
-        Ok(self
-            .usermem_ctl
-            .as_ref()
-            .ok_or(ReadoutError::MetricNotAvailable)?
-            .value_string()
-            .unwrap()
-            .parse::<u64>()
-            .unwrap()
-            / 1024)
+        let pages = ctl_value(&self.free_count_ctl)?
+            + ctl_value(&self.inactive_count_ctl)?
+            + ctl_value(&self.cache_count_ctl).unwrap_or(0);
+
+        Ok(pages * ctl_value(&self.page_size_ctl)? / 1024)
     }
 
     fn buffers(&self) -> Result<u64, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        Ok(ctl_value(&self.bufspace_ctl)? / 1024)
     }
 
     fn cached(&self) -> Result<u64, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        // v_cache_count is always zero since FreeBSD 12, where cached file
+        // data lives in inactive pages instead.
+        let pages =
+            ctl_value(&self.inactive_count_ctl)? + ctl_value(&self.cache_count_ctl).unwrap_or(0);
+
+        Ok(pages * ctl_value(&self.page_size_ctl)? / 1024)
     }
 
     fn reclaimable(&self) -> Result<u64, ReadoutError> {
@@ -344,10 +500,9 @@ impl MemoryReadout for FreeBSDMemoryReadout {
     }
 
     fn used(&self) -> Result<u64, ReadoutError> {
-        let total = self.total().unwrap();
-        let free = self.free().unwrap();
+        let pages = ctl_value(&self.active_count_ctl)? + ctl_value(&self.wire_count_ctl)?;
 
-        Ok(total - free)
+        Ok(pages * ctl_value(&self.page_size_ctl)? / 1024)
     }
 
     fn swap_total(&self) -> Result<u64, ReadoutError> {
@@ -369,15 +524,15 @@ impl ProductReadout for FreeBSDProductReadout {
     }
 
     fn family(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::MetricNotAvailable)
+        kenv_get("smbios.system.family")
     }
 
     fn vendor(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::MetricNotAvailable)
+        kenv_get("smbios.system.maker")
     }
 
     fn product(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::MetricNotAvailable)
+        kenv_get("smbios.system.product")
     }
 }
 
@@ -431,30 +586,342 @@ impl FreeBSDPackageReadout {
 
 impl NetworkReadout for FreeBSDNetworkReadout {
     fn new() -> Self {
-        FreeBSDNetworkReadout
+        FreeBSDNetworkReadout {
+            default_interface: shared::DefaultInterface::default(),
+        }
     }
 
-    fn tx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        Ok(shared::link_info(ifname)?.data.ifi_obytes as usize)
     }
 
-    fn tx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        Ok(shared::link_info(ifname)?.data.ifi_opackets as usize)
     }
 
-    fn rx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        Ok(shared::link_info(ifname)?.data.ifi_ibytes as usize)
     }
 
-    fn rx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        Ok(shared::link_info(ifname)?.data.ifi_ipackets as usize)
     }
 
     fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
-        shared::logical_address(interface)
+        let address = self
+            .default_interface
+            .resolve(interface)
+            .and_then(|ifname| shared::logical_address(Some(ifname)));
+
+        match interface {
+            // There may be no IPv4 default route, e.g. behind some VPNs.
+            None => address.or_else(|_| shared::primary_address()),
+            Some(_) => address,
+        }
     }
 
-    fn physical_address(&self, _: Option<&str>) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn physical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        shared::link_info(ifname)?.mac_address()
+    }
+
+    fn default_gateway(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        shared::default_gateway(interface)
+    }
+
+    fn dns_servers(&self) -> Result<Vec<String>, ReadoutError> {
+        shared::dns_servers()
+    }
+
+    fn link_speed(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::link_speed(self.default_interface.resolve(interface)?)
+    }
+}
+
+#[allow(non_camel_case_types, dead_code)]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct bintime {
+    sec: libc::time_t,
+    frac: u64,
+}
+
+/// Mirrors `struct devstat`, declared in `sys/devicestat.h`.
+#[allow(non_camel_case_types, dead_code)]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct devstat {
+    sequence0: libc::c_uint,
+    allocated: libc::c_int,
+    start_count: libc::c_uint,
+    end_count: libc::c_uint,
+    busy_from: bintime,
+    dev_links: *mut devstat,
+    device_number: u32,
+    device_name: [libc::c_char; 16],
+    unit_number: libc::c_int,
+    bytes: [u64; 4],
+    operations: [u64; 4],
+    duration: [bintime; 4],
+    busy_time: bintime,
+    creation_time: bintime,
+    block_size: u32,
+    tag_types: [u64; 3],
+    flags: libc::c_int,
+    device_type: u32,
+    priority: libc::c_int,
+    id: *const libc::c_void,
+    sequence1: libc::c_uint,
+}
+
+// The records are split by this size, so it has to match the kernel's on LP64 platforms.
+#[cfg(target_pointer_width = "64")]
+const _: () = assert!(std::mem::size_of::<devstat>() == 288);
+
+/// Reads the statistics of every device from `kern.devstat.all`, which holds
+/// a generation number followed by an array of `struct devstat`.
+fn devstat_all() -> Result<Vec<devstat>, ReadoutError> {
+    let name = std::ffi::CString::new("kern.devstat.all").expect("Unable to create c string");
+    let mut len: libc::size_t = 0;
+
+    let ret = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            std::ptr::null_mut(),
+            &mut len,
+            std::ptr::null(),
+            0,
+        )
+    };
+
+    if ret != 0 {
+        return Err(ReadoutError::Other(String::from(
+            "Could not read the size of kern.devstat.all",
+        )));
+    }
+
+    let mut buffer = vec![0u8; len];
+    let ret = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            &mut len,
+            std::ptr::null(),
+            0,
+        )
+    };
+
+    if ret != 0 {
+        return Err(ReadoutError::Other(String::from(
+            "Could not read kern.devstat.all",
+        )));
+    }
+
+    let records = buffer
+        .get(std::mem::size_of::<libc::c_long>()..len)
+        .unwrap_or_default();
+
+    Ok(records
+        .chunks_exact(std::mem::size_of::<devstat>())
+        .map(|record| unsafe { std::ptr::read_unaligned(record.as_ptr() as *const devstat) })
+        .collect())
+}
+
+/// Reads a variable from the kernel environment, _e.g._ `smbios.system.maker`.
+fn kenv_get(name: &str) -> Result<String, ReadoutError> {
+    let error = || ReadoutError::Other(format!("Could not read \"{name}\" through kenv."));
+
+    let name = std::ffi::CString::new(name).map_err(|_| error())?;
+    let mut buffer = vec![0u8; libc::KENV_MVALLEN as usize + 1];
+
+    let len = unsafe {
+        libc::kenv(
+            libc::KENV_GET,
+            name.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_char,
+            buffer.len() as libc::c_int,
+        )
+    };
+
+    if len < 0 {
+        return Err(error());
+    }
+
+    let value = std::ffi::CStr::from_bytes_until_nul(&buffer)
+        .map_err(|_| error())?
+        .to_string_lossy()
+        .trim()
+        .to_string();
+
+    if value.is_empty() {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    Ok(value)
+}
+
+/// Reads the value of an integer sysctl, whichever its width.
+fn ctl_value(ctl: &Option<Ctl>) -> Result<u64, ReadoutError> {
+    use sysctl::CtlValue;
+
+    match ctl
+        .as_ref()
+        .ok_or(ReadoutError::MetricNotAvailable)?
+        .value()?
+    {
+        CtlValue::Int(value) => Ok(value as u64),
+        CtlValue::Uint(value) => Ok(value as u64),
+        CtlValue::Long(value) => Ok(value as u64),
+        CtlValue::Ulong(value) => Ok(value),
+        CtlValue::S64(value) => Ok(value as u64),
+        CtlValue::U64(value) => Ok(value),
+        _ => Err(ReadoutError::MetricNotAvailable),
+    }
+}
+
+/// Returns the feature flags reported by the `CPUID` instruction, named after
+/// their Linux counterparts.
+#[cfg(target_arch = "x86_64")]
+// Older toolchains require unsafe for the CPUID intrinsics, which newer ones consider safe.
+#[allow(unused_unsafe)]
+fn cpuid_flags() -> Vec<String> {
+    use std::arch::x86_64::{__cpuid, __cpuid_count};
+
+    let mut flags = Vec::new();
+    let mut check = |register: u32, bits: &[(u32, &str)]| {
+        for (bit, flag) in bits {
+            if register & (1 << bit) != 0 {
+                flags.push(flag.to_string());
+            }
+        }
+    };
+
+    // SAFETY: CPUID is available on every x86_64 processor.
+    let max_leaf = unsafe { __cpuid(0) }.eax;
+    let leaf1 = unsafe { __cpuid(1) };
+
+    check(
+        leaf1.edx,
+        &[
+            (0, "fpu"),
+            (4, "tsc"),
+            (8, "cx8"),
+            (15, "cmov"),
+            (23, "mmx"),
+            (24, "fxsr"),
+            (25, "sse"),
+            (26, "sse2"),
+            (28, "ht"),
+        ],
+    );
+    check(
+        leaf1.ecx,
+        &[
+            (0, "pni"),
+            (1, "pclmulqdq"),
+            (9, "ssse3"),
+            (12, "fma"),
+            (13, "cx16"),
+            (19, "sse4_1"),
+            (20, "sse4_2"),
+            (22, "movbe"),
+            (23, "popcnt"),
+            (25, "aes"),
+            (26, "xsave"),
+            (28, "avx"),
+            (29, "f16c"),
+            (30, "rdrand"),
+        ],
+    );
+
+    if max_leaf >= 7 {
+        let leaf7 = unsafe { __cpuid_count(7, 0) };
+
+        check(
+            leaf7.ebx,
+            &[
+                (3, "bmi1"),
+                (5, "avx2"),
+                (8, "bmi2"),
+                (9, "erms"),
+                (16, "avx512f"),
+                (17, "avx512dq"),
+                (18, "rdseed"),
+                (19, "adx"),
+                (29, "sha_ni"),
+                (30, "avx512bw"),
+                (31, "avx512vl"),
+            ],
+        );
+        check(leaf7.ecx, &[(9, "vaes"), (10, "vpclmulqdq")]);
+    }
+
+    flags.sort();
+    flags
+}
+
+// This function returns the PPID of a given PID:
+//  - The file used to extract this data: /proc/<pid>/status
+//  - The format of the file is: command_name command_pid command_ppid ...
+fn get_parent(pid: i32) -> i32 {
+    let process_path = PathBuf::from("/proc").join(pid.to_string()).join("status");
+    if let Ok(content) = fs::read_to_string(process_path) {
+        if let Some(val) = content.split_whitespace().nth(2) {
+            if let Ok(c) = val.parse::<i32>() {
+                return c;
+            }
+        }
+
+        return -1;
+    }
+
+    -1
+}
+
+/// Provides access to the process tree through /proc/<pid>/status.
+struct ProcFs;
+
+impl shared::ProcessTree for ProcFs {
+    fn parent(&self, pid: i32) -> Option<i32> {
+        Some(get_parent(pid)).filter(|&ppid| ppid > 0)
+    }
+
+    fn name(&self, pid: i32) -> Option<String> {
+        let status =
+            fs::read_to_string(PathBuf::from("/proc").join(pid.to_string()).join("status"));
+        Some(status.ok()?.split_whitespace().next()?.to_string())
+    }
+}
+
+// This function returns the terminal inside of which the user is operating
+fn terminal_process() -> Option<shared::Terminal> {
+    shared::find_terminal(&ProcFs, get_parent(unsafe { libc::getppid() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_battery_state() {
+        assert!(matches!(battery_state(0), Ok(BatteryState::Discharging)));
+        assert!(matches!(battery_state(1), Ok(BatteryState::Discharging)));
+        assert!(matches!(battery_state(2), Ok(BatteryState::Charging)));
+        assert!(matches!(battery_state(4), Ok(BatteryState::Discharging)));
+        assert!(matches!(battery_state(5), Ok(BatteryState::Discharging)));
+        assert!(matches!(battery_state(6), Ok(BatteryState::Charging)));
+        assert!(matches!(battery_state(3), Err(ReadoutError::Other(_))));
+        assert!(matches!(
+            battery_state(7),
+            Err(ReadoutError::MetricUnavailableForHardware(_))
+        ));
+        assert!(matches!(
+            battery_state(-1),
+            Err(ReadoutError::MetricUnavailableForHardware(_))
+        ));
     }
 }
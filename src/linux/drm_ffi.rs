@@ -0,0 +1,241 @@
+//! A minimal binding to the DRM mode-setting ioctls, mirroring the structs
+//! declared in `include/uapi/drm/drm_mode.h`.
+#![allow(non_camel_case_types, dead_code)]
+
+use std::fs::File;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+const DRM_IOCTL_MODE_GETRESOURCES: u64 = 0xC040_64A0;
+const DRM_IOCTL_MODE_GETCRTC: u64 = 0xC068_64A1;
+const DRM_IOCTL_MODE_GETENCODER: u64 = 0xC014_64A6;
+const DRM_IOCTL_MODE_GETCONNECTOR: u64 = 0xC050_64A7;
+
+const DRM_MODE_CONNECTED: u32 = 1;
+const DRM_MODE_FLAG_INTERLACE: u32 = 1 << 4;
+const DRM_MODE_FLAG_DBLSCAN: u32 = 1 << 5;
+
+/// Connector type names, indexed by `DRM_MODE_CONNECTOR_*`.
+const CONNECTOR_TYPES: [&str; 21] = [
+    "Unknown",
+    "VGA",
+    "DVI-I",
+    "DVI-D",
+    "DVI-A",
+    "Composite",
+    "SVIDEO",
+    "LVDS",
+    "Component",
+    "DIN",
+    "DP",
+    "HDMI-A",
+    "HDMI-B",
+    "TV",
+    "eDP",
+    "Virtual",
+    "DSI",
+    "DPI",
+    "Writeback",
+    "SPI",
+    "USB",
+];
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+struct drm_mode_card_res {
+    fb_id_ptr: u64,
+    crtc_id_ptr: u64,
+    connector_id_ptr: u64,
+    encoder_id_ptr: u64,
+    count_fbs: u32,
+    count_crtcs: u32,
+    count_connectors: u32,
+    count_encoders: u32,
+    min_width: u32,
+    max_width: u32,
+    min_height: u32,
+    max_height: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct drm_mode_modeinfo {
+    pub clock: u32,
+    pub hdisplay: u16,
+    pub hsync_start: u16,
+    pub hsync_end: u16,
+    pub htotal: u16,
+    pub hskew: u16,
+    pub vdisplay: u16,
+    pub vsync_start: u16,
+    pub vsync_end: u16,
+    pub vtotal: u16,
+    pub vscan: u16,
+    pub vrefresh: u32,
+    pub flags: u32,
+    pub type_: u32,
+    pub name: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+struct drm_mode_crtc {
+    set_connectors_ptr: u64,
+    count_connectors: u32,
+    crtc_id: u32,
+    fb_id: u32,
+    x: u32,
+    y: u32,
+    gamma_size: u32,
+    mode_valid: u32,
+    mode: drm_mode_modeinfo,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+struct drm_mode_get_encoder {
+    encoder_id: u32,
+    encoder_type: u32,
+    crtc_id: u32,
+    possible_crtcs: u32,
+    possible_clones: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+struct drm_mode_get_connector {
+    encoders_ptr: u64,
+    modes_ptr: u64,
+    props_ptr: u64,
+    prop_values_ptr: u64,
+    count_modes: u32,
+    count_props: u32,
+    count_encoders: u32,
+    encoder_id: u32,
+    connector_id: u32,
+    connector_type: u32,
+    connector_type_id: u32,
+    connection: u32,
+    mm_width: u32,
+    mm_height: u32,
+    subpixel: u32,
+    pad: u32,
+}
+
+/// The mode a connector is currently being driven at.
+#[derive(Debug, Clone)]
+pub struct CurrentMode {
+    /// The connector's name, _e.g._ `HDMI-A-1`
+    pub connector: String,
+    pub mode: drm_mode_modeinfo,
+}
+
+impl drm_mode_modeinfo {
+    /// Computes the refresh rate from the pixel clock, falling back to
+    /// the rounded value reported by the kernel.
+    pub fn refresh_rate(&self) -> f32 {
+        let (htotal, vtotal) = (self.htotal as f32, self.vtotal as f32);
+        if self.clock == 0 || htotal == 0.0 || vtotal == 0.0 {
+            return self.vrefresh as f32;
+        }
+
+        let mut refresh = self.clock as f32 * 1000.0 / (htotal * vtotal);
+
+        if self.flags & DRM_MODE_FLAG_INTERLACE != 0 {
+            refresh *= 2.0;
+        }
+
+        if self.flags & DRM_MODE_FLAG_DBLSCAN != 0 {
+            refresh /= 2.0;
+        }
+
+        if self.vscan > 1 {
+            refresh /= self.vscan as f32;
+        }
+
+        refresh
+    }
+}
+
+fn ioctl<T>(file: &File, request: u64, arg: &mut T) -> bool {
+    unsafe { libc::ioctl(file.as_raw_fd(), request as _, arg as *mut T) == 0 }
+}
+
+/// Returns the current mode of every connected connector of the given
+/// `/dev/dri/card*` node which is driven by a CRTC.
+///
+/// `None` is returned if the node can't be opened, _e.g._ because the user
+/// lacks the permissions to do so.
+pub fn current_modes(card: &Path) -> Option<Vec<CurrentMode>> {
+    let file = File::open(card).ok()?;
+
+    // The first call only fills in the number of objects.
+    let mut resources = drm_mode_card_res::default();
+    if !ioctl(&file, DRM_IOCTL_MODE_GETRESOURCES, &mut resources) {
+        return None;
+    }
+
+    let mut connector_ids = vec![0u32; resources.count_connectors as usize];
+    resources = drm_mode_card_res {
+        connector_id_ptr: connector_ids.as_mut_ptr() as u64,
+        count_connectors: connector_ids.len() as u32,
+        ..Default::default()
+    };
+
+    if !ioctl(&file, DRM_IOCTL_MODE_GETRESOURCES, &mut resources) {
+        return None;
+    }
+
+    // A connector may have been hot-plugged in between both calls.
+    connector_ids.truncate(resources.count_connectors as usize);
+
+    let mut modes = Vec::new();
+
+    for connector_id in connector_ids {
+        // Passing a non-zero mode count prevents the kernel from probing the
+        // connector, which can take a considerable amount of time.
+        let mut probe_mode = drm_mode_modeinfo::default();
+        let mut connector = drm_mode_get_connector {
+            connector_id,
+            modes_ptr: &mut probe_mode as *mut drm_mode_modeinfo as u64,
+            count_modes: 1,
+            ..Default::default()
+        };
+
+        if !ioctl(&file, DRM_IOCTL_MODE_GETCONNECTOR, &mut connector)
+            || connector.connection != DRM_MODE_CONNECTED
+            || connector.encoder_id == 0
+        {
+            continue;
+        }
+
+        let mut encoder = drm_mode_get_encoder {
+            encoder_id: connector.encoder_id,
+            ..Default::default()
+        };
+
+        if !ioctl(&file, DRM_IOCTL_MODE_GETENCODER, &mut encoder) || encoder.crtc_id == 0 {
+            continue;
+        }
+
+        let mut crtc = drm_mode_crtc {
+            crtc_id: encoder.crtc_id,
+            ..Default::default()
+        };
+
+        if !ioctl(&file, DRM_IOCTL_MODE_GETCRTC, &mut crtc) || crtc.mode_valid == 0 {
+            continue;
+        }
+
+        let connector_type = CONNECTOR_TYPES
+            .get(connector.connector_type as usize)
+            .unwrap_or(&"Unknown");
+
+        modes.push(CurrentMode {
+            connector: format!("{connector_type}-{}", connector.connector_type_id),
+            mode: crtc.mode,
+        });
+    }
+
+    Some(modes)
+}
@@ -1,6 +1,8 @@
 #![allow(clippy::unnecessary_cast)]
+mod drm_ffi;
 mod pci_devices;
 mod sysinfo_ffi;
+mod wayland;
 
 use self::pci_devices::get_pci_devices;
 use crate::extra;
@@ -44,7 +46,9 @@ pub struct LinuxMemoryReadout {
 pub struct LinuxBatteryReadout;
 pub struct LinuxProductReadout;
 pub struct LinuxPackageReadout;
-pub struct LinuxNetworkReadout;
+pub struct LinuxNetworkReadout {
+    default_interface: shared::DefaultInterface,
+}
 
 impl BatteryReadout for LinuxBatteryReadout {
     fn new() -> Self {
@@ -52,117 +56,216 @@ impl BatteryReadout for LinuxBatteryReadout {
     }
 
     fn percentage(&self) -> Result<u8, ReadoutError> {
-        if let Some(entries) = get_entries(Path::new("/sys/class/power_supply")) {
-            let dirs: Vec<PathBuf> = entries
-                .into_iter()
-                .filter(|x| {
-                    x.components()
-                        .last()
-                        .unwrap()
-                        .as_os_str()
-                        .to_string_lossy()
-                        .starts_with("BAT")
-                })
-                .collect();
-
-            if let Some(battery) = dirs.first() {
-                let path_to_capacity = battery.join("capacity");
-                let percentage_text = extra::pop_newline(fs::read_to_string(path_to_capacity)?);
-                let percentage_parsed = percentage_text.parse::<u8>();
+        let batteries = LinuxBatteryReadout::batteries()?;
+
+        if let [battery] = &batteries[..] {
+            let path_to_capacity = battery.join("capacity");
+            let percentage_text = extra::pop_newline(fs::read_to_string(path_to_capacity)?);
+            let percentage_parsed = percentage_text.parse::<u8>();
+
+            return match percentage_parsed {
+                Ok(p) => Ok(p),
+                Err(e) => Err(ReadoutError::Other(format!(
+                    "Could not parse the value '{percentage_text}' into a digit: {e:?}"
+                ))),
+            };
+        }
 
-                match percentage_parsed {
-                    Ok(p) => return Ok(p),
-                    Err(e) => {
-                        return Err(ReadoutError::Other(format!(
-                            "Could not parse the value '{percentage_text}' into a digit: {e:?}"
-                        )))
-                    }
-                };
-            }
-        };
+        // The capacity of each pack is weighted by how much energy it is able to hold.
+        let (now, full) = batteries
+            .iter()
+            .try_fold((0.0, 0.0), |(total_now, total_full), battery| {
+                let (now, full) = read_battery_value(battery, "energy_now")
+                    .zip(read_battery_value(battery, "energy_full"))
+                    .or_else(|| {
+                        read_battery_value(battery, "charge_now")
+                            .zip(read_battery_value(battery, "charge_full"))
+                    })?;
+
+                Some((total_now + now, total_full + full))
+            })
+            .ok_or_else(|| {
+                ReadoutError::Other(String::from(
+                    "Could not read the batteries' energy or charge values.",
+                ))
+            })?;
+
+        if full == 0.0 {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
 
-        Err(ReadoutError::Other("No batteries detected.".to_string()))
+        Ok((now / full * 100.0).round().min(100.0) as u8)
     }
 
     fn status(&self) -> Result<BatteryState, ReadoutError> {
-        if let Some(entries) = get_entries(Path::new("/sys/class/power_supply")) {
-            let dirs: Vec<PathBuf> = entries
-                .into_iter()
-                .filter(|x| {
-                    x.components()
-                        .last()
-                        .unwrap()
-                        .as_os_str()
-                        .to_string_lossy()
-                        .starts_with("BAT")
-                })
-                .collect();
-
-            if let Some(battery) = dirs.first() {
-                let path_to_status = battery.join("status");
-                let status_text =
-                    extra::pop_newline(fs::read_to_string(path_to_status)?).to_lowercase();
-
-                match &status_text[..] {
-                    "charging" => return Ok(BatteryState::Charging),
-                    "discharging" | "full" => return Ok(BatteryState::Discharging),
-                    s => {
-                        return Err(ReadoutError::Other(format!(
-                            "Got an unexpected value \"{s}\" reading battery status"
-                        )))
-                    }
+        let mut state = None;
+        let mut unexpected = None;
+
+        for battery in LinuxBatteryReadout::batteries()? {
+            let path_to_status = battery.join("status");
+            let status_text =
+                extra::pop_newline(fs::read_to_string(path_to_status)?).to_lowercase();
+
+            match &status_text[..] {
+                "charging" => return Ok(BatteryState::Charging),
+                "discharging" | "full" => state = Some(BatteryState::Discharging),
+                // An idle pack, e.g. one reporting "not charging", is expected while the
+                // other one is in use.
+                _ => {
+                    unexpected.get_or_insert(status_text);
                 }
             }
         }
 
-        Err(ReadoutError::Other("No batteries detected.".to_string()))
+        state.ok_or_else(|| {
+            ReadoutError::Other(format!(
+                "Got an unexpected value \"{}\" reading battery status",
+                unexpected.unwrap_or_default()
+            ))
+        })
     }
 
     fn health(&self) -> Result<u8, ReadoutError> {
-        if let Some(entries) = get_entries(Path::new("/sys/class/power_supply")) {
-            let dirs: Vec<PathBuf> = entries
-                .into_iter()
-                .filter(|x| {
-                    !x.components()
-                        .last()
-                        .unwrap()
-                        .as_os_str()
-                        .to_string_lossy()
-                        .starts_with("ADP")
-                })
-                .collect();
-
-            if let Some(battery) = dirs.first() {
-                let energy_full =
-                    extra::pop_newline(fs::read_to_string(battery.join("energy_full"))?)
-                        .parse::<u8>();
-
-                let energy_full_design =
-                    extra::pop_newline(fs::read_to_string(battery.join("energy_full_design"))?)
-                        .parse::<u8>();
+        let (full, design) = LinuxBatteryReadout::batteries()?
+            .iter()
+            .try_fold((0.0, 0.0), |(total_full, total_design), battery| {
+                let (full, design) = read_battery_value(battery, "energy_full")
+                    .zip(read_battery_value(battery, "energy_full_design"))
+                    .or_else(|| {
+                        read_battery_value(battery, "charge_full")
+                            .zip(read_battery_value(battery, "charge_full_design"))
+                    })?;
+
+                Some((total_full + full, total_design + design))
+            })
+            .ok_or_else(|| ReadoutError::Other("Error calculating battery health.".to_string()))?;
+
+        if design == 0.0 {
+            return Err(ReadoutError::Other(
+                "Error calculating battery health.".to_string(),
+            ));
+        }
 
-                match (energy_full, energy_full_design) {
-                    (Ok(mut ef), Ok(efd)) => {
-                        if ef > efd {
-                            ef = efd;
-                            return Ok((ef as f32 / efd as f32 * 100_f32).ceil() as u8);
-                        }
+        Ok((full.min(design) / design * 100.0).ceil() as u8)
+    }
 
-                        return Ok((ef as f32 / efd as f32 * 100_f32).ceil() as u8);
-                    }
+    fn time_remaining(&self) -> Result<usize, ReadoutError> {
+        let batteries = LinuxBatteryReadout::batteries()?;
+        let (mut now, mut full, mut rate) = (0.0, 0.0, 0.0);
+
+        for battery in &batteries {
+            // Newer kernels expose energy_* (µWh) and power_now (µW), while older
+            // ones expose charge_* (µAh) and current_now (µA) instead.
+            let (pack_now, pack_full, pack_rate) = match (
+                read_battery_value(battery, "energy_now"),
+                read_battery_value(battery, "energy_full"),
+                read_battery_value(battery, "power_now"),
+            ) {
+                (Some(now), Some(full), Some(rate)) => (now, full, rate),
+                _ => match (
+                    read_battery_value(battery, "charge_now"),
+                    read_battery_value(battery, "charge_full"),
+                    read_battery_value(battery, "current_now"),
+                ) {
+                    (Some(now), Some(full), Some(rate)) => (now, full, rate),
                     _ => {
-                        return Err(ReadoutError::Other(
-                            "Error calculating battery health.".to_string(),
-                        ))
+                        return Err(ReadoutError::Other(String::from(
+                            "Could not read the battery's energy or charge values.",
+                        )))
                     }
-                }
-            }
+                },
+            };
+
+            now += pack_now;
+            full += pack_full;
+            // Some drivers report a negative rate while discharging.
+            rate += pack_rate.abs();
+        }
+
+        if rate == 0.0 {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        let remaining = match self.status()? {
+            BatteryState::Charging => (full - now).max(0.0),
+            BatteryState::Discharging => now,
+        };
+
+        Ok((remaining / rate * 3600.0).round() as usize)
+    }
+
+    fn temperature(&self) -> Result<f32, ReadoutError> {
+        let batteries = LinuxBatteryReadout::batteries()?;
+
+        read_battery_temperature(&batteries[0])
+    }
+
+    fn power_draw(&self) -> Result<f32, ReadoutError> {
+        let watts = LinuxBatteryReadout::batteries()?
+            .iter()
+            .try_fold(0.0, |total, battery| {
+                Some(total + read_power_draw(battery)?)
+            })
+            .ok_or(ReadoutError::MetricNotAvailable)?;
+
+        match self.status()? {
+            BatteryState::Charging => Ok(-watts as f32),
+            BatteryState::Discharging => Ok(watts as f32),
+        }
+    }
+}
+
+impl LinuxBatteryReadout {
+    /// Returns the `power_supply` directories of the batteries that are present, sorted by
+    /// name, or an error if there are none.
+    fn batteries() -> Result<Vec<PathBuf>, ReadoutError> {
+        let batteries: Vec<PathBuf> = get_entries(Path::new("/sys/class/power_supply"))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|x| {
+                x.components()
+                    .next_back()
+                    .unwrap()
+                    .as_os_str()
+                    .to_string_lossy()
+                    .starts_with("BAT")
+            })
+            // Removable packs keep their entry around when they're taken out.
+            .filter(|x| read_battery_value(x, "present") != Some(0.0))
+            .sorted()
+            .collect();
+
+        if batteries.is_empty() {
+            return Err(ReadoutError::MetricUnavailableForHardware(
+                "No batteries detected.".to_string(),
+            ));
         }
 
-        Err(ReadoutError::Other("No batteries detected.".to_string()))
+        Ok(batteries)
     }
 }
 
+/// Reads the absolute power draw, in watts, of the battery whose `power_supply` directory is
+/// given, using either `power_now` (µW), or `voltage_now` (µV) and `current_now` (µA).
+fn read_power_draw(battery: &Path) -> Option<f64> {
+    // Some drivers report negative values while discharging.
+    if let Some(microwatts) = read_battery_value(battery, "power_now") {
+        return Some(microwatts.abs() / 1_000_000.0);
+    }
+
+    let microvolts = read_battery_value(battery, "voltage_now")?;
+    let microamps = read_battery_value(battery, "current_now")?;
+
+    Some((microvolts * microamps).abs() / 1_000_000_000_000.0)
+}
+
+/// Reads a numeric attribute of the battery whose `power_supply` directory is given.
+fn read_battery_value(battery: &Path, file: &str) -> Option<f64> {
+    fs::read_to_string(battery.join(file))
+        .ok()
+        .and_then(|v| extra::pop_newline(v).parse::<f64>().ok())
+}
+
 impl KernelReadout for LinuxKernelReadout {
     fn new() -> Self {
         LinuxKernelReadout {
@@ -186,95 +289,116 @@ impl KernelReadout for LinuxKernelReadout {
             .ok_or(ReadoutError::MetricNotAvailable)?
             .value_string()?)
     }
+
+    fn architecture(&self) -> Result<String, ReadoutError> {
+        shared::architecture()
+    }
 }
 
 impl NetworkReadout for LinuxNetworkReadout {
     fn new() -> Self {
-        LinuxNetworkReadout
+        LinuxNetworkReadout {
+            default_interface: shared::DefaultInterface::default(),
+        }
     }
 
     fn tx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
-        if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net")
-                .join(ifname)
-                .join("statistics/tx_bytes");
-            let content = std::fs::read_to_string(rx_file)?;
-            let bytes = extra::pop_newline(content)
-                .parse::<usize>()
-                .unwrap_or_default();
-            Ok(bytes)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
-        }
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "tx_bytes")
     }
 
     fn tx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
-        if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net")
-                .join(ifname)
-                .join("statistics/tx_packets");
-            let content = std::fs::read_to_string(rx_file)?;
-            let packets = extra::pop_newline(content)
-                .parse::<usize>()
-                .unwrap_or_default();
-            Ok(packets)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
-        }
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "tx_packets")
     }
 
     fn rx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
-        if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net")
-                .join(ifname)
-                .join("statistics/rx_bytes");
-            let content = std::fs::read_to_string(rx_file)?;
-            let bytes = extra::pop_newline(content)
-                .parse::<usize>()
-                .unwrap_or_default();
-            Ok(bytes)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
-        }
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "rx_bytes")
     }
 
     fn rx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
-        if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net")
-                .join(ifname)
-                .join("statistics/rx_packets");
-            let content = std::fs::read_to_string(rx_file)?;
-            let packets = extra::pop_newline(content)
-                .parse::<usize>()
-                .unwrap_or_default();
-            Ok(packets)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
-        }
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "rx_packets")
     }
 
     fn physical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        shared::interface_address(self.default_interface.resolve(interface)?)
+    }
+
+    fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        shared::logical_address(Some(self.default_interface.resolve(interface)?))
+    }
+
+    fn ssid(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        let ifname = LinuxNetworkReadout::wireless_interface(interface)?;
+        let (ssid, _) = LinuxNetworkReadout::iw_link(&ifname)?;
+
+        ssid.ok_or(ReadoutError::MetricNotAvailable)
+    }
+
+    fn signal_strength(&self, interface: Option<&str>) -> Result<i32, ReadoutError> {
+        let ifname = LinuxNetworkReadout::wireless_interface(interface)?;
+
+        if let Some(signal) = fs::read_to_string("/proc/net/wireless")
+            .ok()
+            .and_then(|content| parse_proc_net_wireless(&content, &ifname))
+        {
+            return Ok(signal);
+        }
+
+        let (_, signal) = LinuxNetworkReadout::iw_link(&ifname)?;
+
+        signal.ok_or(ReadoutError::MetricNotAvailable)
+    }
+
+    fn default_gateway(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        let content = fs::read_to_string("/proc/net/route")?;
+
+        shared::parse_proc_net_route(&content, interface)
+            .map(|(_, gateway)| gateway.to_string())
+            .ok_or(ReadoutError::MetricNotAvailable)
+    }
+
+    fn dns_servers(&self) -> Result<Vec<String>, ReadoutError> {
+        shared::dns_servers()
+    }
+
+    fn link_speed(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        read_link_speed(&PathBuf::from("/sys/class/net").join(ifname))
+    }
+}
+
+impl LinuxNetworkReadout {
+    /// Returns the given interface if it is a wireless interface that is up, or the first such
+    /// interface if none is given.
+    fn wireless_interface(interface: Option<&str>) -> Result<String, ReadoutError> {
+        let is_wireless_and_up = |path: &Path| {
+            path.join("wireless").is_dir()
+                && fs::read_to_string(path.join("operstate"))
+                    .map(|state| state.trim() == "up")
+                    .unwrap_or(false)
+        };
+
         if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net").join(ifname).join("address");
-            let content = std::fs::read_to_string(rx_file)?;
-            Ok(content)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
+            if is_wireless_and_up(&Path::new("/sys/class/net").join(ifname)) {
+                return Ok(ifname.to_string());
+            }
+
+            return Err(ReadoutError::MetricNotAvailable);
         }
+
+        get_entries(Path::new("/sys/class/net"))
+            .unwrap_or_default()
+            .into_iter()
+            .sorted()
+            .find(|path| is_wireless_and_up(path))
+            .and_then(|path| path.file_name()?.to_str().map(String::from))
+            .ok_or(ReadoutError::MetricNotAvailable)
     }
 
-    fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
-        shared::logical_address(interface)
+    /// Returns the SSID and signal strength reported by `iw dev <interface> link`.
+    fn iw_link(ifname: &str) -> Result<(Option<String>, Option<i32>), ReadoutError> {
+        let output = shared::command_output(Path::new("iw"), &["dev", ifname, "link"])?;
+
+        Ok(parse_iw_link(&output))
     }
 }
 
@@ -287,62 +411,86 @@ impl GeneralReadout for LinuxGeneralReadout {
     }
 
     fn backlight(&self) -> Result<usize, ReadoutError> {
-        if let Some(base) = get_entries(Path::new("/sys/class/backlight/")) {
-            if let Some(backlight_path) = base.into_iter().next() {
-                let max_brightness_path = backlight_path.join("max_brightness");
-                let current_brightness_path = backlight_path.join("brightness");
+        let base = Path::new("/sys/class/backlight/");
 
-                let max_brightness_value =
-                    extra::pop_newline(fs::read_to_string(max_brightness_path)?)
-                        .parse::<usize>()
-                        .ok();
+        match backlight_devices(base).first() {
+            Some(device) => read_backlight(device),
+            None => Err(ReadoutError::Other(String::from(
+                "Could not obtain backlight information.",
+            ))),
+        }
+    }
 
-                let current_brightness_value =
-                    extra::pop_newline(fs::read_to_string(current_brightness_path)?)
-                        .parse::<usize>()
-                        .ok();
+    fn backlights(&self) -> Result<Vec<(String, usize)>, ReadoutError> {
+        let devices = backlight_devices(Path::new("/sys/class/backlight/"));
 
-                match (current_brightness_value, max_brightness_value) {
-                    (Some(c), Some(m)) => {
-                        let brightness = c as f64 / m as f64 * 100f64;
-                        return Ok(brightness.round() as usize);
-                    }
-                    _ => {
-                        return Err(ReadoutError::Other(String::from(
-                            "Error occurred while calculating backlight (brightness) value.",
-                        )));
-                    }
-                }
-            }
+        if devices.is_empty() {
+            return Err(ReadoutError::Other(String::from(
+                "Could not obtain backlight information.",
+            )));
         }
 
-        Err(ReadoutError::Other(String::from(
-            "Could not obtain backlight information.",
-        )))
+        Ok(devices
+            .iter()
+            .filter_map(|device| {
+                let name = device.file_name()?.to_string_lossy().into_owned();
+                Some((name, read_backlight(device).ok()?))
+            })
+            .collect())
     }
 
     fn resolution(&self) -> Result<String, ReadoutError> {
-        let drm = Path::new("/sys/class/drm");
-
-        if let Some(entries) = get_entries(drm) {
-            let mut resolutions: Vec<String> = Vec::new();
-            entries.into_iter().for_each(|entry| {
-                // Append "modes" to /sys/class/drm/<card>/
-                let modes = entry.join("modes");
-                if let Ok(file) = File::open(modes) {
-                    // Push the resolution to the resolutions vector.
-                    if let Some(Ok(res)) = BufReader::new(file).lines().next() {
-                        resolutions.push(res);
-                    }
-                }
-            });
+        Ok(self
+            .displays()?
+            .iter()
+            .map(|display| match display.refresh_rate {
+                Some(refresh_rate) => format!(
+                    "{}x{}@{}Hz",
+                    display.width,
+                    display.height,
+                    refresh_rate.round()
+                ),
+                None => format!("{}x{}", display.width, display.height),
+            })
+            .join(", "))
+    }
 
-            return Ok(resolutions.join(", "));
+    fn displays(&self) -> Result<Vec<Display>, ReadoutError> {
+        let mut errors: Vec<&str> = Vec::with_capacity(3);
+
+        // Querying the DRM nodes directly yields the mode each connector is
+        // currently driven at, but requires access to /dev/dri/card*.
+        match drm_displays()
+            .filter(|displays| !displays.is_empty())
+            .or_else(|| sysfs_displays(Path::new("/sys/class/drm")))
+        {
+            Some(displays) if !displays.is_empty() => return Ok(displays),
+            Some(_) => errors.push("DRM: no connected displays were found"),
+            None => errors.push("DRM: /sys/class/drm could not be read"),
         }
 
-        Err(ReadoutError::Other(
-            "Could not obtain screen resolution from /sys/class/drm".to_string(),
-        ))
+        // The compositor knows the geometry of its outputs even when the kernel
+        // doesn't expose their modes, e.g. with simpledrm or some NVIDIA setups.
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            match wayland::displays() {
+                Some(displays) if !displays.is_empty() => return Ok(displays),
+                Some(_) => errors.push("Wayland: the compositor advertised no outputs"),
+                None => errors.push("Wayland: could not connect to the compositor"),
+            }
+        } else {
+            errors.push("Wayland: no session is running");
+        }
+
+        let randr_error = match shared::x11_displays() {
+            Ok(displays) if !displays.is_empty() => return Ok(displays),
+            Ok(_) => String::from("no connected outputs were found"),
+            Err(e) => e.to_string(),
+        };
+
+        Err(ReadoutError::Other(format!(
+            "Could not obtain screen resolution ({}; RandR: {randr_error})",
+            errors.join("; ")
+        )))
     }
 
     fn username(&self) -> Result<String, ReadoutError> {
@@ -374,205 +522,1273 @@ impl GeneralReadout for LinuxGeneralReadout {
         shared::desktop_environment()
     }
 
-    fn session(&self) -> Result<String, ReadoutError> {
-        shared::session()
+    fn desktop_environment_version(&self) -> Result<String, ReadoutError> {
+        shared::desktop_environment_version()
+    }
+
+    fn session(&self) -> Result<String, ReadoutError> {
+        shared::session()
+    }
+
+    fn window_manager(&self) -> Result<String, ReadoutError> {
+        shared::window_manager()
+    }
+
+    fn theme(&self) -> Result<String, ReadoutError> {
+        desktop_theme(
+            "gtk-theme",
+            &[("General", "ColorScheme"), ("KDE", "widgetStyle")],
+            "gtk-theme-name",
+        )
+    }
+
+    fn icon_theme(&self) -> Result<String, ReadoutError> {
+        desktop_theme("icon-theme", &[("Icons", "Theme")], "gtk-icon-theme-name")
+    }
+
+    fn terminal(&self) -> Result<String, ReadoutError> {
+        let terminal = terminal_process()
+            .map(|terminal| terminal.to_string())
+            .unwrap_or_default();
+
+        if terminal.is_empty() {
+            return Err(ReadoutError::Other(
+                "Querying terminal information failed".to_string(),
+            ));
+        }
+
+        Ok(terminal)
+    }
+
+    fn terminal_version(&self) -> Result<String, ReadoutError> {
+        let terminal = terminal_process().ok_or(ReadoutError::MetricNotAvailable)?;
+        let exe = fs::read_link(
+            PathBuf::from("/proc")
+                .join(terminal.pid.to_string())
+                .join("exe"),
+        )
+        .ok();
+
+        shared::terminal_version(&terminal.name, exe.as_deref())
+    }
+
+    fn shell(&self, format: ShellFormat, kind: ShellKind) -> Result<String, ReadoutError> {
+        shared::shell(format, kind)
+    }
+
+    fn cpu_model_name(&self) -> Result<String, ReadoutError> {
+        Ok(shared::cpu_model_name())
+    }
+
+    fn cpu_vendor(&self) -> Result<String, ReadoutError> {
+        parse_cpuinfo_vendor(&fs::read_to_string("/proc/cpuinfo")?)
+            .ok_or(ReadoutError::MetricNotAvailable)
+    }
+
+    fn cpu_flags(&self) -> Result<Vec<String>, ReadoutError> {
+        let flags = parse_cpuinfo_flags(&fs::read_to_string("/proc/cpuinfo")?);
+
+        if flags.is_empty() {
+            Err(ReadoutError::MetricNotAvailable)
+        } else {
+            Ok(flags)
+        }
+    }
+
+    fn cpu_usage(&self) -> Result<usize, ReadoutError> {
+        let snapshot = || {
+            fs::read_to_string("/proc/stat")
+                .ok()
+                .and_then(|content| parse_proc_stat(&content))
+        };
+
+        if let Some(first) = snapshot() {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+
+            if let Some(usage) = snapshot().and_then(|second| first.usage_until(&second)) {
+                return Ok(usage);
+            }
+        }
+
+        // The load average lags behind the actual usage, hence why it's only
+        // used as a fallback.
+        let mut info = self.sysinfo;
+        let info_ptr: *mut sysinfo = &mut info;
+        let ret = unsafe { sysinfo(info_ptr) };
+
+        if ret != -1 {
+            let f_load = 1f64 / (1 << libc::SI_LOAD_SHIFT) as f64;
+            let cpu_usage = info.loads[0] as f64 * f_load;
+            let cpu_usage_u = (cpu_usage / self.cpu_cores()? as f64 * 100.0).round() as usize;
+            return Ok(cpu_usage_u as usize);
+        }
+
+        Err(ReadoutError::Other(
+            "Something went wrong during the initialization of the sysinfo struct.".to_string(),
+        ))
+    }
+
+    fn load_average(&self) -> Result<(f64, f64, f64), ReadoutError> {
+        let mut info = self.sysinfo;
+        let info_ptr: *mut sysinfo = &mut info;
+        let ret = unsafe { sysinfo(info_ptr) };
+
+        if ret != -1 {
+            let f_load = 1f64 / (1 << libc::SI_LOAD_SHIFT) as f64;
+            return Ok((
+                info.loads[0] as f64 * f_load,
+                info.loads[1] as f64 * f_load,
+                info.loads[2] as f64 * f_load,
+            ));
+        }
+
+        Err(ReadoutError::Other(
+            "Something went wrong during the initialization of the sysinfo struct.".to_string(),
+        ))
+    }
+
+    fn cpu_physical_cores(&self) -> Result<usize, ReadoutError> {
+        use std::io::{BufRead, BufReader};
+        if let Ok(content) = File::open("/proc/cpuinfo") {
+            let reader = BufReader::new(content);
+            for line in reader.lines().map_while(Result::ok) {
+                if line.to_lowercase().starts_with("cpu cores") {
+                    return Ok(line
+                        .split(':')
+                        .nth(1)
+                        .unwrap()
+                        .trim()
+                        .parse::<usize>()
+                        .unwrap());
+                }
+            }
+        }
+
+        Err(ReadoutError::MetricNotAvailable)
+    }
+
+    fn cpu_cores(&self) -> Result<usize, ReadoutError> {
+        Ok(unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) } as usize)
+    }
+
+    fn cpu_frequency(&self) -> Result<usize, ReadoutError> {
+        // cpufreq is the only source on aarch64 hosts, whose /proc/cpuinfo lacks the
+        // "cpu MHz" field, so it has to take precedence.
+        if let Ok(content) =
+            fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq")
+        {
+            if let Some(freq) = parse_cpufreq(&content) {
+                return Ok(freq);
+            }
+        }
+
+        // Virtual machines usually don't expose cpufreq.
+        if let Ok(content) = fs::read_to_string("/proc/cpuinfo") {
+            if let Some(freq) = parse_cpuinfo_mhz(&content) {
+                return Ok(freq);
+            }
+        }
+
+        Err(ReadoutError::MetricNotAvailable)
+    }
+
+    fn cpu_temperature(&self) -> Result<f32, ReadoutError> {
+        const HWMON_DRIVERS: [&str; 3] = ["coretemp", "k10temp", "cpu_thermal"];
+
+        if let Some(mut hwmons) = get_entries(Path::new("/sys/class/hwmon")) {
+            hwmons.sort();
+
+            for hwmon in hwmons {
+                let Ok(name) = fs::read_to_string(hwmon.join("name")) else {
+                    continue;
+                };
+
+                if !HWMON_DRIVERS.contains(&name.trim()) {
+                    continue;
+                }
+
+                // temp1_input is the package sensor for every driver listed above; the other
+                // inputs, if any, belong to individual cores or dies.
+                let inputs: Vec<PathBuf> = get_entries(&hwmon)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|path| {
+                        let name = path.file_name()?.to_str()?;
+                        let index = name.strip_prefix("temp")?.strip_suffix("_input")?;
+                        index.parse::<usize>().ok().map(|index| (index, path))
+                    })
+                    .sorted_by_key(|(index, _)| *index)
+                    .map(|(_, path)| path)
+                    .collect();
+
+                for input in inputs {
+                    if let Some(temp) = read_millidegrees(&input) {
+                        return Ok(temp);
+                    }
+                }
+            }
+        }
+
+        if let Some(mut zones) = get_entries(Path::new("/sys/class/thermal")) {
+            zones.sort();
+
+            for zone in zones {
+                let is_pkg_temp = fs::read_to_string(zone.join("type"))
+                    .map(|zone_type| zone_type.trim() == "x86_pkg_temp")
+                    .unwrap_or(false);
+
+                if is_pkg_temp {
+                    if let Some(temp) = read_millidegrees(&zone.join("temp")) {
+                        return Ok(temp);
+                    }
+                }
+            }
+        }
+
+        Err(ReadoutError::MetricNotAvailable)
+    }
+
+    fn uptime(&self) -> Result<usize, ReadoutError> {
+        let mut info = self.sysinfo;
+        let info_ptr: *mut sysinfo = &mut info;
+        let ret = unsafe { sysinfo(info_ptr) };
+
+        if ret != -1 {
+            return Ok(info.uptime as usize);
+        }
+
+        Err(ReadoutError::Other(
+            "Something went wrong during the initialization of the sysinfo struct.".to_string(),
+        ))
+    }
+
+    fn machine(&self) -> Result<String, ReadoutError> {
+        // Most ARM boards don't provide any DMI information.
+        if !Path::new("/sys/class/dmi/id").exists() {
+            return devicetree_machine();
+        }
+
+        let product_readout = LinuxProductReadout::new();
+
+        let vendor = product_readout.vendor()?;
+        let family = product_readout.family()?;
+        let product = product_readout.product()?;
+        let version = extra::pop_newline(fs::read_to_string("/sys/class/dmi/id/product_version")?);
+
+        shared::machine_name(&vendor, &family, &product, &version)
+    }
+
+    fn os_name(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    fn disk_space(&self, path: &Path) -> Result<(u64, u64), ReadoutError> {
+        shared::disk_space(path)
+    }
+
+    fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
+        let db = pci_database();
+        let mut gpus = vec![];
+
+        if let (Ok(db), Ok(devices)) = (&db, get_pci_devices()) {
+            for device in devices {
+                if !device.is_gpu(db) {
+                    continue;
+                };
+
+                if let Some(sub_device_name) = device.get_device_name(db) {
+                    gpus.push(sub_device_name);
+                };
+            }
+        }
+
+        // GPUs found on ARM boards are usually platform devices described
+        // by the devicetree rather than PCI devices.
+        if gpus.is_empty() {
+            gpus = platform_gpus(Path::new("/sys/class/drm"));
+        }
+
+        if gpus.is_empty() {
+            // Without pci.ids, PCI GPUs can't be named at all.
+            Err(db.err().unwrap_or(ReadoutError::MetricNotAvailable))
+        } else {
+            Ok(gpus)
+        }
+    }
+
+    fn gpu_drivers(&self) -> Result<Vec<String>, ReadoutError> {
+        let db = pci_database()?;
+
+        let drivers: Vec<String> = get_pci_devices()?
+            .into_iter()
+            .filter(|device| device.is_gpu(&db) && device.get_device_name(&db).is_some())
+            .map(|device| match device.driver() {
+                Some(driver) => match gpu_driver_version(&driver) {
+                    Some(version) => format!("{driver} ({version})"),
+                    None => driver,
+                },
+                None => String::from("(no driver)"),
+            })
+            .collect();
+
+        if drivers.is_empty() {
+            Err(ReadoutError::MetricNotAvailable)
+        } else {
+            Ok(drivers)
+        }
+    }
+
+    fn gpu_memory(&self) -> Result<Vec<(String, u64)>, ReadoutError> {
+        let db = pci_database()?;
+
+        let memory: Vec<(String, u64)> = get_pci_devices()?
+            .into_iter()
+            .filter(|device| device.is_gpu(&db))
+            .filter_map(|device| {
+                let name = device.get_device_name(&db)?;
+                let vram = read_gpu_memory(
+                    device.path(),
+                    device.driver().as_deref(),
+                    Path::new("/proc/driver/nvidia/gpus"),
+                )?;
+
+                Some((name, vram))
+            })
+            .collect();
+
+        if memory.is_empty() {
+            Err(ReadoutError::MetricNotAvailable)
+        } else {
+            Ok(memory)
+        }
+    }
+
+    fn disk_spaces(&self) -> Result<Vec<DiskSpace>, ReadoutError> {
+        let mounts = fs::read_to_string("/proc/self/mounts")?;
+
+        Ok(parse_mounts(&mounts)
+            .into_iter()
+            .filter_map(|(mount_point, fs_type)| {
+                let (used, total) = shared::disk_space(&mount_point).ok()?;
+
+                Some(DiskSpace {
+                    mount_point,
+                    fs_type,
+                    used,
+                    total,
+                })
+            })
+            .collect())
+    }
+
+    fn disks(&self) -> Result<Vec<Disk>, ReadoutError> {
+        let disks = block_devices(Path::new("/sys/block"));
+
+        if disks.is_empty() {
+            Err(ReadoutError::MetricNotAvailable)
+        } else {
+            Ok(disks)
+        }
+    }
+
+    fn disk_io(&self, all: bool) -> Result<Vec<DiskIo>, ReadoutError> {
+        let diskstats = fs::read_to_string("/proc/diskstats")?;
+
+        Ok(parse_diskstats(&diskstats)
+            .into_iter()
+            .filter(|disk| {
+                // Only whole disks are listed in /sys/block, where slashes
+                // in the device name are replaced by exclamation marks.
+                all || (!disk.name.starts_with("loop")
+                    && !disk.name.starts_with("ram")
+                    && Path::new("/sys/block")
+                        .join(disk.name.replace('/', "!"))
+                        .exists())
+            })
+            .collect())
+    }
+
+    fn locale(&self) -> Result<String, ReadoutError> {
+        if let Ok(locale) = shared::locale() {
+            return Ok(locale);
+        }
+
+        // The environment is scrubbed for cron jobs and some display managers, in which case
+        // the system-wide configuration is the next best thing.
+        for path in ["/etc/locale.conf", "/etc/default/locale"] {
+            if let Some(locale) = fs::read_to_string(path)
+                .ok()
+                .and_then(|content| parse_locale_conf(&content))
+            {
+                return Ok(locale);
+            }
+        }
+
+        Err(ReadoutError::MetricNotAvailable)
+    }
+
+    fn init_system(&self) -> Result<String, ReadoutError> {
+        let comm = fs::read_to_string("/proc/1/comm")?;
+        let comm = comm.trim();
+
+        match comm {
+            "systemd" => Ok(String::from("systemd")),
+            "openrc-init" => Ok(String::from("OpenRC")),
+            "runit" => Ok(String::from("runit")),
+            "s6-svscan" => Ok(String::from("s6")),
+            "dinit" => Ok(String::from("dinit")),
+            // Several init systems hand over to or are installed as a binary named "init", in
+            // which case their runtime directories give them away.
+            "init" => {
+                if Path::new("/run/systemd/system").is_dir() {
+                    Ok(String::from("systemd"))
+                } else if Path::new("/run/openrc").is_dir() {
+                    Ok(String::from("OpenRC"))
+                } else if Path::new("/run/runit").is_dir() {
+                    Ok(String::from("runit"))
+                } else {
+                    Ok(String::from("SysVinit"))
+                }
+            }
+            shell if extra::common_shells().contains(&shell) => Err(ReadoutError::Warning(
+                format!("PID 1 is the \"{shell}\" shell, this is likely a container."),
+            )),
+            other => Ok(other.to_string()),
+        }
+    }
+}
+
+/// The time spent by all CPUs since boot, as reported by the aggregate `cpu` line of
+/// `/proc/stat`, in clock ticks.
+#[derive(Debug, PartialEq)]
+struct CpuTimes {
+    busy: u64,
+    total: u64,
+}
+
+impl CpuTimes {
+    /// Returns the CPU usage, as a percentage, between this snapshot and a later one.
+    fn usage_until(&self, later: &CpuTimes) -> Option<usize> {
+        let total = later.total.checked_sub(self.total)?;
+        let busy = later.busy.saturating_sub(self.busy);
+
+        if total == 0 {
+            return None;
+        }
+
+        Some(((busy as f64 / total as f64 * 100.0).round() as usize).min(100))
+    }
+}
+
+/// Parses the aggregate `cpu` line of `/proc/stat`.
+fn parse_proc_stat(content: &str) -> Option<CpuTimes> {
+    let times: Vec<u64> = content
+        .lines()
+        .find(|line| line.split_whitespace().next() == Some("cpu"))?
+        .split_whitespace()
+        .skip(1)
+        .map(|time| time.parse::<u64>().ok())
+        .collect::<Option<_>>()?;
+
+    if times.len() < 4 {
+        return None;
+    }
+
+    // Fields: user, nice, system, idle, iowait, irq, softirq, steal, guest, guest_nice.
+    // Guest time is already accounted for in user and nice, so it's left out.
+    let total: u64 = times.iter().take(8).sum();
+    let idle = times[3] + times.get(4).copied().unwrap_or(0);
+
+    Some(CpuTimes {
+        busy: total - idle,
+        total,
+    })
+}
+
+/// Parses the contents of `cpuinfo_max_freq`, which is expressed in kHz, into MHz.
+fn parse_cpufreq(content: &str) -> Option<usize> {
+    content
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|&khz| khz != 0)
+        .map(|khz| khz / 1000)
+}
+
+/// Parses the first `cpu MHz` field found in the contents of `/proc/cpuinfo`.
+fn parse_cpuinfo_mhz(content: &str) -> Option<usize> {
+    content
+        .lines()
+        .find(|line| line.starts_with("cpu MHz"))
+        .and_then(|line| line.split(':').nth(1))
+        .and_then(|mhz| mhz.trim().parse::<f64>().ok())
+        .map(|mhz| mhz.round() as usize)
+}
+
+/// Reads the machine's model from the devicetree, falling back to `/proc/cpuinfo`.
+fn devicetree_machine() -> Result<String, ReadoutError> {
+    // e.g. "Raspberry Pi 4 Model B Rev 1.4\0"
+    if let Ok(model) = fs::read_to_string("/proc/device-tree/model") {
+        let model = model.trim_end_matches('\0').trim();
+        if !model.is_empty() {
+            return Ok(model.to_string());
+        }
+    }
+
+    parse_cpuinfo_model(&fs::read_to_string("/proc/cpuinfo")?)
+        .ok_or(ReadoutError::MetricNotAvailable)
+}
+
+/// Parses the CPU vendor from the contents of `/proc/cpuinfo`, using the `vendor_id`
+/// field on x86 and the `CPU implementer` field on ARM.
+fn parse_cpuinfo_vendor(content: &str) -> Option<String> {
+    let value_of = |field: &str| {
+        content.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == field).then(|| value.trim())
+        })
+    };
+
+    if let Some(vendor_id) = value_of("vendor_id") {
+        return Some(shared::cpu_vendor_name(vendor_id));
+    }
+
+    let implementer = value_of("CPU implementer")?;
+    let implementer = u8::from_str_radix(implementer.trim_start_matches("0x"), 16).ok()?;
+
+    let vendor = match implementer {
+        0x41 => "ARM",
+        0x42 => "Broadcom",
+        0x43 => "Cavium",
+        0x46 => "Fujitsu",
+        0x48 => "HiSilicon",
+        0x4e => "NVIDIA",
+        0x50 => "Applied Micro",
+        0x51 => "Qualcomm",
+        0x53 => "Samsung",
+        0x56 => "Marvell",
+        0x61 => "Apple",
+        0x69 => "Intel",
+        0x6d => "Microsoft",
+        0xc0 => "Ampere",
+        _ => return None,
+    };
+
+    Some(vendor.to_string())
+}
+
+/// Parses the `flags` (x86) or `Features` (ARM) fields of every core listed in the
+/// contents of `/proc/cpuinfo`.
+fn parse_cpuinfo_flags(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            matches!(key.trim(), "flags" | "Features").then_some(value)
+        })
+        .flat_map(str::split_whitespace)
+        .map(str::to_lowercase)
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Parses the `Model` field, or failing that the `Hardware` field, found in the
+/// contents of `/proc/cpuinfo` on ARM machines.
+fn parse_cpuinfo_model(content: &str) -> Option<String> {
+    let value_of = |field: &str| {
+        content.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let value = value.trim();
+            (key.trim() == field && !value.is_empty()).then(|| value.to_string())
+        })
+    };
+
+    value_of("Model").or_else(|| value_of("Hardware"))
+}
+
+/// Returns the physical disks listed in the given `/sys/block` directory, skipping
+/// virtual devices and empty drives, _e.g._ card readers.
+fn block_devices(sys_block: &Path) -> Vec<Disk> {
+    let Ok(entries) = fs::read_dir(sys_block) else {
+        return vec![];
+    };
+
+    let mut disks: Vec<Disk> = entries
+        .map_while(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if ["loop", "ram", "zram", "dm-"]
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+            {
+                return None;
+            }
+
+            let device = entry.path();
+            let read = |file: &str| {
+                fs::read_to_string(device.join(file))
+                    .ok()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+
+            // The size is always expressed in 512-byte sectors.
+            let size = read("size")?.parse::<u64>().ok()? * 512;
+            if size == 0 {
+                return None;
+            }
+
+            // NVMe namespaces may only expose the model through their controller.
+            let model = read("device/model").or_else(|| read("device/device/model"));
+            let rotational = read("queue/rotational").map(|rotational| rotational == "1");
+
+            Some(Disk {
+                name,
+                model,
+                size,
+                rotational,
+            })
+        })
+        .collect();
+
+    disks.sort_by(|a, b| a.name.cmp(&b.name));
+    disks
+}
+
+/// Parses the contents of `/proc/diskstats`, where sector counts are always
+/// expressed in units of 512 bytes.
+fn parse_diskstats(content: &str) -> Vec<DiskIo> {
+    content
+        .lines()
+        .filter_map(|line| {
+            // Fields: major, minor, name, reads completed, reads merged, sectors read,
+            // time spent reading, writes completed, writes merged, sectors written, ...
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let sectors_read = fields.get(5)?.parse::<u64>().ok()?;
+            let sectors_written = fields.get(9)?.parse::<u64>().ok()?;
+
+            Some(DiskIo {
+                name: fields[2].to_string(),
+                bytes_read: sectors_read * 512,
+                bytes_written: sectors_written * 512,
+            })
+        })
+        .collect()
+}
+
+/// Parses the contents of `/etc/locale.conf` or `/etc/default/locale`, which hold shell-like
+/// variable assignments, following the precedence of `LC_ALL`, `LC_MESSAGES` and `LANG`.
+fn parse_locale_conf(content: &str) -> Option<String> {
+    let value_of = |var: &str| {
+        content
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix(var)?.strip_prefix('='))
+            .next_back()
+            .map(|value| {
+                value
+                    .trim()
+                    .trim_matches(|c| c == '"' || c == '\'')
+                    .to_string()
+            })
+    };
+
+    ["LC_ALL", "LC_MESSAGES"]
+        .into_iter()
+        .filter_map(value_of)
+        .find(|value| !value.is_empty())
+        .or_else(|| value_of("LANG"))
+}
+
+/// Parses the signal level of the given interface, in dBm, from the contents of
+/// `/proc/net/wireless`.
+fn parse_proc_net_wireless(content: &str, ifname: &str) -> Option<i32> {
+    // The first two lines are headers.
+    let line = content
+        .lines()
+        .skip(2)
+        .find(|line| line.trim_start().starts_with(&format!("{ifname}:")))?;
+
+    // Fields: interface, status, link quality, signal level, noise level, ...
+    let level = line
+        .split_whitespace()
+        .nth(3)?
+        .trim_end_matches('.')
+        .parse::<i32>()
+        .ok()?;
+
+    match level {
+        // A level of zero means that the interface isn't associated with any network.
+        0 => None,
+        // Some drivers report the level as an unsigned 8-bit value.
+        level if level > 0 => Some(level - 256),
+        level => Some(level),
+    }
+}
+
+/// Parses the SSID and signal strength, in dBm, out of the output of `iw dev <interface> link`.
+fn parse_iw_link(output: &str) -> (Option<String>, Option<i32>) {
+    let mut ssid = None;
+    let mut signal = None;
+
+    for line in output.lines().map(str::trim) {
+        if let Some(value) = line.strip_prefix("SSID: ") {
+            ssid = Some(value.to_string());
+        } else if let Some(value) = line.strip_prefix("signal: ") {
+            signal = value
+                .split_whitespace()
+                .next()
+                .and_then(|dbm| dbm.parse::<i32>().ok());
+        }
+    }
+
+    (ssid, signal)
+}
+
+/// Reads the link speed, in Mbit/s, of the interface whose sysfs directory is given.
+fn read_link_speed(interface_dir: &Path) -> Result<usize, ReadoutError> {
+    // Reading the speed of an interface that is down fails with EINVAL, while wireless
+    // interfaces and some virtual ones report -1.
+    let content = fs::read_to_string(interface_dir.join("speed"))
+        .map_err(|_| ReadoutError::MetricNotAvailable)?;
+
+    match content.trim().parse::<i64>() {
+        Ok(speed) if speed > 0 => Ok(speed as usize),
+        _ => Err(ReadoutError::MetricNotAvailable),
+    }
+}
+
+/// Reads the temperature of the battery whose `power_supply` directory is given, which is
+/// either exposed directly in tenths of a degree, or through an associated hwmon device.
+fn read_battery_temperature(battery: &Path) -> Result<f32, ReadoutError> {
+    if let Ok(temp) = fs::read_to_string(battery.join("temp")) {
+        if let Ok(decidegrees) = temp.trim().parse::<i32>() {
+            return Ok(decidegrees as f32 / 10.0);
+        }
+    }
+
+    get_entries(battery)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(OsStr::to_str)
+                .is_some_and(|name| name.starts_with("hwmon"))
+        })
+        .sorted()
+        .find_map(|hwmon| read_millidegrees(&hwmon.join("temp1_input")))
+        .ok_or(ReadoutError::MetricNotAvailable)
+}
+
+/// Returns the displays driven by every readable `/dev/dri/card*` node, or
+/// `None` if none of them could be queried.
+fn drm_displays() -> Option<Vec<Display>> {
+    let mut cards: Vec<PathBuf> = get_entries(Path::new("/dev/dri"))?
+        .into_iter()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .and_then(OsStr::to_str)
+                .is_some_and(|name| name.starts_with("card"))
+        })
+        .collect();
+    cards.sort();
+
+    let mut displays: Option<Vec<Display>> = None;
+
+    for card in cards {
+        if let Some(modes) = drm_ffi::current_modes(&card) {
+            displays
+                .get_or_insert_with(Vec::new)
+                .extend(modes.into_iter().map(|current| Display {
+                    name: Some(current.connector),
+                    width: current.mode.hdisplay as u32,
+                    height: current.mode.vdisplay as u32,
+                    refresh_rate: Some(current.mode.refresh_rate()),
+                    scaled_width: None,
+                    scaled_height: None,
+                }));
+        }
+    }
+
+    displays
+}
+
+/// Returns the connected displays listed under `drm`, _e.g._ `/sys/class/drm`,
+/// along with their preferred mode.
+fn sysfs_displays(drm: &Path) -> Option<Vec<Display>> {
+    let mut entries = get_entries(drm)?;
+    entries.sort();
+
+    let mut displays: Vec<Display> = Vec::new();
+
+    for entry in entries {
+        // Connectors are named after their card, e.g. "card0-HDMI-A-1", which
+        // rules out the cards themselves and render nodes such as "renderD128".
+        let connector = match entry
+            .file_name()
+            .and_then(OsStr::to_str)
+            .and_then(|name| name.split_once('-'))
+        {
+            Some((card, connector))
+                if card
+                    .strip_prefix("card")
+                    .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())) =>
+            {
+                connector.to_string()
+            }
+            _ => continue,
+        };
+
+        // Empty ports may still advertise modes, so only connected ones are kept.
+        match fs::read_to_string(entry.join("status")) {
+            Ok(status) if status.trim() == "connected" => (),
+            _ => continue,
+        }
+
+        // The first mode is the preferred one, e.g. "1920x1080".
+        if let Ok(file) = File::open(entry.join("modes")) {
+            if let Some(Ok(mode)) = BufReader::new(file).lines().next() {
+                if let Some((width, height)) = parse_drm_mode(&mode) {
+                    displays.push(Display {
+                        name: Some(connector),
+                        width,
+                        height,
+                        refresh_rate: None,
+                        scaled_width: None,
+                        scaled_height: None,
+                    });
+                }
+            }
+        }
+    }
+
+    Some(displays)
+}
+
+/// Returns the backlight devices under `base`, _e.g._ `/sys/class/backlight`, with the
+/// ones most likely to control the internal panel first.
+fn backlight_devices(base: &Path) -> Vec<PathBuf> {
+    // Lower is better; external monitors, e.g. ddcci devices, come last.
+    let priority = |device: &PathBuf| {
+        let name = device
+            .file_name()
+            .and_then(OsStr::to_str)
+            .unwrap_or_default();
+
+        if name == "intel_backlight" {
+            0
+        } else if name.starts_with("amdgpu_bl") {
+            1
+        } else if name.starts_with("acpi_video") {
+            2
+        } else if fs::read_to_string(device.join("type")).is_ok_and(|t| t.trim() == "raw") {
+            3
+        } else {
+            4
+        }
+    };
+
+    let mut devices = get_entries(base).unwrap_or_default();
+    devices.sort_by_cached_key(|device| (priority(device), device.clone()));
+    devices
+}
+
+/// Reads the brightness of a backlight device as a percentage.
+fn read_backlight(device: &Path) -> Result<usize, ReadoutError> {
+    let read = |file: &str| -> Result<usize, ReadoutError> {
+        extra::pop_newline(fs::read_to_string(device.join(file))?)
+            .parse::<usize>()
+            .map_err(|_| {
+                ReadoutError::Other(String::from(
+                    "Error occurred while calculating backlight (brightness) value.",
+                ))
+            })
+    };
+
+    let (current, max) = (read("brightness")?, read("max_brightness")?);
+    if max == 0 {
+        return Err(ReadoutError::MetricNotAvailable);
     }
 
-    fn window_manager(&self) -> Result<String, ReadoutError> {
-        shared::window_manager()
+    Ok((current as f64 / max as f64 * 100f64).round() as usize)
+}
+
+/// Looks up a theme setting in the configuration of the running desktop environment.
+///
+/// `gsettings_key` is read from `org.gnome.desktop.interface` on GNOME-based desktops,
+/// `kde_keys` are `(section, key)` pairs looked up in `kdeglobals` on KDE, and `gtk_key` is
+/// read from the GTK 3 and GTK 2 configuration files otherwise.
+fn desktop_theme(
+    gsettings_key: &str,
+    kde_keys: &[(&str, &str)],
+    gtk_key: &str,
+) -> Result<String, ReadoutError> {
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .to_lowercase();
+    let config_dir = dirs::config_dir();
+
+    if desktop.contains("kde") {
+        if let Some(content) = config_dir
+            .as_ref()
+            .and_then(|dir| fs::read_to_string(dir.join("kdeglobals")).ok())
+        {
+            if let Some(value) = kde_keys
+                .iter()
+                .find_map(|&(section, key)| parse_ini_value(&content, Some(section), key))
+            {
+                return Ok(value);
+            }
+        }
     }
 
-    fn terminal(&self) -> Result<String, ReadoutError> {
-        // This function returns the PPID of a given PID:
-        //  - The file used to extract this data: /proc/<pid>/status
-        //  - This function parses and returns the value of the ppid line.
-        fn get_parent(pid: i32) -> i32 {
-            let process_path = PathBuf::from("/proc").join(pid.to_string()).join("status");
-            let file = File::open(process_path);
-            match file {
-                Ok(content) => {
-                    let reader = BufReader::new(content);
-                    for line in reader.lines().map_while(Result::ok) {
-                        if line.to_uppercase().starts_with("PPID") {
-                            let s_mem_kb: String =
-                                line.chars().filter(|c| c.is_ascii_digit()).collect();
-                            return s_mem_kb.parse::<i32>().unwrap_or(-1);
-                        }
-                    }
+    let is_gnome_based = ["gnome", "unity", "budgie", "pantheon"]
+        .iter()
+        .any(|de| desktop.contains(de));
 
-                    -1
-                }
+    if is_gnome_based && extra::which("gsettings") {
+        // gsettings can hang when there is no D-Bus session to talk to.
+        if let Ok(output) = shared::command_output(
+            Path::new("gsettings"),
+            &["get", "org.gnome.desktop.interface", gsettings_key],
+        ) {
+            let value = output.trim().trim_matches('\'');
 
-                Err(_) => -1,
+            if !value.is_empty() {
+                return Ok(value.to_string());
             }
         }
+    }
 
-        // This function returns the name associated with a given PPID
-        fn terminal_name() -> String {
-            let mut terminal_pid = get_parent(unsafe { libc::getppid() });
+    let gtk3 = config_dir.map(|dir| dir.join("gtk-3.0/settings.ini"));
+    let gtk2 = dirs::home_dir().map(|dir| dir.join(".gtkrc-2.0"));
 
-            let path = PathBuf::from("/proc")
-                .join(terminal_pid.to_string())
-                .join("comm");
+    gtk3.into_iter()
+        .map(|path| (path, Some("Settings")))
+        .chain(gtk2.into_iter().map(|path| (path, None)))
+        .find_map(|(path, section)| {
+            parse_ini_value(&fs::read_to_string(path).ok()?, section, gtk_key)
+        })
+        .ok_or(ReadoutError::MetricNotAvailable)
+}
 
-            // The below loop will traverse /proc to find the
-            // terminal inside of which the user is operating
-            if let Ok(mut terminal_name) = fs::read_to_string(path) {
-                // Any command_name we find that matches
-                // one of the elements within this table
-                // is effectively ignored
-                while extra::common_shells().contains(&terminal_name.replace('\n', "").as_str()) {
-                    let ppid = get_parent(terminal_pid);
-                    terminal_pid = ppid;
+/// Returns the value of `key` from an INI-style file, _e.g._ `settings.ini`.
+///
+/// If `section` is `None`, the key is looked up regardless of the section it
+/// belongs to, which is useful for files lacking sections, _e.g._ `.gtkrc-2.0`.
+fn parse_ini_value(content: &str, section: Option<&str>, key: &str) -> Option<String> {
+    let mut current_section: Option<&str> = None;
+
+    for line in content.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current_section = Some(name);
+            continue;
+        }
 
-                    let path = PathBuf::from("/proc").join(ppid.to_string()).join("comm");
+        if section.is_some() && current_section != section {
+            continue;
+        }
 
-                    if let Ok(comm) = fs::read_to_string(path) {
-                        terminal_name = comm;
-                    }
+        if let Some((k, v)) = line.split_once('=') {
+            if k.trim() == key {
+                let value = v.trim().trim_matches('"');
+                if !value.is_empty() {
+                    return Some(value.to_string());
                 }
-
-                return terminal_name;
             }
-
-            String::new()
         }
+    }
 
-        let terminal = terminal_name();
+    None
+}
 
-        if terminal.is_empty() {
-            return Err(ReadoutError::Other(
-                "Querying terminal information failed".to_string(),
-            ));
+/// Returns the version of a GPU driver, which for in-tree modules is that of the kernel.
+fn gpu_driver_version(driver: &str) -> Option<String> {
+    if driver == "nvidia" {
+        if let Some(version) = fs::read_to_string("/proc/driver/nvidia/version")
+            .ok()
+            .and_then(|content| parse_nvidia_version(&content))
+        {
+            return Some(version);
         }
-
-        Ok(terminal)
     }
 
-    fn shell(&self, format: ShellFormat, kind: ShellKind) -> Result<String, ReadoutError> {
-        shared::shell(format, kind)
+    // Only out-of-tree modules carry a version of their own.
+    let version = fs::read_to_string(Path::new("/sys/module").join(driver).join("version"))
+        .ok()
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty());
+
+    // e.g. "6.7.9-arch1-1"
+    version.or_else(|| {
+        let release = fs::read_to_string("/proc/sys/kernel/osrelease").ok()?;
+        release.trim().split('-').next().map(String::from)
+    })
+}
+
+/// Parses the version out of `/proc/driver/nvidia/version`, _e.g._
+/// `NVRM version: NVIDIA UNIX x86_64 Kernel Module  550.78  Sun Apr 14 06:35:45 UTC 2024`
+fn parse_nvidia_version(content: &str) -> Option<String> {
+    let line = content
+        .lines()
+        .find(|line| line.starts_with("NVRM version"))?;
+    let (_, rest) = line.split_once("Kernel Module")?;
+
+    // The open kernel modules insert the architecture before the version.
+    rest.split_whitespace()
+        .find(|token| token.starts_with(|c: char| c.is_ascii_digit()) && token.contains('.'))
+        .map(String::from)
+}
+
+/// Returns the total video memory of a GPU in bytes, given its sysfs directory.
+///
+/// Integrated GPUs, _e.g._ those driven by `i915`, share the system's memory and
+/// aren't reported.
+fn read_gpu_memory(device: &Path, driver: Option<&str>, nvidia_gpus: &Path) -> Option<u64> {
+    // Exposed by amdgpu, among others.
+    if let Some(vram) = fs::read_to_string(device.join("mem_info_vram_total"))
+        .ok()
+        .and_then(|vram| vram.trim().parse::<u64>().ok())
+        .filter(|&vram| vram > 0)
+    {
+        return Some(vram);
     }
 
-    fn cpu_model_name(&self) -> Result<String, ReadoutError> {
-        Ok(shared::cpu_model_name())
+    if driver != Some("nvidia") {
+        return None;
     }
 
-    fn cpu_usage(&self) -> Result<usize, ReadoutError> {
-        let mut info = self.sysinfo;
-        let info_ptr: *mut sysinfo = &mut info;
-        let ret = unsafe { sysinfo(info_ptr) };
+    // e.g. /proc/driver/nvidia/gpus/0000:01:00.0/information
+    let information =
+        fs::read_to_string(nvidia_gpus.join(device.file_name()?).join("information")).ok()?;
 
-        if ret != -1 {
-            let f_load = 1f64 / (1 << libc::SI_LOAD_SHIFT) as f64;
-            let cpu_usage = info.loads[0] as f64 * f_load;
-            let cpu_usage_u =
-                (cpu_usage / self.cpu_cores().unwrap() as f64 * 100.0).round() as usize;
-            return Ok(cpu_usage_u as usize);
+    information.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if key.trim() != "Video Memory" {
+            return None;
         }
 
-        Err(ReadoutError::Other(
-            "Something went wrong during the initialization of the sysinfo struct.".to_string(),
+        let mut value = value.split_whitespace();
+        let amount = value.next()?.parse::<u64>().ok()?;
+        let unit = match value.next().unwrap_or("MB") {
+            "KB" => 1024,
+            "MB" => 1024 * 1024,
+            "GB" => 1024 * 1024 * 1024,
+            _ => return None,
+        };
+
+        Some(amount * unit)
+    })
+}
+
+/// Reads the `pci.ids` database, which isn't shipped by minimal installations.
+fn pci_database() -> Result<Database, ReadoutError> {
+    Database::read().map_err(|_| {
+        ReadoutError::Other(String::from(
+            "pci.ids not found, is the hwdata or pciutils package installed?",
         ))
+    })
+}
+
+/// Returns the non-PCI GPUs exposed through the DRM subsystem, _e.g._
+/// `vc4-drm (Broadcom VideoCore IV)`.
+fn platform_gpus(drm: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(drm) else {
+        return vec![];
+    };
+
+    // Connectors, e.g. card0-HDMI-A-1, are skipped.
+    let mut cards: Vec<PathBuf> = entries
+        .map_while(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("card"))
+                .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(|entry| entry.path())
+        .collect();
+
+    cards.sort();
+
+    let mut devices = vec![];
+    let mut gpus = vec![];
+
+    for card in cards {
+        let Ok(device) = fs::canonicalize(card.join("device")) else {
+            continue;
+        };
+
+        // A device may back more than one card, and PCI devices are
+        // already taken care of by get_pci_devices().
+        let subsystem = fs::read_link(device.join("subsystem")).ok();
+        if devices.contains(&device)
+            || subsystem.as_deref().and_then(Path::file_name) == Some(OsStr::new("pci"))
+        {
+            continue;
+        }
+
+        if let Some(name) = fs::read_to_string(device.join("uevent"))
+            .ok()
+            .and_then(|uevent| parse_platform_gpu(&uevent))
+        {
+            gpus.push(name);
+        }
+
+        devices.push(device);
     }
 
-    fn cpu_physical_cores(&self) -> Result<usize, ReadoutError> {
-        use std::io::{BufRead, BufReader};
-        if let Ok(content) = File::open("/proc/cpuinfo") {
-            let reader = BufReader::new(content);
-            for line in reader.lines().map_while(Result::ok) {
-                if line.to_lowercase().starts_with("cpu cores") {
-                    return Ok(line
-                        .split(':')
-                        .nth(1)
-                        .unwrap()
-                        .trim()
-                        .parse::<usize>()
-                        .unwrap());
-                }
+    gpus
+}
+
+/// Builds a GPU name from the `uevent` file of a devicetree device.
+fn parse_platform_gpu(uevent: &str) -> Option<String> {
+    let mut driver = None;
+    let mut full_name = None;
+    let mut compatible = vec![];
+
+    for line in uevent.lines() {
+        match line.split_once('=') {
+            Some(("DRIVER", value)) => driver = Some(value),
+            Some(("OF_FULLNAME", value)) => full_name = Some(value),
+            Some((key, value)) if key.starts_with("OF_COMPATIBLE_") && key != "OF_COMPATIBLE_N" => {
+                compatible.push(value)
             }
+            _ => {}
         }
-
-        Err(ReadoutError::MetricNotAvailable)
     }
 
-    fn cpu_cores(&self) -> Result<usize, ReadoutError> {
-        Ok(unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) } as usize)
+    // Compatible strings are listed from the most to the least specific one.
+    let model = compatible
+        .iter()
+        .find_map(|compatible| describe_compatible(compatible))
+        .or_else(|| compatible.first().map(|c| c.to_string()))
+        .or_else(|| {
+            let name = full_name?.rsplit('/').next()?;
+            (!name.is_empty()).then(|| name.to_string())
+        });
+
+    match (driver, model) {
+        (Some(driver), Some(model)) => Some(format!("{driver} ({model})")),
+        (Some(driver), None) => Some(driver.to_string()),
+        (None, model) => model,
     }
+}
 
-    fn uptime(&self) -> Result<usize, ReadoutError> {
-        let mut info = self.sysinfo;
-        let info_ptr: *mut sysinfo = &mut info;
-        let ret = unsafe { sysinfo(info_ptr) };
+/// Maps a devicetree compatible string, _e.g._ `arm,mali-t860`, to a GPU model.
+fn describe_compatible(compatible: &str) -> Option<String> {
+    let (vendor, model) = compatible.split_once(',')?;
+
+    match vendor {
+        "brcm" => {
+            let generation = if model.contains("2712") || model.contains("vc6") {
+                "VII"
+            } else if model.contains("2711") || model.contains("vc5") {
+                "VI"
+            } else if model.contains("2835") || model.contains("vc4") {
+                "IV"
+            } else {
+                return None;
+            };
 
-        if ret != -1 {
-            return Ok(info.uptime as usize);
+            Some(format!("Broadcom VideoCore {generation}"))
+        }
+        "arm" => {
+            let model = model.strip_prefix("mali-")?;
+            match model {
+                "utgard" | "midgard" | "bifrost" | "valhall" => {
+                    let mut family = model.chars();
+                    let first = family.next()?.to_ascii_uppercase();
+                    Some(format!("Arm Mali ({first}{})", family.as_str()))
+                }
+                _ => Some(format!("Arm Mali-{}", model.to_ascii_uppercase())),
+            }
         }
+        "qcom" => {
+            let model = model.strip_prefix("adreno")?;
+            let number: String = model
+                .trim_start_matches('-')
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
 
-        Err(ReadoutError::Other(
-            "Something went wrong during the initialization of the sysinfo struct.".to_string(),
-        ))
+            if number.is_empty() {
+                Some(String::from("Qualcomm Adreno"))
+            } else {
+                Some(format!("Qualcomm Adreno {number}"))
+            }
+        }
+        "vivante" => Some(String::from("Vivante GPU")),
+        _ => None,
     }
+}
 
-    fn machine(&self) -> Result<String, ReadoutError> {
-        let product_readout = LinuxProductReadout::new();
+/// Parses a DRM mode name, _e.g._ `1920x1080`, into its width and height.
+fn parse_drm_mode(mode: &str) -> Option<(u32, u32)> {
+    let (width, height) = mode.trim().split_once('x')?;
 
-        let vendor = product_readout.vendor()?;
-        let family = product_readout.family()?;
-        let product = product_readout.product()?;
-        let version = extra::pop_newline(fs::read_to_string("/sys/class/dmi/id/product_version")?);
+    // Interlaced modes are suffixed with an "i", e.g. "1920x1080i".
+    let height = height.trim_end_matches(|c: char| !c.is_ascii_digit());
 
-        // If one field is generic, the others are likely the same, so fail the readout.
-        if vendor.eq_ignore_ascii_case("system manufacturer") {
-            return Err(ReadoutError::Other(String::from(
-                "Your manufacturer may have not specified your machine's product information.",
-            )));
-        }
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
 
-        let new_product =
-            format!("{vendor} {family} {product} {version}").replace("To be filled by O.E.M.", "");
+/// Reads a temperature expressed in millidegrees Celsius, as exposed by hwmon and thermal zones.
+fn read_millidegrees(path: &Path) -> Option<f32> {
+    fs::read_to_string(path)
+        .ok()?
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .map(|millidegrees| millidegrees as f32 / 1000.0)
+}
 
-        if family == product && family == version {
-            return Ok(family);
-        } else if version.is_empty() || version.len() <= 22 {
-            return Ok(new_product.split_whitespace().unique().join(" "));
-        }
+/// Parses the contents of `/proc/self/mounts` into pairs of mount points and filesystem types,
+/// skipping pseudo-filesystems and subsequent mounts of an already seen device.
+fn parse_mounts(content: &str) -> Vec<(PathBuf, String)> {
+    // Whitespace in mount points is escaped using octal sequences, e.g. "\040" for a space.
+    fn unescape(field: &str) -> String {
+        let mut output = String::with_capacity(field.len());
+        let mut chars = field.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                let code: String = chars.clone().take(3).collect();
+                if let Ok(byte) = u8::from_str_radix(&code, 8) {
+                    output.push(byte as char);
+                    chars.nth(2);
+                    continue;
+                }
+            }
 
-        Ok(version)
-    }
+            output.push(c);
+        }
 
-    fn os_name(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        output
     }
 
-    fn disk_space(&self, path: &Path) -> Result<(u64, u64), ReadoutError> {
-        shared::disk_space(path)
-    }
+    let mut devices: Vec<&str> = Vec::new();
+    let mut mounts = Vec::new();
 
-    fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
-        let db = match Database::read() {
-            Ok(db) => db,
-            _ => return Err(ReadoutError::MetricNotAvailable),
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(device), Some(mount_point), Some(fs_type)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
         };
 
-        let devices = get_pci_devices()?;
-        let mut gpus = vec![];
-
-        for device in devices {
-            if !device.is_gpu(&db) {
-                continue;
-            };
-
-            if let Some(sub_device_name) = device.get_device_name(&db) {
-                gpus.push(sub_device_name);
-            };
+        if shared::is_pseudo_filesystem(fs_type) || devices.contains(&device) {
+            continue;
         }
 
-        if gpus.is_empty() {
-            Err(ReadoutError::MetricNotAvailable)
-        } else {
-            Ok(gpus)
-        }
+        devices.push(device);
+        mounts.push((PathBuf::from(unescape(mount_point)), fs_type.to_string()));
     }
+
+    mounts
 }
 
 impl MemoryReadout for LinuxMemoryReadout {
@@ -766,7 +1982,7 @@ impl PackageReadout for LinuxPackageReadout {
             packages.push((PackageManager::Homebrew, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_nix() {
+        if let Some(c) = LinuxPackageReadout::count_nix(&home) {
             packages.push((PackageManager::Nix, c));
         }
 
@@ -991,9 +2207,49 @@ impl LinuxPackageReadout {
     }
 
     /// Returns the number of installed packages for systems
-    /// that utilize `nix` as their package manager.
-    fn count_nix() -> Option<usize> {
-        return 'sqlite: {
+    /// that utilize `nix` as their package manager, i.e. those of the
+    /// NixOS system profile and of the user's profile.
+    fn count_nix(home: &Path) -> Option<usize> {
+        match (
+            LinuxPackageReadout::count_nixos_system(),
+            shared::count_nix_profile(home),
+        ) {
+            (None, None) => LinuxPackageReadout::count_nix_store(),
+            (system, profile) => Some(system.unwrap_or(0) + profile.unwrap_or(0)),
+        }
+    }
+
+    /// Returns the number of packages referenced by the environment of
+    /// the current NixOS system, which `/run/current-system/sw` points to.
+    fn count_nixos_system() -> Option<usize> {
+        let system_path = std::fs::canonicalize("/run/current-system/sw").ok()?;
+        let db = "/nix/var/nix/db/db.sqlite";
+
+        let con = sqlite::Connection::open_with_flags(
+            "file:".to_owned() + db + "?immutable=1",
+            sqlite::OpenFlags::new().with_read_only().with_uri(),
+        )
+        .ok()?;
+
+        let mut statement = con
+            .prepare(
+                "SELECT COUNT(*) FROM Refs WHERE referrer = \
+                (SELECT id FROM ValidPaths WHERE path = ?) AND reference != referrer",
+            )
+            .ok()?;
+        statement.bind((1, system_path.to_str()?)).ok()?;
+        statement.next().ok()?;
+
+        match statement.read::<Option<i64>, _>(0) {
+            Ok(Some(count)) => Some(count as usize),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of signed paths of the nix store, which is
+    /// used when no profile could be read.
+    fn count_nix_store() -> Option<usize> {
+        'sqlite: {
             let db = "/nix/var/nix/db/db.sqlite";
             if !Path::new(db).is_file() {
                 break 'sqlite None;
@@ -1020,6 +2276,540 @@ impl LinuxPackageReadout {
             }
 
             None
+        }
+    }
+}
+
+// This function returns the PPID of a given PID:
+//  - The file used to extract this data: /proc/<pid>/status
+//  - This function parses and returns the value of the ppid line.
+fn get_parent(pid: i32) -> i32 {
+    let process_path = PathBuf::from("/proc").join(pid.to_string()).join("status");
+    let file = File::open(process_path);
+    match file {
+        Ok(content) => {
+            let reader = BufReader::new(content);
+            for line in reader.lines().map_while(Result::ok) {
+                if line.to_uppercase().starts_with("PPID") {
+                    let s_mem_kb: String = line.chars().filter(|c| c.is_ascii_digit()).collect();
+                    return s_mem_kb.parse::<i32>().unwrap_or(-1);
+                }
+            }
+
+            -1
+        }
+
+        Err(_) => -1,
+    }
+}
+
+/// Provides access to the process tree through /proc.
+struct ProcFs;
+
+impl shared::ProcessTree for ProcFs {
+    fn parent(&self, pid: i32) -> Option<i32> {
+        Some(get_parent(pid)).filter(|&ppid| ppid > 0)
+    }
+
+    fn name(&self, pid: i32) -> Option<String> {
+        shared::process_name(Path::new("/proc"), pid)
+    }
+}
+
+// This function returns the terminal inside of which the user is operating
+fn terminal_process() -> Option<shared::Terminal> {
+    shared::find_terminal(&ProcFs, get_parent(unsafe { libc::getppid() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpufreq() {
+        assert_eq!(parse_cpufreq("3900000\n"), Some(3900));
+        assert_eq!(parse_cpufreq("0\n"), None);
+        assert_eq!(parse_cpufreq("<unknown>\n"), None);
+    }
+
+    #[test]
+    fn test_parse_cpuinfo_mhz() {
+        let cpuinfo = "processor\t: 0\n\
+                       vendor_id\t: GenuineIntel\n\
+                       model name\t: Intel(R) Core(TM) i5-8265U CPU @ 1.60GHz\n\
+                       cpu MHz\t\t: 1799.998\n\
+                       cache size\t: 6144 KB\n";
+
+        assert_eq!(parse_cpuinfo_mhz(cpuinfo), Some(1800));
+        assert_eq!(
+            parse_cpuinfo_mhz("processor\t: 0\nBogoMIPS\t: 108.00\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_locale_conf() {
+        assert_eq!(
+            parse_locale_conf("LANG=en_US.UTF-8\n"),
+            Some(String::from("en_US.UTF-8"))
+        );
+        assert_eq!(
+            parse_locale_conf("# Generated by locale-gen\nLANG=\"de_DE.UTF-8\"\nLC_MESSAGES=C\n"),
+            Some(String::from("C"))
+        );
+        assert_eq!(
+            parse_locale_conf("LANGUAGE=en_GB:en\nLC_ALL=\nLANG=en_GB.UTF-8\n"),
+            Some(String::from("en_GB.UTF-8"))
+        );
+        assert_eq!(parse_locale_conf("LANGUAGE=en_GB:en\n"), None);
+    }
+
+    #[test]
+    fn test_parse_proc_net_wireless() {
+        let content = "Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE\n \
+                       face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22\n \
+                       wlp2s0: 0000   54.  -56.  -256        0      0      0      0     89        0\n";
+
+        assert_eq!(parse_proc_net_wireless(content, "wlp2s0"), Some(-56));
+        assert_eq!(parse_proc_net_wireless(content, "wlan0"), None);
+    }
+
+    #[test]
+    fn test_parse_iw_link() {
+        let output = "Connected to 52:9a:d2:d3:b5:fd (on wlp2s0)\n\
+                      \tSSID: Home Network\n\
+                      \tfreq: 5180\n\
+                      \tsignal: -56 dBm\n\
+                      \trx bitrate: 866.7 MBit/s\n";
+
+        assert_eq!(
+            parse_iw_link(output),
+            (Some(String::from("Home Network")), Some(-56))
+        );
+        assert_eq!(parse_iw_link("Not connected.\n"), (None, None));
+    }
+
+    #[test]
+    fn test_read_link_speed() {
+        let interface_dir = shared::TempDir::new("link-speed");
+
+        assert!(matches!(
+            read_link_speed(&interface_dir),
+            Err(ReadoutError::MetricNotAvailable)
+        ));
+
+        fs::write(interface_dir.join("speed"), "-1\n").unwrap();
+        assert!(matches!(
+            read_link_speed(&interface_dir),
+            Err(ReadoutError::MetricNotAvailable)
+        ));
+
+        fs::write(interface_dir.join("speed"), "1000\n").unwrap();
+        assert_eq!(read_link_speed(&interface_dir).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_read_power_draw() {
+        let battery = shared::TempDir::new("power-draw");
+
+        assert_eq!(read_power_draw(&battery), None);
+
+        // Batteries exposing charge_* values only report the voltage and current.
+        fs::write(battery.join("voltage_now"), "12100000\n").unwrap();
+        fs::write(battery.join("current_now"), "-850000\n").unwrap();
+        assert_eq!(read_power_draw(&battery), Some(10.285));
+
+        fs::write(battery.join("power_now"), "8520000\n").unwrap();
+        assert_eq!(read_power_draw(&battery), Some(8.52));
+    }
+
+    #[test]
+    fn test_parse_drm_mode() {
+        assert_eq!(parse_drm_mode("1920x1080\n"), Some((1920, 1080)));
+        assert_eq!(parse_drm_mode("1920x1080i"), Some((1920, 1080)));
+        assert_eq!(parse_drm_mode("preferred"), None);
+    }
+
+    #[test]
+    fn test_drm_mode_refresh_rate() {
+        let mut mode = drm_ffi::drm_mode_modeinfo {
+            clock: 148500,
+            hdisplay: 1920,
+            htotal: 2200,
+            vdisplay: 1080,
+            vtotal: 1125,
+            vrefresh: 60,
+            ..Default::default()
+        };
+        assert_eq!(mode.refresh_rate(), 60.0);
+
+        mode.clock = 0;
+        assert_eq!(mode.refresh_rate(), 60.0);
+    }
+
+    #[test]
+    fn test_sysfs_displays() {
+        let drm = shared::TempDir::new("displays");
+
+        for (entry, status, modes) in [
+            ("card0", None, None),
+            (
+                "card0-DP-1",
+                Some("connected\n"),
+                Some("2560x1440\n1920x1080\n"),
+            ),
+            ("card0-DP-2", Some("disconnected\n"), Some("1920x1080\n")),
+            ("card0-HDMI-A-1", Some("connected\n"), Some("1920x1080\n")),
+            ("renderD128", None, Some("1920x1080\n")),
+        ] {
+            let dir = drm.join(entry);
+            fs::create_dir_all(&dir).unwrap();
+
+            if let Some(status) = status {
+                fs::write(dir.join("status"), status).unwrap();
+            }
+
+            if let Some(modes) = modes {
+                fs::write(dir.join("modes"), modes).unwrap();
+            }
+        }
+
+        let displays = sysfs_displays(&drm).unwrap();
+        let names: Vec<_> = displays.iter().map(|d| d.name.as_deref()).collect();
+        assert_eq!(names, [Some("DP-1"), Some("HDMI-A-1")]);
+        assert_eq!((displays[0].width, displays[0].height), (2560, 1440));
+    }
+
+    #[test]
+    fn test_backlight_devices() {
+        let base = shared::TempDir::new("backlight");
+
+        for (device, kind, brightness, max_brightness) in [
+            ("acpi_video0", "firmware", "5", "10"),
+            ("ddcci13", "raw", "50", "100"),
+            ("intel_backlight", "raw", "960", "1200"),
+            ("broken", "platform", "1", "0"),
+        ] {
+            let dir = base.join(device);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("type"), format!("{kind}\n")).unwrap();
+            fs::write(dir.join("brightness"), format!("{brightness}\n")).unwrap();
+            fs::write(dir.join("max_brightness"), format!("{max_brightness}\n")).unwrap();
+        }
+
+        let devices = backlight_devices(&base);
+        let names: Vec<_> = devices.iter().filter_map(|d| d.file_name()).collect();
+        assert_eq!(
+            names,
+            ["intel_backlight", "acpi_video0", "ddcci13", "broken"]
+        );
+
+        assert_eq!(read_backlight(&devices[0]).unwrap(), 80);
+        assert!(matches!(
+            read_backlight(&devices[3]),
+            Err(ReadoutError::MetricNotAvailable)
+        ));
+    }
+
+    #[test]
+    fn test_parse_ini_value() {
+        let settings = "[Settings]\ngtk-theme-name=Adwaita-dark\ngtk-icon-theme-name = Papirus\n";
+        assert_eq!(
+            parse_ini_value(settings, Some("Settings"), "gtk-theme-name").as_deref(),
+            Some("Adwaita-dark")
+        );
+        assert_eq!(
+            parse_ini_value(settings, Some("Settings"), "gtk-icon-theme-name").as_deref(),
+            Some("Papirus")
+        );
+        assert_eq!(
+            parse_ini_value(settings, Some("General"), "gtk-theme-name"),
+            None
+        );
+
+        let gtkrc = "# -- THEME AUTO-WRITTEN --\ngtk-theme-name=\"Arc-Dark\"\n";
+        assert_eq!(
+            parse_ini_value(gtkrc, None, "gtk-theme-name").as_deref(),
+            Some("Arc-Dark")
+        );
+
+        let kdeglobals = "[General]\nColorScheme=BreezeDark\n\n[Icons]\nTheme=breeze-dark\n";
+        assert_eq!(
+            parse_ini_value(kdeglobals, Some("Icons"), "Theme").as_deref(),
+            Some("breeze-dark")
+        );
+    }
+
+    #[test]
+    fn test_parse_nvidia_version() {
+        let version = "NVRM version: NVIDIA UNIX x86_64 Kernel Module  550.78  \
+                       Sun Apr 14 06:35:45 UTC 2024\n\
+                       GCC version:  gcc version 13.2.1 20230801 (GCC)\n";
+        assert_eq!(parse_nvidia_version(version).as_deref(), Some("550.78"));
+
+        let open = "NVRM version: NVIDIA UNIX Open Kernel Module for x86_64  550.78  \
+                    Release Build  (dvs-builder@U16-I3-B03-4-3)\n";
+        assert_eq!(parse_nvidia_version(open).as_deref(), Some("550.78"));
+
+        assert_eq!(parse_nvidia_version("GCC version: 13.2.1\n"), None);
+    }
+
+    #[test]
+    fn test_read_gpu_memory() {
+        let root = shared::TempDir::new("vram");
+        let devices = root.join("devices");
+        let nvidia_gpus = root.join("nvidia");
+
+        let amd = devices.join("0000:03:00.0");
+        fs::create_dir_all(&amd).unwrap();
+        fs::write(amd.join("mem_info_vram_total"), "17163091968\n").unwrap();
+        assert_eq!(
+            read_gpu_memory(&amd, Some("amdgpu"), &nvidia_gpus),
+            Some(17163091968)
+        );
+
+        let intel = devices.join("0000:00:02.0");
+        fs::create_dir_all(&intel).unwrap();
+        assert_eq!(read_gpu_memory(&intel, Some("i915"), &nvidia_gpus), None);
+
+        let nvidia = devices.join("0000:01:00.0");
+        fs::create_dir_all(&nvidia).unwrap();
+        assert_eq!(read_gpu_memory(&nvidia, Some("nvidia"), &nvidia_gpus), None);
+
+        fs::create_dir_all(nvidia_gpus.join("0000:01:00.0")).unwrap();
+        fs::write(
+            nvidia_gpus.join("0000:01:00.0").join("information"),
+            "Model: \t\t NVIDIA GeForce GTX 1080\nIRQ:   \t\t 130\nVideo Memory: \t 8192 MB\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_gpu_memory(&nvidia, Some("nvidia"), &nvidia_gpus),
+            Some(8192 * 1024 * 1024)
+        );
+    }
+
+    #[test]
+    fn test_parse_platform_gpu() {
+        assert_eq!(
+            parse_platform_gpu(
+                "DRIVER=vc4-drm\nOF_NAME=gpu\nOF_FULLNAME=/gpu\nOF_COMPATIBLE_0=brcm,bcm2835-vc4\nOF_COMPATIBLE_N=1\n"
+            ),
+            Some(String::from("vc4-drm (Broadcom VideoCore IV)"))
+        );
+        assert_eq!(
+            parse_platform_gpu(
+                "DRIVER=panfrost\nOF_FULLNAME=/gpu@ff9a0000\nOF_COMPATIBLE_0=rockchip,rk3399-mali\nOF_COMPATIBLE_1=arm,mali-t860\nOF_COMPATIBLE_N=2\n"
+            ),
+            Some(String::from("panfrost (Arm Mali-T860)"))
+        );
+        assert_eq!(
+            parse_platform_gpu("DRIVER=lima\nOF_COMPATIBLE_0=arm,mali-utgard\nOF_COMPATIBLE_N=1\n"),
+            Some(String::from("lima (Arm Mali (Utgard))"))
+        );
+        assert_eq!(
+            parse_platform_gpu("DRIVER=msm_dpu\nOF_COMPATIBLE_0=qcom,adreno-630.2\n"),
+            Some(String::from("msm_dpu (Qualcomm Adreno 630)"))
+        );
+        assert_eq!(
+            parse_platform_gpu(
+                "DRIVER=sun4i-drm\nOF_COMPATIBLE_0=allwinner,sun50i-h6-display-engine\n"
+            ),
+            Some(String::from(
+                "sun4i-drm (allwinner,sun50i-h6-display-engine)"
+            ))
+        );
+        assert_eq!(parse_platform_gpu("MODALIAS=of:Ngpu\n"), None);
+    }
+
+    #[test]
+    fn test_platform_gpus() {
+        let drm = shared::TempDir::new("gpus");
+        let device = drm.join("card0").join("device");
+        fs::create_dir_all(&device).unwrap();
+        fs::create_dir_all(drm.join("card0-HDMI-A-1")).unwrap();
+        fs::write(
+            device.join("uevent"),
+            "DRIVER=v3d\nOF_COMPATIBLE_0=brcm,2711-v3d\nOF_COMPATIBLE_N=1\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            platform_gpus(&drm),
+            vec![String::from("v3d (Broadcom VideoCore VI)")]
+        );
+        assert!(platform_gpus(&drm.join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_parse_cpuinfo_model() {
+        let raspberry_pi = "processor\t: 0\nmodel name\t: ARMv7 Processor rev 3 (v7l)\n\nHardware\t: BCM2835\nRevision\t: c03114\nModel\t\t: Raspberry Pi 4 Model B Rev 1.4\n";
+        assert_eq!(
+            parse_cpuinfo_model(raspberry_pi),
+            Some(String::from("Raspberry Pi 4 Model B Rev 1.4"))
+        );
+        assert_eq!(
+            parse_cpuinfo_model("processor\t: 0\nHardware\t: Qualcomm Technologies, Inc SM8250\n"),
+            Some(String::from("Qualcomm Technologies, Inc SM8250"))
+        );
+        assert_eq!(
+            parse_cpuinfo_model(
+                "processor\t: 0\nmodel\t\t: 158\nmodel name\t: Intel(R) Core(TM) i7\n"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let stat = "cpu  4705 356 584 3699 23 0 12 100 2000 30\ncpu0 1393 2 111 914 5 0 2 25 500 10\nintr 114930548 113199788 3 0 5 263 0 4 [...]\nctxt 1990473\nbtime 1062191376\n";
+        assert_eq!(
+            parse_proc_stat(stat),
+            Some(CpuTimes {
+                busy: 4705 + 356 + 584 + 12 + 100,
+                total: 4705 + 356 + 584 + 3699 + 23 + 12 + 100,
+            })
+        );
+
+        // Older kernels only provide the first four fields.
+        assert_eq!(
+            parse_proc_stat("cpu 10 0 10 80\n"),
+            Some(CpuTimes {
+                busy: 20,
+                total: 100
+            })
+        );
+        assert_eq!(parse_proc_stat("cpu0 10 0 10 80\n"), None);
+        assert_eq!(parse_proc_stat("cpu 10 0 ten 80\n"), None);
+    }
+
+    #[test]
+    fn test_cpu_times_usage_until() {
+        let first = CpuTimes {
+            busy: 100,
+            total: 1000,
+        };
+
+        assert_eq!(
+            first.usage_until(&CpuTimes {
+                busy: 125,
+                total: 1100
+            }),
+            Some(25)
+        );
+        assert_eq!(
+            first.usage_until(&CpuTimes {
+                busy: 300,
+                total: 1100
+            }),
+            Some(100)
+        );
+        assert_eq!(first.usage_until(&first), None);
+    }
+
+    #[test]
+    fn test_parse_cpuinfo_vendor() {
+        assert_eq!(
+            parse_cpuinfo_vendor("processor\t: 0\nvendor_id\t: AuthenticAMD\ncpu family\t: 25\n"),
+            Some(String::from("AMD"))
+        );
+        assert_eq!(
+            parse_cpuinfo_vendor(
+                "processor\t: 0\nBogoMIPS\t: 48.00\nCPU implementer\t: 0x41\nCPU architecture: 8\n"
+            ),
+            Some(String::from("ARM"))
+        );
+        assert_eq!(
+            parse_cpuinfo_vendor("processor\t: 0\nCPU implementer\t: 0x51\n"),
+            Some(String::from("Qualcomm"))
+        );
+        assert_eq!(
+            parse_cpuinfo_vendor("processor\t: 0\nCPU implementer\t: 0xff\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_cpuinfo_flags() {
+        let x86 = "processor\t: 0\nflags\t\t: fpu sse4_2 avx2 avx\n\nprocessor\t: 1\nflags\t\t: fpu sse4_2 avx2 avx\n";
+        assert_eq!(
+            parse_cpuinfo_flags(x86),
+            vec!["avx", "avx2", "fpu", "sse4_2"]
+        );
+
+        let aarch64 =
+            "processor\t: 0\nBogoMIPS\t: 108.00\nFeatures\t: fp asimd evtstrm crc32 cpuid\n";
+        assert_eq!(
+            parse_cpuinfo_flags(aarch64),
+            vec!["asimd", "cpuid", "crc32", "evtstrm", "fp"]
+        );
+        assert!(parse_cpuinfo_flags("processor\t: 0\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_diskstats() {
+        let diskstats = "   7       0 loop0 52 0 2110 15 0 0 0 0 0 32 15 0 0 0 0 0 0\n 259       0 nvme0n1 301427 96211 21489810 48231 655019 372418 39110664 412093 0 290472 489017 0 0 0 0 31215 28692\n 259       1 nvme0n1p1 312 1012 13540 43 2 0 2 0 0 60 43 0 0 0 0 0 0\n";
+
+        assert_eq!(
+            parse_diskstats(diskstats),
+            vec![
+                DiskIo {
+                    name: String::from("loop0"),
+                    bytes_read: 2110 * 512,
+                    bytes_written: 0,
+                },
+                DiskIo {
+                    name: String::from("nvme0n1"),
+                    bytes_read: 21489810 * 512,
+                    bytes_written: 39110664 * 512,
+                },
+                DiskIo {
+                    name: String::from("nvme0n1p1"),
+                    bytes_read: 13540 * 512,
+                    bytes_written: 2 * 512,
+                },
+            ]
+        );
+        assert!(parse_diskstats("8 0 sda\n").is_empty());
+    }
+
+    #[test]
+    fn test_block_devices() {
+        let sys_block = shared::TempDir::new("block");
+        let write = |path: &str, content: &str| {
+            let path = sys_block.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
         };
+
+        write("sda/size", "3907029168\n");
+        write("sda/device/model", "ST2000DM008-2FR1\n");
+        write("sda/queue/rotational", "1\n");
+        write("nvme0n1/size", "1953525168\n");
+        write(
+            "nvme0n1/device/device/model",
+            "Samsung SSD 980 PRO 1TB                 \n",
+        );
+        write("nvme0n1/queue/rotational", "0\n");
+        write("sdb/size", "0\n");
+        write("loop0/size", "1024\n");
+        write("zram0/size", "16777216\n");
+
+        assert_eq!(
+            block_devices(&sys_block),
+            vec![
+                Disk {
+                    name: String::from("nvme0n1"),
+                    model: Some(String::from("Samsung SSD 980 PRO 1TB")),
+                    size: 1953525168 * 512,
+                    rotational: Some(false),
+                },
+                Disk {
+                    name: String::from("sda"),
+                    model: Some(String::from("ST2000DM008-2FR1")),
+                    size: 3907029168 * 512,
+                    rotational: Some(true),
+                },
+            ]
+        );
     }
 }
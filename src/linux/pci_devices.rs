@@ -1,7 +1,7 @@
 use std::{
-    fs::{read_dir, read_to_string},
+    fs::{read_dir, read_link, read_to_string},
     io,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use pciid_parser::{schema::SubDeviceId, Database};
@@ -42,17 +42,18 @@ impl PciDevice {
         PciDevice { base_path }
     }
 
-    fn read_value(&self, readable_value: PciDeviceReadableValues) -> String {
+    fn read_value(&self, readable_value: PciDeviceReadableValues) -> Result<String, io::Error> {
         let value_path = self.base_path.join(readable_value.as_str());
 
-        match read_to_string(&value_path) {
-            Ok(hex_string) => parse_device_hex(&hex_string),
-            _ => panic!("Could not find value: {:?}", value_path),
-        }
+        read_to_string(value_path).map(|hex_string| parse_device_hex(&hex_string))
     }
 
+    /// Returns whether the device is a display controller. Devices whose class
+    /// can't be read are never considered to be one.
     pub fn is_gpu(&self, db: &Database) -> bool {
-        let class_value = self.read_value(PciDeviceReadableValues::Class);
+        let Ok(class_value) = self.read_value(PciDeviceReadableValues::Class) else {
+            return false;
+        };
         let first_pair = class_value.chars().take(2).collect::<String>();
         let classes = ["Display controller", "VGA compatible controller"];
 
@@ -62,18 +63,33 @@ impl PciDevice {
         }
     }
 
+    /// Returns the sysfs directory of the device, _e.g._ `/sys/bus/pci/devices/0000:03:00.0`
+    pub fn path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// Returns the name of the kernel driver bound to the device, _e.g._ `amdgpu`
+    pub fn driver(&self) -> Option<String> {
+        let driver = read_link(self.base_path.join("driver")).ok()?;
+        Some(driver.file_name()?.to_string_lossy().into_owned())
+    }
+
+    /// Returns `None` if any of the device's identifiers can't be read from sysfs.
+    ///
+    /// Devices which aren't listed in the database, _e.g._ recently released
+    /// ones, are named after their vendor and device IDs instead.
     pub fn get_device_name(&self, db: &Database) -> Option<String> {
-        let vendor_value = self.read_value(PciDeviceReadableValues::Vendor);
-        let sub_vendor_value = self.read_value(PciDeviceReadableValues::SubVendor);
-        let device_value = self.read_value(PciDeviceReadableValues::Device);
-        let sub_device_value = self.read_value(PciDeviceReadableValues::SubDevice);
+        let vendor_value = self.read_value(PciDeviceReadableValues::Vendor).ok()?;
+        let sub_vendor_value = self.read_value(PciDeviceReadableValues::SubVendor).ok()?;
+        let device_value = self.read_value(PciDeviceReadableValues::Device).ok()?;
+        let sub_device_value = self.read_value(PciDeviceReadableValues::SubDevice).ok()?;
 
         let Some(vendor) = db.vendors.get(&vendor_value) else {
-            return None;
+            return Some(format!("Unknown GPU [{vendor_value}:{device_value}]"));
         };
 
         let Some(device) = vendor.devices.get(&device_value) else {
-            return None;
+            return Some(format!("{} device [{device_value}]", vendor.name));
         };
         // To return device name if no valid subdevice name is found
         let device_name = device.name.to_owned();
@@ -89,13 +105,31 @@ impl PciDevice {
                 _ => return Some(device_name),
             };
             let end = sub_device.len() - 1;
+            let sub_device_name = sub_device.chars().take(end).skip(start).collect::<String>();
 
-            Some(sub_device.chars().take(end).skip(start).collect::<String>())
+            Some(strip_vendor_prefix(&sub_device_name, &vendor.name).to_string())
         } else {
-            Some(device_name)
+            Some(strip_vendor_prefix(&device_name, &vendor.name).to_string())
         }
     }
 }
+
+/// Strips the vendor's full name from a device name which repeats it, _e.g._
+/// `NVIDIA Corporation NVIDIA GeForce RTX 3080` becomes `NVIDIA GeForce RTX 3080`.
+fn strip_vendor_prefix<'a>(name: &'a str, vendor: &str) -> &'a str {
+    let Some(rest) = name.strip_prefix(vendor).map(str::trim_start) else {
+        return name;
+    };
+
+    let short_vendor = vendor.split_whitespace().next().unwrap_or(vendor);
+
+    if rest.starts_with(short_vendor) {
+        rest
+    } else {
+        name
+    }
+}
+
 pub fn get_pci_devices() -> Result<Vec<PciDevice>, io::Error> {
     let devices_dir = read_dir("/sys/bus/pci/devices/")?;
 
@@ -106,3 +140,54 @@ pub fn get_pci_devices() -> Result<Vec<PciDevice>, io::Error> {
 
     Ok(devices)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::TempDir;
+    use std::fs;
+
+    #[test]
+    fn test_read_value() {
+        let dir = TempDir::new("pci");
+        fs::write(dir.join("class"), "0x060400\n").unwrap();
+        fs::write(dir.join("vendor"), "0x8086\n").unwrap();
+        fs::write(dir.join("device"), "0x7a30\n").unwrap();
+
+        // Some PCI bridges don't expose subsystem_vendor nor subsystem_device.
+        let device = PciDevice::new(dir.to_path_buf());
+        assert_eq!(
+            device.read_value(PciDeviceReadableValues::Class).unwrap(),
+            "060400"
+        );
+        assert_eq!(
+            device.read_value(PciDeviceReadableValues::Vendor).unwrap(),
+            "8086"
+        );
+        assert!(device
+            .read_value(PciDeviceReadableValues::SubVendor)
+            .is_err());
+        assert!(device
+            .read_value(PciDeviceReadableValues::SubDevice)
+            .is_err());
+    }
+
+    #[test]
+    fn test_strip_vendor_prefix() {
+        assert_eq!(
+            strip_vendor_prefix(
+                "NVIDIA Corporation NVIDIA GeForce RTX 3080",
+                "NVIDIA Corporation"
+            ),
+            "NVIDIA GeForce RTX 3080"
+        );
+        assert_eq!(
+            strip_vendor_prefix("GA102 [GeForce RTX 3080]", "NVIDIA Corporation"),
+            "GA102 [GeForce RTX 3080]"
+        );
+        assert_eq!(
+            strip_vendor_prefix("Intel Corporation UHD Graphics", "Intel Corporation"),
+            "Intel Corporation UHD Graphics"
+        );
+    }
+}
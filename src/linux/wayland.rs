@@ -0,0 +1,443 @@
+//! Queries the outputs advertised by the Wayland compositor through `wl_output`
+//! and, if available, `zxdg_output_manager_v1`.
+//!
+//! `libwayland-client` doesn't export the protocol interfaces when it is loaded
+//! at runtime, so the few that are needed are declared here. Events are
+//! handled through dispatchers rather than listeners, which spares us from
+//! declaring a callback per event.
+#![allow(non_upper_case_globals)]
+
+use crate::traits::Display;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::{null, null_mut};
+use wayland_sys::client::*;
+use wayland_sys::common::*;
+use wayland_sys::ffi_dispatch;
+
+macro_rules! cstr {
+    ($s:literal) => {
+        concat!($s, "\0").as_ptr() as *const c_char
+    };
+}
+
+/// Allows raw pointers to be stored in statics.
+struct SyncWrapper<T>(T);
+unsafe impl<T> Sync for SyncWrapper<T> {}
+
+/// Declares a message whose arguments don't refer to other interfaces.
+macro_rules! message {
+    ($name:literal, $signature:literal) => {
+        wl_message {
+            name: cstr!($name),
+            signature: cstr!($signature),
+            types: NULL_TYPES.0.as_ptr(),
+        }
+    };
+}
+
+static NULL_TYPES: SyncWrapper<[*const wl_interface; 8]> = SyncWrapper([null(); 8]);
+
+static wl_registry_requests: SyncWrapper<[wl_message; 1]> = SyncWrapper([message!("bind", "usun")]);
+
+static wl_registry_events: SyncWrapper<[wl_message; 2]> =
+    SyncWrapper([message!("global", "usu"), message!("global_remove", "u")]);
+
+static wl_registry_interface: wl_interface = wl_interface {
+    name: cstr!("wl_registry"),
+    version: 1,
+    request_count: 1,
+    requests: wl_registry_requests.0.as_ptr(),
+    event_count: 2,
+    events: wl_registry_events.0.as_ptr(),
+};
+
+static wl_output_requests: SyncWrapper<[wl_message; 1]> = SyncWrapper([message!("release", "3")]);
+
+static wl_output_events: SyncWrapper<[wl_message; 6]> = SyncWrapper([
+    message!("geometry", "iiiiissi"),
+    message!("mode", "uiii"),
+    message!("done", "2"),
+    message!("scale", "2i"),
+    message!("name", "4s"),
+    message!("description", "4s"),
+]);
+
+static wl_output_interface: wl_interface = wl_interface {
+    name: cstr!("wl_output"),
+    version: 4,
+    request_count: 1,
+    requests: wl_output_requests.0.as_ptr(),
+    event_count: 6,
+    events: wl_output_events.0.as_ptr(),
+};
+
+static zxdg_output_manager_v1_types: SyncWrapper<[*const wl_interface; 2]> = SyncWrapper([
+    &zxdg_output_v1_interface as *const wl_interface,
+    &wl_output_interface as *const wl_interface,
+]);
+
+static zxdg_output_manager_v1_requests: SyncWrapper<[wl_message; 2]> = SyncWrapper([
+    message!("destroy", ""),
+    wl_message {
+        name: cstr!("get_xdg_output"),
+        signature: cstr!("no"),
+        types: zxdg_output_manager_v1_types.0.as_ptr(),
+    },
+]);
+
+static zxdg_output_manager_v1_interface: wl_interface = wl_interface {
+    name: cstr!("zxdg_output_manager_v1"),
+    version: 3,
+    request_count: 2,
+    requests: zxdg_output_manager_v1_requests.0.as_ptr(),
+    event_count: 0,
+    events: null(),
+};
+
+static zxdg_output_v1_requests: SyncWrapper<[wl_message; 1]> =
+    SyncWrapper([message!("destroy", "")]);
+
+static zxdg_output_v1_events: SyncWrapper<[wl_message; 5]> = SyncWrapper([
+    message!("logical_position", "ii"),
+    message!("logical_size", "ii"),
+    message!("done", ""),
+    message!("name", "2s"),
+    message!("description", "2s"),
+]);
+
+static zxdg_output_v1_interface: wl_interface = wl_interface {
+    name: cstr!("zxdg_output_v1"),
+    version: 3,
+    request_count: 1,
+    requests: zxdg_output_v1_requests.0.as_ptr(),
+    event_count: 5,
+    events: zxdg_output_v1_events.0.as_ptr(),
+};
+
+const WL_DISPLAY_GET_REGISTRY: u32 = 1;
+const WL_REGISTRY_BIND: u32 = 0;
+const WL_OUTPUT_MODE_CURRENT: u32 = 0x1;
+const ZXDG_OUTPUT_MANAGER_V1_GET_XDG_OUTPUT: u32 = 1;
+
+/// The globals of interest advertised by the registry, as `(name, version)`.
+#[derive(Default)]
+struct Globals {
+    outputs: Vec<(u32, u32)>,
+    xdg_output_manager: Option<(u32, u32)>,
+}
+
+/// The state of a single output, as reported by `wl_output` and `zxdg_output_v1`.
+#[derive(Default)]
+struct Output {
+    name: Option<String>,
+    mode: Option<(i32, i32, i32)>,
+    scale: i32,
+    logical_size: Option<(i32, i32)>,
+}
+
+impl Output {
+    fn to_display(&self) -> Option<Display> {
+        let (width, height, refresh) = self.mode?;
+
+        let scaled = match self.logical_size {
+            Some(size) if size != (width, height) => Some(size),
+            Some(_) => None,
+            None if self.scale > 1 => Some((width / self.scale, height / self.scale)),
+            None => None,
+        };
+
+        Some(Display {
+            name: self.name.clone(),
+            width: width as u32,
+            height: height as u32,
+            // Refresh rates are expressed in mHz.
+            refresh_rate: (refresh > 0).then_some(refresh as f32 / 1000.0),
+            scaled_width: scaled.map(|(width, _)| width as u32),
+            scaled_height: scaled.map(|(_, height)| height as u32),
+        })
+    }
+}
+
+unsafe fn string_argument(argument: &wl_argument) -> Option<String> {
+    if argument.s.is_null() {
+        return None;
+    }
+
+    Some(CStr::from_ptr(argument.s).to_string_lossy().into_owned())
+}
+
+unsafe extern "C" fn dispatch_registry(
+    data: *const c_void,
+    _: *mut c_void,
+    opcode: u32,
+    _: *const wl_message,
+    arguments: *const wl_argument,
+) -> c_int {
+    let globals = &mut *(data as *mut Globals);
+
+    // Only the "global" event is of interest.
+    if opcode != 0 {
+        return 0;
+    }
+
+    let arguments = std::slice::from_raw_parts(arguments, 3);
+    let (name, version) = (arguments[0].u, arguments[2].u);
+
+    match string_argument(&arguments[1]).as_deref() {
+        Some("wl_output") => globals.outputs.push((name, version)),
+        Some("zxdg_output_manager_v1") => globals.xdg_output_manager = Some((name, version)),
+        _ => (),
+    }
+
+    0
+}
+
+unsafe extern "C" fn dispatch_output(
+    data: *const c_void,
+    _: *mut c_void,
+    opcode: u32,
+    _: *const wl_message,
+    arguments: *const wl_argument,
+) -> c_int {
+    let output = &mut *(data as *mut Output);
+
+    match opcode {
+        // mode
+        1 => {
+            let arguments = std::slice::from_raw_parts(arguments, 4);
+            if arguments[0].u & WL_OUTPUT_MODE_CURRENT != 0 {
+                output.mode = Some((arguments[1].i, arguments[2].i, arguments[3].i));
+            }
+        }
+        // scale
+        3 => output.scale = (*arguments).i,
+        // name
+        4 => output.name = string_argument(&*arguments),
+        _ => (),
+    }
+
+    0
+}
+
+unsafe extern "C" fn dispatch_xdg_output(
+    data: *const c_void,
+    _: *mut c_void,
+    opcode: u32,
+    _: *const wl_message,
+    arguments: *const wl_argument,
+) -> c_int {
+    let output = &mut *(data as *mut Output);
+
+    match opcode {
+        // logical_size
+        1 => {
+            let arguments = std::slice::from_raw_parts(arguments, 2);
+            output.logical_size = Some((arguments[0].i, arguments[1].i));
+        }
+        // name, which wl_output only reports from version 4 onwards.
+        3 if output.name.is_none() => output.name = string_argument(&*arguments),
+        _ => (),
+    }
+
+    0
+}
+
+unsafe fn bind(
+    registry: *mut wl_proxy,
+    name: u32,
+    interface: &'static wl_interface,
+    version: u32,
+) -> *mut wl_proxy {
+    let version = version.min(interface.version as u32);
+    let mut arguments = [
+        wl_argument { u: name },
+        wl_argument { s: interface.name },
+        wl_argument { u: version },
+        wl_argument { o: null() },
+    ];
+
+    ffi_dispatch!(
+        wayland_client_handle(),
+        wl_proxy_marshal_array_constructor_versioned,
+        registry,
+        WL_REGISTRY_BIND,
+        arguments.as_mut_ptr(),
+        interface,
+        version
+    )
+}
+
+/// Returns the outputs of the compositor that `WAYLAND_DISPLAY` points to, or
+/// `None` if it couldn't be reached.
+pub fn displays() -> Option<Vec<Display>> {
+    if !is_lib_available() {
+        return None;
+    }
+
+    let handle = wayland_client_handle();
+
+    unsafe {
+        let display = ffi_dispatch!(handle, wl_display_connect, null());
+        if display.is_null() {
+            return None;
+        }
+
+        let outputs = query_outputs(display);
+        ffi_dispatch!(handle, wl_display_disconnect, display);
+
+        Some(
+            outputs?
+                .iter()
+                .filter_map(|output| output.to_display())
+                .collect(),
+        )
+    }
+}
+
+#[allow(clippy::vec_box)]
+unsafe fn query_outputs(display: *mut wl_display) -> Option<Vec<Box<Output>>> {
+    let handle = wayland_client_handle();
+    let mut proxies: Vec<*mut wl_proxy> = Vec::new();
+
+    let registry = ffi_dispatch!(
+        handle,
+        wl_proxy_marshal_array_constructor,
+        display as *mut wl_proxy,
+        WL_DISPLAY_GET_REGISTRY,
+        [wl_argument { o: null() }].as_mut_ptr(),
+        &wl_registry_interface
+    );
+
+    if registry.is_null() {
+        return None;
+    }
+
+    proxies.push(registry);
+
+    let mut globals = Box::<Globals>::default();
+    ffi_dispatch!(
+        handle,
+        wl_proxy_add_dispatcher,
+        registry,
+        dispatch_registry,
+        &mut *globals as *mut Globals as *const c_void,
+        null_mut()
+    );
+
+    let mut outputs: Vec<Box<Output>> = Vec::new();
+    let mut result = None;
+
+    if ffi_dispatch!(handle, wl_display_roundtrip, display) >= 0 {
+        let manager = globals
+            .xdg_output_manager
+            .map(|(name, version)| bind(registry, name, &zxdg_output_manager_v1_interface, version))
+            .filter(|manager| !manager.is_null());
+
+        if let Some(manager) = manager {
+            proxies.push(manager);
+        }
+
+        for &(name, version) in &globals.outputs {
+            let proxy = bind(registry, name, &wl_output_interface, version);
+            if proxy.is_null() {
+                continue;
+            }
+
+            proxies.push(proxy);
+
+            // Boxing keeps the address handed to the dispatchers stable.
+            let mut output = Box::new(Output {
+                scale: 1,
+                ..Default::default()
+            });
+            let data = &mut *output as *mut Output as *const c_void;
+
+            ffi_dispatch!(
+                handle,
+                wl_proxy_add_dispatcher,
+                proxy,
+                dispatch_output,
+                data,
+                null_mut()
+            );
+
+            if let Some(manager) = manager {
+                let xdg_output = ffi_dispatch!(
+                    handle,
+                    wl_proxy_marshal_array_constructor,
+                    manager,
+                    ZXDG_OUTPUT_MANAGER_V1_GET_XDG_OUTPUT,
+                    [
+                        wl_argument { o: null() },
+                        wl_argument {
+                            o: proxy as *const c_void
+                        }
+                    ]
+                    .as_mut_ptr(),
+                    &zxdg_output_v1_interface
+                );
+
+                if !xdg_output.is_null() {
+                    proxies.push(xdg_output);
+                    ffi_dispatch!(
+                        handle,
+                        wl_proxy_add_dispatcher,
+                        xdg_output,
+                        dispatch_xdg_output,
+                        data,
+                        null_mut()
+                    );
+                }
+            }
+
+            outputs.push(output);
+        }
+
+        if ffi_dispatch!(handle, wl_display_roundtrip, display) >= 0 {
+            result = Some(outputs);
+        }
+    }
+
+    for proxy in proxies.into_iter().rev() {
+        ffi_dispatch!(handle, wl_proxy_destroy, proxy);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_to_display() {
+        let mut output = Output {
+            name: Some(String::from("eDP-1")),
+            mode: Some((2880, 1800, 59_990)),
+            scale: 2,
+            logical_size: None,
+        };
+
+        let display = output.to_display().unwrap();
+        assert_eq!(display.refresh_rate, Some(59.99));
+        assert_eq!(
+            (display.scaled_width, display.scaled_height),
+            (Some(1440), Some(900))
+        );
+
+        // Fractional scales are only reflected by the logical size.
+        output.logical_size = Some((1920, 1200));
+        let display = output.to_display().unwrap();
+        assert_eq!(
+            (display.scaled_width, display.scaled_height),
+            (Some(1920), Some(1200))
+        );
+
+        output.logical_size = Some((2880, 1800));
+        assert_eq!(output.to_display().unwrap().scaled_width, None);
+
+        output.mode = None;
+        assert!(output.to_display().is_none());
+    }
+}
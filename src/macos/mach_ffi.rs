@@ -8,9 +8,13 @@ use mach2::message::mach_msg_type_number_t;
 use mach2::vm_types::{integer_t, natural_t};
 
 use core_foundation::array::CFArrayRef;
-use core_foundation::base::{mach_port_t, CFAllocatorRef, CFRelease, CFTypeRef, TCFTypeRef};
+use core_foundation::base::{
+    mach_port_t, Boolean, CFAllocatorRef, CFRelease, CFTypeRef, TCFTypeRef,
+};
 use core_foundation::dictionary::{CFDictionaryRef, CFMutableDictionaryRef};
+use core_foundation::error::CFErrorRef;
 use core_foundation::string::CFStringRef;
+use core_foundation::url::CFURLRef;
 use core_graphics::display::CGDirectDisplayID;
 use libc::c_char;
 use std::os::raw::c_uint;
@@ -21,6 +25,10 @@ pub type io_object_t = mach_port_t;
 pub type io_service_t = io_object_t;
 pub type IOOptionBits = c_uint;
 pub type io_registry_entry_t = io_object_t;
+pub type io_iterator_t = io_object_t;
+pub type io_connect_t = io_object_t;
+pub type id = *mut libc::c_void;
+pub type SEL = *mut libc::c_void;
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Default)]
@@ -51,6 +59,109 @@ pub(crate) struct vm_statistics64 {
     pub total_uncompressed_pages_in_compressor: u64,
 }
 
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct SMCKeyData_vers_t {
+    pub major: u8,
+    pub minor: u8,
+    pub build: u8,
+    pub reserved: u8,
+    pub release: u16,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct SMCKeyData_pLimitData_t {
+    pub version: u16,
+    pub length: u16,
+    pub cpu_plimit: u32,
+    pub gpu_plimit: u32,
+    pub mem_plimit: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct SMCKeyData_keyInfo_t {
+    pub data_size: u32,
+    pub data_type: u32,
+    pub data_attributes: u8,
+}
+
+/// The structure exchanged with the `AppleSMC` kernel extension, see
+/// `IOConnectCallStructMethod`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct SMCKeyData_t {
+    pub key: u32,
+    pub vers: SMCKeyData_vers_t,
+    pub p_limit_data: SMCKeyData_pLimitData_t,
+    pub key_info: SMCKeyData_keyInfo_t,
+    pub result: u8,
+    pub status: u8,
+    pub data8: u8,
+    pub data32: u32,
+    pub bytes: [u8; 32],
+}
+
+#[repr(C, packed(4))]
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct timeval32 {
+    pub tv_sec: i32,
+    pub tv_usec: i32,
+}
+
+/// The 64-bit interface statistics, declared in `net/if_var.h`.
+#[repr(C, packed(4))]
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct if_data64 {
+    pub ifi_type: u8,
+    pub ifi_typelen: u8,
+    pub ifi_physical: u8,
+    pub ifi_addrlen: u8,
+    pub ifi_hdrlen: u8,
+    pub ifi_recvquota: u8,
+    pub ifi_xmitquota: u8,
+    pub ifi_unused1: u8,
+    pub ifi_mtu: u32,
+    pub ifi_metric: u32,
+    pub ifi_baudrate: u64,
+    pub ifi_ipackets: u64,
+    pub ifi_ierrors: u64,
+    pub ifi_opackets: u64,
+    pub ifi_oerrors: u64,
+    pub ifi_collisions: u64,
+    pub ifi_ibytes: u64,
+    pub ifi_obytes: u64,
+    pub ifi_imcasts: u64,
+    pub ifi_omcasts: u64,
+    pub ifi_iqdrops: u64,
+    pub ifi_noproto: u64,
+    pub ifi_recvtiming: u32,
+    pub ifi_xmittiming: u32,
+    pub ifi_lastchange: timeval32,
+}
+
+/// The `RTM_IFINFO2` message returned by the `NET_RT_IFLIST2` sysctl, declared in
+/// `net/if.h`.
+#[repr(C, packed(4))]
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct if_msghdr2 {
+    pub ifm_msglen: u16,
+    pub ifm_version: u8,
+    pub ifm_type: u8,
+    pub ifm_addrs: i32,
+    pub ifm_flags: i32,
+    pub ifm_index: u16,
+    pub ifm_snd_len: i32,
+    pub ifm_snd_maxlen: i32,
+    pub ifm_snd_drops: i32,
+    pub ifm_timer: i32,
+    pub ifm_data: if_data64,
+}
+
+pub(crate) const NET_RT_IFLIST2: i32 = 6;
+pub(crate) const RTM_IFINFO2: u8 = 0x12;
+
 extern "C" {
     pub fn host_statistics64(
         host_priv: host_t,
@@ -71,6 +182,31 @@ extern "C" {
         matching: CFDictionaryRef,
     ) -> io_service_t;
 
+    pub fn IOServiceGetMatchingServices(
+        master_port: mach_port_t,
+        matching: CFDictionaryRef,
+        existing: *mut io_iterator_t,
+    ) -> kern_return_t;
+
+    pub fn IOIteratorNext(iterator: io_iterator_t) -> io_object_t;
+
+    pub fn IORegistryEntryGetChildEntry(
+        entry: io_registry_entry_t,
+        plane: *const c_char,
+        child: *mut io_registry_entry_t,
+    ) -> kern_return_t;
+
+    pub fn IORegistryEntryFromPath(
+        main_port: mach_port_t,
+        path: *const c_char,
+    ) -> io_registry_entry_t;
+
+    pub fn IORegistryEntryGetParentEntry(
+        entry: io_registry_entry_t,
+        plane: *const c_char,
+        parent: *mut io_registry_entry_t,
+    ) -> kern_return_t;
+
     pub fn IORegistryEntryCreateCFProperties(
         entry: io_registry_entry_t,
         properties: *mut CFMutableDictionaryRef,
@@ -80,5 +216,68 @@ extern "C" {
 
     pub fn IOObjectRelease(object: io_object_t) -> kern_return_t;
 
+    pub fn IOServiceOpen(
+        service: io_service_t,
+        owning_task: mach_port_t,
+        connect_type: u32,
+        connect: *mut io_connect_t,
+    ) -> kern_return_t;
+
+    pub fn IOServiceClose(connect: io_connect_t) -> kern_return_t;
+
+    pub fn IOConnectCallStructMethod(
+        connection: mach_port_t,
+        selector: u32,
+        input_struct: *const libc::c_void,
+        input_struct_cnt: libc::size_t,
+        output_struct: *mut libc::c_void,
+        output_struct_cnt: *mut libc::size_t,
+    ) -> kern_return_t;
+
     pub fn DisplayServicesGetBrightness(id: CGDirectDisplayID, brightness: *mut f32) -> i32;
+
+    pub fn CoreDisplay_Display_GetUserBrightness(id: CGDirectDisplayID) -> f64;
+
+    pub static kCFPreferencesAnyApplication: CFStringRef;
+
+    pub static kCFURLVolumeAvailableCapacityForImportantUsageKey: CFStringRef;
+
+    pub fn CFURLCopyResourcePropertyForKey(
+        url: CFURLRef,
+        key: CFStringRef,
+        value: *mut CFTypeRef,
+        error: *mut CFErrorRef,
+    ) -> Boolean;
+
+    pub fn CFPreferencesCopyAppValue(key: CFStringRef, application_id: CFStringRef) -> CFTypeRef;
+}
+
+#[link(name = "objc")]
+extern "C" {
+    pub fn objc_getClass(name: *const c_char) -> id;
+
+    pub fn sel_registerName(name: *const c_char) -> SEL;
+
+    /// Has to be transmuted to a function pointer matching the signature of the method that
+    /// is being invoked before being called.
+    pub fn objc_msgSend();
+
+    pub fn objc_autoreleasePoolPush() -> *mut libc::c_void;
+
+    pub fn objc_autoreleasePoolPop(pool: *mut libc::c_void);
+}
+
+#[link(name = "CoreWLAN", kind = "framework")]
+extern "C" {}
+
+#[link(name = "SystemConfiguration", kind = "framework")]
+extern "C" {
+    pub fn SCDynamicStoreCreate(
+        allocator: CFAllocatorRef,
+        name: CFStringRef,
+        callout: *const libc::c_void,
+        context: *mut libc::c_void,
+    ) -> CFTypeRef;
+
+    pub fn SCDynamicStoreCopyValue(store: CFTypeRef, key: CFStringRef) -> CFTypeRef;
 }
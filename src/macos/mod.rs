@@ -1,6 +1,25 @@
 #![allow(clippy::unnecessary_cast)]
 use crate::extra;
-use crate::macos::mach_ffi::{io_registry_entry_t, DisplayServicesGetBrightness, IOObjectRelease};
+use crate::macos::mach_ffi::{
+    id, objc_autoreleasePoolPop, objc_autoreleasePoolPush, objc_getClass, objc_msgSend,
+    sel_registerName, SCDynamicStoreCopyValue, SCDynamicStoreCreate, SEL,
+};
+use crate::macos::mach_ffi::{if_data64, if_msghdr2, NET_RT_IFLIST2, RTM_IFINFO2};
+use crate::macos::mach_ffi::{
+    io_connect_t, IOConnectCallStructMethod, IOServiceClose, IOServiceOpen, SMCKeyData_t,
+};
+use crate::macos::mach_ffi::{
+    io_iterator_t, IOIteratorNext, IORegistryEntryFromPath, IORegistryEntryGetChildEntry,
+    IORegistryEntryGetParentEntry, IOServiceGetMatchingServices,
+};
+use crate::macos::mach_ffi::{
+    io_registry_entry_t, CoreDisplay_Display_GetUserBrightness, DisplayServicesGetBrightness,
+    IOObjectRelease,
+};
+use crate::macos::mach_ffi::{kCFPreferencesAnyApplication, CFPreferencesCopyAppValue};
+use crate::macos::mach_ffi::{
+    kCFURLVolumeAvailableCapacityForImportantUsageKey, CFURLCopyResourcePropertyForKey,
+};
 use crate::macos::mach_ffi::{
     kIOMasterPortDefault, vm_statistics64, IORegistryEntryCreateCFProperties,
     IOServiceGetMatchingService, IOServiceMatching,
@@ -8,17 +27,23 @@ use crate::macos::mach_ffi::{
 use crate::shared;
 use crate::traits::ReadoutError::MetricNotAvailable;
 use crate::traits::*;
-use core_foundation::base::{TCFType, ToVoid};
-use core_foundation::dictionary::{CFMutableDictionary, CFMutableDictionaryRef};
+use core_foundation::array::{CFArray, CFArrayRef};
+use core_foundation::base::{CFType, CFTypeRef, TCFType, ToVoid};
+use core_foundation::boolean::{CFBoolean, CFBooleanRef};
+use core_foundation::data::{CFData, CFDataRef};
+use core_foundation::dictionary::{
+    CFDictionary, CFDictionaryRef, CFMutableDictionary, CFMutableDictionaryRef,
+};
 use core_foundation::number::{CFNumber, CFNumberRef};
-use core_foundation::string::CFString;
-use core_graphics::display::{CGDisplay, CGMainDisplayID};
+use core_foundation::propertylist::CFPropertyList;
+use core_foundation::string::{CFString, CFStringRef};
+use core_graphics::display::CGDisplay;
 use core_video_sys::{
     kCVTimeIsIndefinite, CVDisplayLinkCreateWithCGDisplay,
     CVDisplayLinkGetNominalOutputVideoRefreshPeriod, CVDisplayLinkRef, CVDisplayLinkRelease,
 };
 use mach2::kern_return::KERN_SUCCESS;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use std::fs::DirEntry;
 use std::path::Path;
 use sysctl::{Ctl, Sysctl};
@@ -43,6 +68,7 @@ pub struct MacOSGeneralReadout {
     boot_time_ctl: Option<Ctl>,
     hostname_ctl: Option<Ctl>,
     os_product_version_ctl: Option<Ctl>,
+    cpu_frequency_ctl: Option<Ctl>,
 }
 
 pub struct MacOSMemoryReadout {
@@ -55,11 +81,23 @@ struct MacOSIOPMPowerSource {
     battery_installed: Option<bool>,
     state_of_charge: Option<usize>,
     charging: Option<bool>,
+    time_remaining: Option<usize>,
+    temperature: Option<f32>,
+    power_draw: Option<f32>,
+    health: Option<u8>,
+}
+
+/// A connection to the System Management Controller, which holds the readings of the
+/// hardware sensors.
+struct MacOSSMC {
+    connection: io_connect_t,
 }
 
 pub struct MacOSPackageReadout;
 
-pub struct MacOSNetworkReadout;
+pub struct MacOSNetworkReadout {
+    default_interface: shared::DefaultInterface,
+}
 
 impl BatteryReadout for MacOSBatteryReadout {
     fn new() -> Self {
@@ -95,7 +133,39 @@ impl BatteryReadout for MacOSBatteryReadout {
     }
 
     fn health(&self) -> Result<u8, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let power_info = self.power_info.as_ref()?;
+
+        power_info.health.ok_or_else(|| {
+            ReadoutError::Other(String::from(
+                "Capacity properties were not present in the dictionary that was returned from IOKit.",
+            ))
+        })
+    }
+
+    fn time_remaining(&self) -> Result<usize, ReadoutError> {
+        let power_info = self.power_info.as_ref()?;
+
+        Ok(power_info
+            .time_remaining
+            .ok_or_else(|| ReadoutError::Other(String::from(
+                "TimeRemaining property was not present in the dictionary that was returned from IOKit.",
+            )))?)
+    }
+
+    fn temperature(&self) -> Result<f32, ReadoutError> {
+        let power_info = self.power_info.as_ref()?;
+
+        power_info
+            .temperature
+            .ok_or(ReadoutError::MetricNotAvailable)
+    }
+
+    fn power_draw(&self) -> Result<f32, ReadoutError> {
+        let power_info = self.power_info.as_ref()?;
+
+        power_info
+            .power_draw
+            .ok_or(ReadoutError::MetricNotAvailable)
     }
 }
 
@@ -124,6 +194,12 @@ impl MacOSIOPMPowerSource {
                 instance.battery_installed = Some(number.to_i32() != Some(0));
             }
 
+            if instance.battery_installed == Some(false) {
+                return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+                    "No batteries detected.",
+                )));
+            }
+
             if let Some(state_of_charge) =
                 battery_data_dict.find(&CFString::new("StateOfCharge").to_void())
             {
@@ -135,6 +211,62 @@ impl MacOSIOPMPowerSource {
                 let number = CFNumber::wrap_under_get_rule((*charging) as CFNumberRef);
                 instance.charging = Some(number.to_i32() != Some(0));
             }
+
+            if let Some(time_remaining) =
+                power_source_dict.find(&CFString::new("TimeRemaining").to_void())
+            {
+                let number = CFNumber::wrap_under_get_rule((*time_remaining) as CFNumberRef);
+
+                // IOKit reports the remaining time in minutes, where 65535 means
+                // that the estimate is still being calculated.
+                if let Some(minutes) = number.to_i32().filter(|m| *m >= 0 && *m != 65535) {
+                    instance.time_remaining = Some(minutes as usize * 60);
+                }
+            }
+
+            // The top-level temperature is expressed in hundredths of a degree Celsius, while
+            // the one found in the battery data is the raw reading of the battery's gauge,
+            // which is expressed in tenths of a kelvin.
+            if let Some(temperature) =
+                power_source_dict.find(&CFString::new("Temperature").to_void())
+            {
+                let number = CFNumber::wrap_under_get_rule((*temperature) as CFNumberRef);
+                instance.temperature = number.to_i32().map(|t| t as f32 / 100.0);
+            } else if let Some(temperature) =
+                battery_data_dict.find(&CFString::new("Temperature").to_void())
+            {
+                let number = CFNumber::wrap_under_get_rule((*temperature) as CFNumberRef);
+                instance.temperature = number.to_i32().map(|t| t as f32 / 10.0 - 273.15);
+            }
+
+            // The amperage (mA) is negative while discharging, the voltage is in mV.
+            if let (Some(amperage), Some(voltage)) = (
+                power_source_dict.find(&CFString::new("InstantAmperage").to_void()),
+                power_source_dict.find(&CFString::new("Voltage").to_void()),
+            ) {
+                let amperage = CFNumber::wrap_under_get_rule((*amperage) as CFNumberRef);
+                let voltage = CFNumber::wrap_under_get_rule((*voltage) as CFNumberRef);
+
+                if let (Some(amperage), Some(voltage)) = (amperage.to_i64(), voltage.to_i64()) {
+                    instance.power_draw = Some(-(amperage * voltage) as f32 / 1_000_000.0);
+                }
+            }
+
+            // The power source is the AppleSmartBattery service, which also holds the
+            // capacities of the battery.
+            let capacity = |key: &str| {
+                power_source_dict
+                    .find(&CFString::new(key).to_void())
+                    .and_then(|capacity| {
+                        CFNumber::wrap_under_get_rule((*capacity) as CFNumberRef).to_i64()
+                    })
+            };
+
+            instance.health = battery_health(
+                capacity("DesignCapacity"),
+                capacity("AppleRawMaxCapacity").or_else(|| capacity("NominalChargeCapacity")),
+                capacity("MaxCapacity"),
+            );
         }
 
         Ok(instance)
@@ -169,12 +301,113 @@ impl MacOSIOPMPowerSource {
             }
         }
 
-        dict_data.ok_or_else(|| ReadoutError::Other(String::from(
+        dict_data.ok_or_else(|| ReadoutError::MetricUnavailableForHardware(String::from(
             "Unable to get the 'IOPMPowerSource' service from IOKit :( Are you on a desktop system?",
         )))
     }
 }
 
+/// Computes the health of a battery from its design capacity (mAh) and its full charge
+/// capacity, preferring the raw one (mAh) over `MaxCapacity`, which Apple Silicon machines
+/// report as a percentage of the design capacity instead.
+fn battery_health(design: Option<i64>, raw_max: Option<i64>, max: Option<i64>) -> Option<u8> {
+    let design = design.filter(|design| *design > 0)?;
+
+    let health = match (raw_max, max) {
+        (Some(raw_max), _) => raw_max as f64 / design as f64 * 100.0,
+        (None, Some(max)) if max <= 100 && design > 100 => max as f64,
+        (None, Some(max)) => max as f64 / design as f64 * 100.0,
+        (None, None) => return None,
+    };
+
+    Some(health.clamp(0.0, 100.0).ceil() as u8)
+}
+
+impl MacOSSMC {
+    const KERNEL_INDEX_SMC: u32 = 2;
+    const CMD_READ_BYTES: u8 = 5;
+    const CMD_READ_KEYINFO: u8 = 9;
+
+    fn open() -> Result<Self, ReadoutError> {
+        let io_service_name = CString::new("AppleSMC").expect("Unable to create c string");
+        let service = unsafe { IOServiceMatching(io_service_name.as_ptr()) };
+        let device = unsafe { IOServiceGetMatchingService(kIOMasterPortDefault, service) };
+
+        if device == 0 {
+            return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+                "Unable to get the 'AppleSMC' service from IOKit.",
+            )));
+        }
+
+        let mut connection: io_connect_t = 0;
+        let kern_return = unsafe {
+            let ret = IOServiceOpen(device, mach2::traps::mach_task_self(), 0, &mut connection);
+            IOObjectRelease(device);
+            ret
+        };
+
+        if kern_return != KERN_SUCCESS {
+            return Err(ReadoutError::Other(format!(
+                "Opening a connection to the SMC failed with return code: {kern_return}"
+            )));
+        }
+
+        Ok(MacOSSMC { connection })
+    }
+
+    fn call(&self, input: &SMCKeyData_t) -> Option<SMCKeyData_t> {
+        let mut output = SMCKeyData_t::default();
+        let mut output_size = std::mem::size_of::<SMCKeyData_t>();
+
+        let kern_return = unsafe {
+            IOConnectCallStructMethod(
+                self.connection,
+                Self::KERNEL_INDEX_SMC,
+                input as *const SMCKeyData_t as *const libc::c_void,
+                std::mem::size_of::<SMCKeyData_t>(),
+                &mut output as *mut SMCKeyData_t as *mut libc::c_void,
+                &mut output_size,
+            )
+        };
+
+        (kern_return == KERN_SUCCESS && output.result == 0).then_some(output)
+    }
+
+    /// Reads the given four-character key and decodes it as a temperature in degrees Celsius.
+    fn read_temperature(&self, key: &str) -> Option<f32> {
+        let key = u32::from_be_bytes(key.as_bytes().try_into().ok()?);
+
+        let key_info = self.call(&SMCKeyData_t {
+            key,
+            data8: Self::CMD_READ_KEYINFO,
+            ..Default::default()
+        })?;
+
+        let value = self.call(&SMCKeyData_t {
+            key,
+            key_info: key_info.key_info,
+            data8: Self::CMD_READ_BYTES,
+            ..Default::default()
+        })?;
+
+        let bytes = value.bytes;
+        match &key_info.key_info.data_type.to_be_bytes() {
+            // Signed fixed point number with 8 fractional bits.
+            b"sp78" => Some(bytes[0] as i8 as f32 + bytes[1] as f32 / 256.0),
+            b"flt " => Some(f32::from_le_bytes(bytes[..4].try_into().ok()?)),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for MacOSSMC {
+    fn drop(&mut self) {
+        unsafe {
+            IOServiceClose(self.connection);
+        }
+    }
+}
+
 impl KernelReadout for MacOSKernelReadout {
     fn new() -> Self {
         MacOSKernelReadout {
@@ -202,6 +435,20 @@ impl KernelReadout for MacOSKernelReadout {
     fn pretty_kernel(&self) -> Result<String, ReadoutError> {
         Ok(format!("{} {}", self.os_type()?, self.os_release()?))
     }
+
+    fn architecture(&self) -> Result<String, ReadoutError> {
+        // Processes running under Rosetta 2 are told they're running on x86_64 hardware.
+        let translated = Ctl::new("sysctl.proc_translated")
+            .and_then(|ctl| ctl.value())
+            .map(|value| matches!(value, sysctl::CtlValue::Int(1)))
+            .unwrap_or(false);
+
+        if translated {
+            return Ok(String::from("arm64"));
+        }
+
+        shared::architecture()
+    }
 }
 
 impl GeneralReadout for MacOSGeneralReadout {
@@ -211,26 +458,62 @@ impl GeneralReadout for MacOSGeneralReadout {
             boot_time_ctl: Ctl::new("kern.boottime").ok(),
             hostname_ctl: Ctl::new("kern.hostname").ok(),
             os_product_version_ctl: Ctl::new("kern.osproductversion").ok(),
+            cpu_frequency_ctl: Ctl::new("hw.cpufrequency").ok(),
         }
     }
 
     fn backlight(&self) -> Result<usize, ReadoutError> {
-        let main_display = unsafe { CGMainDisplayID() };
-        let mut display_brightness: f32 = 0.0;
+        // Only the built-in display has a backlight that can be read, and it's inactive
+        // while the lid is closed.
+        let builtin_display = CGDisplay::active_displays()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|&display| CGDisplay::new(display).is_builtin())
+            .ok_or(MetricNotAvailable)?;
 
+        let mut display_brightness: f32 = 0.0;
         let return_value =
-            unsafe { DisplayServicesGetBrightness(main_display, &mut display_brightness) };
+            unsafe { DisplayServicesGetBrightness(builtin_display, &mut display_brightness) };
 
         if return_value == 0 {
-            Ok((display_brightness * 100.0) as usize)
-        } else {
-            Err(ReadoutError::Other(format!(
-                "Could not query display brightness of main display, got return code {return_value}"
-            )))
+            return Ok((display_brightness * 100.0) as usize);
         }
+
+        // DisplayServices frequently fails on Apple Silicon machines, where CoreDisplay
+        // still works.
+        let user_brightness = unsafe { CoreDisplay_Display_GetUserBrightness(builtin_display) };
+        if (0.0..=1.0).contains(&user_brightness) {
+            return Ok((user_brightness * 100.0).round() as usize);
+        }
+
+        MacOSGeneralReadout::registry_brightness().ok_or_else(|| {
+            ReadoutError::Other(format!(
+                "Could not query display brightness of built-in display, got return code {return_value}"
+            ))
+        })
     }
 
     fn resolution(&self) -> Result<String, ReadoutError> {
+        let output: Vec<String> = self
+            .displays()?
+            .iter()
+            .map(
+                |display| match (display.scaled_width, display.scaled_height) {
+                    (Some(ui_width), Some(ui_height)) => format!(
+                        "{}x{}@{}fps (as {ui_width}x{ui_height})",
+                        display.width,
+                        display.height,
+                        display.refresh_rate.unwrap_or_default() as i32
+                    ),
+                    _ => format!("{}x{}", display.width, display.height),
+                },
+            )
+            .collect();
+
+        Ok(output.join("\n"))
+    }
+
+    fn displays(&self) -> Result<Vec<Display>, ReadoutError> {
         let displays = CGDisplay::active_displays();
         if let Err(e) = displays {
             return Err(ReadoutError::Other(format!(
@@ -245,42 +528,53 @@ impl GeneralReadout for MacOSGeneralReadout {
             .filter(|d| d.is_active())
             .collect();
 
-        let mut output: Vec<String> = Vec::with_capacity(displays.len());
+        let mut output: Vec<Display> = Vec::with_capacity(displays.len());
 
         for display in displays {
             let (ui_width, ui_height) = (display.pixels_wide(), display.pixels_high());
-            let mut out_string: String = format!("{ui_width}x{ui_height}");
+            let mut info = Display {
+                name: None,
+                width: ui_width as u32,
+                height: ui_height as u32,
+                refresh_rate: None,
+                scaled_width: None,
+                scaled_height: None,
+            };
 
             if let Some(mode) = display.display_mode() {
                 let (real_width, real_height) = (mode.pixel_width(), mode.pixel_height());
 
-                let mut refresh_rate: i32 = mode.refresh_rate().round() as i32;
-                if refresh_rate == 0 {
+                let mut refresh_rate = mode.refresh_rate();
+                if refresh_rate == 0.0 {
                     unsafe {
                         let mut link: CVDisplayLinkRef = std::mem::zeroed();
                         CVDisplayLinkCreateWithCGDisplay(display.id, &mut link);
 
                         let time = CVDisplayLinkGetNominalOutputVideoRefreshPeriod(link);
                         if (time.flags & kCVTimeIsIndefinite) == 0 {
-                            refresh_rate =
-                                ((time.timeScale as f64) / (time.timeValue as f64)) as i32;
+                            refresh_rate = (time.timeScale as f64) / (time.timeValue as f64);
                         }
 
                         CVDisplayLinkRelease(link);
                     }
                 }
 
+                if refresh_rate > 0.0 {
+                    info.refresh_rate = Some(refresh_rate as f32);
+                }
+
                 if real_width != ui_width || real_height != ui_height {
-                    out_string = format!(
-                        "{real_width}x{real_height}@{refresh_rate}fps (as {ui_width}x{ui_height})"
-                    );
+                    info.width = real_width as u32;
+                    info.height = real_height as u32;
+                    info.scaled_width = Some(ui_width as u32);
+                    info.scaled_height = Some(ui_height as u32);
                 }
             }
 
-            output.push(out_string);
+            output.push(info);
         }
 
-        Ok(output.join("\n"))
+        Ok(output)
     }
 
     fn username(&self) -> Result<String, ReadoutError> {
@@ -313,6 +607,24 @@ impl GeneralReadout for MacOSGeneralReadout {
         Ok(String::from("Quartz Compositor"))
     }
 
+    fn theme(&self) -> Result<String, ReadoutError> {
+        // AppleInterfaceStyle is only set while dark mode is enabled.
+        let key = CFString::from_static_string("AppleInterfaceStyle");
+        let value = unsafe {
+            CFPreferencesCopyAppValue(key.as_concrete_TypeRef(), kCFPreferencesAnyApplication)
+        };
+
+        if value.is_null() {
+            return Ok(String::from("Light"));
+        }
+
+        let value = unsafe { CFType::wrap_under_create_rule(value) };
+        match value.downcast::<CFString>() {
+            Some(style) if style.to_string() == "Dark" => Ok(String::from("Dark")),
+            _ => Ok(String::from("Light")),
+        }
+    }
+
     fn terminal(&self) -> Result<String, ReadoutError> {
         use std::env::var;
 
@@ -335,6 +647,11 @@ impl GeneralReadout for MacOSGeneralReadout {
             return Ok(terminal);
         }
 
+        // Only a handful of terminals set TERM_PROGRAM, and it isn't forwarded over SSH.
+        if let Some(terminal) = terminal_process() {
+            return Ok(terminal.to_string());
+        }
+
         if let Ok(terminal_env) = var("TERM") {
             return Ok(terminal_env);
         }
@@ -354,10 +671,80 @@ impl GeneralReadout for MacOSGeneralReadout {
             .value_string()?)
     }
 
+    fn cpu_vendor(&self) -> Result<String, ReadoutError> {
+        match Ctl::new("machdep.cpu.vendor").and_then(|ctl| ctl.value_string()) {
+            Ok(vendor) => Ok(shared::cpu_vendor_name(&vendor)),
+            // Apple Silicon machines don't provide machdep.cpu.vendor.
+            Err(_) if cfg!(target_arch = "aarch64") => Ok(String::from("Apple")),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn cpu_flags(&self) -> Result<Vec<String>, ReadoutError> {
+        let mut flags = std::collections::BTreeSet::new();
+
+        // Intel machines, e.g. "FPU VME DE PSE TSC MSR PAE MCE CX8 APIC SEP MTRR PGE ..."
+        for name in [
+            "machdep.cpu.features",
+            "machdep.cpu.leaf7_features",
+            "machdep.cpu.extfeatures",
+        ] {
+            if let Ok(features) = Ctl::new(name).and_then(|ctl| ctl.value_string()) {
+                flags.extend(
+                    features
+                        .split_whitespace()
+                        .map(|flag| flag.to_lowercase().replace('.', "_")),
+                );
+            }
+        }
+
+        // Apple Silicon machines expose each feature as a boolean, which are
+        // named after their Linux counterparts.
+        if flags.is_empty() {
+            const FEATURES: [(&str, &str); 14] = [
+                ("hw.optional.floatingpoint", "fp"),
+                ("hw.optional.neon", "asimd"),
+                ("hw.optional.armv8_crc32", "crc32"),
+                ("hw.optional.armv8_1_atomics", "atomics"),
+                ("hw.optional.neon_fp16", "asimdhp"),
+                ("hw.optional.arm.FEAT_AES", "aes"),
+                ("hw.optional.arm.FEAT_PMULL", "pmull"),
+                ("hw.optional.arm.FEAT_SHA1", "sha1"),
+                ("hw.optional.arm.FEAT_SHA256", "sha2"),
+                ("hw.optional.arm.FEAT_SHA512", "sha512"),
+                ("hw.optional.arm.FEAT_SHA3", "sha3"),
+                ("hw.optional.arm.FEAT_DotProd", "asimddp"),
+                ("hw.optional.arm.FEAT_BF16", "bf16"),
+                ("hw.optional.arm.FEAT_I8MM", "i8mm"),
+            ];
+
+            for (name, flag) in FEATURES {
+                let enabled = Ctl::new(name)
+                    .and_then(|ctl| ctl.value())
+                    .map(|value| matches!(value, sysctl::CtlValue::Int(1)))
+                    .unwrap_or(false);
+
+                if enabled {
+                    flags.insert(flag.to_string());
+                }
+            }
+        }
+
+        if flags.is_empty() {
+            Err(MetricNotAvailable)
+        } else {
+            Ok(flags.into_iter().collect())
+        }
+    }
+
     fn cpu_usage(&self) -> Result<usize, ReadoutError> {
         shared::cpu_usage()
     }
 
+    fn load_average(&self) -> Result<(f64, f64, f64), ReadoutError> {
+        shared::load_average()
+    }
+
     fn cpu_physical_cores(&self) -> Result<usize, ReadoutError> {
         shared::cpu_physical_cores()
     }
@@ -366,6 +753,62 @@ impl GeneralReadout for MacOSGeneralReadout {
         shared::cpu_cores()
     }
 
+    fn cpu_core_layout(&self) -> Result<CpuCoreLayout, ReadoutError> {
+        // Each performance level, i.e. cluster kind, is described by hw.perflevel<n>.*,
+        // starting with the most performant one.
+        let sysctl_int = |name: &str| -> Result<usize, ReadoutError> {
+            match Ctl::new(name)?.value()? {
+                sysctl::CtlValue::Int(value) => Ok(value as usize),
+                sysctl::CtlValue::Uint(value) => Ok(value as usize),
+                _ => Err(MetricNotAvailable),
+            }
+        };
+
+        // Intel machines, and older releases, don't know about performance levels.
+        if sysctl_int("hw.nperflevels").unwrap_or(1) < 2 {
+            return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+                "The processor's cores are all of the same kind.",
+            )));
+        }
+
+        Ok(CpuCoreLayout {
+            performance: sysctl_int("hw.perflevel0.physicalcpu")?,
+            efficiency: sysctl_int("hw.perflevel1.physicalcpu")?,
+        })
+    }
+
+    fn cpu_frequency(&self) -> Result<usize, ReadoutError> {
+        // Apple Silicon machines don't provide hw.cpufrequency.
+        let hertz = match self
+            .cpu_frequency_ctl
+            .as_ref()
+            .ok_or(MetricNotAvailable)?
+            .value()?
+        {
+            sysctl::CtlValue::S64(s) => s as u64,
+            sysctl::CtlValue::U64(s) => s,
+            sysctl::CtlValue::Int(s) => s as u64,
+            sysctl::CtlValue::Uint(s) => s as u64,
+            _ => return Err(MetricNotAvailable),
+        };
+
+        Ok((hertz / 1_000_000) as usize)
+    }
+
+    fn cpu_temperature(&self) -> Result<f32, ReadoutError> {
+        // Intel machines expose the CPU proximity and die sensors, while Apple Silicon
+        // machines only expose per-cluster sensors, the first of which is picked.
+        const SENSOR_KEYS: [&str; 7] = ["TC0P", "TC0D", "TC0E", "TC0F", "Tp09", "Tp0T", "Tp01"];
+
+        let smc = MacOSSMC::open()?;
+
+        SENSOR_KEYS
+            .iter()
+            .filter_map(|key| smc.read_temperature(key))
+            .find(|&temp| temp > 0.0)
+            .ok_or(MetricNotAvailable)
+    }
+
     fn uptime(&self) -> Result<usize, ReadoutError> {
         use libc::timeval;
         use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -391,26 +834,337 @@ impl GeneralReadout for MacOSGeneralReadout {
 
     fn machine(&self) -> Result<String, ReadoutError> {
         let product_readout = MacOSProductReadout::new();
-        product_readout.product()
+        let model = product_readout.product()?;
+
+        Ok(mac_marketing_name(&model).unwrap_or(model))
     }
 
     fn os_name(&self) -> Result<String, ReadoutError> {
-        let version: String = self.operating_system_version()?.into();
-        let major_version_name = macos_version_to_name(&self.operating_system_version()?);
-
-        Ok(format!("macOS {version} {major_version_name}"))
+        let operating_system_version = self.operating_system_version()?;
+        let version: String = operating_system_version.into();
+
+        // The license shipped with the system names the release, which saves
+        // us from having to keep up with every new one.
+        let major_version_name = std::fs::read_to_string(MACOS_LICENSE)
+            .ok()
+            .and_then(|license| macos_name_from_license(&license))
+            .or_else(|| macos_version_to_name(&operating_system_version).map(String::from));
+
+        match major_version_name {
+            Some(name) => Ok(format!("macOS {version} {name}")),
+            None => Ok(format!("macOS {version}")),
+        }
     }
 
     fn disk_space(&self, path: &Path) -> Result<(u64, u64), ReadoutError> {
-        shared::disk_space(path)
+        // statfs ignores purgeable space, which Finder counts as available.
+        match volume_capacity(path) {
+            Some(capacity) => Ok(capacity),
+            None => shared::disk_space(path),
+        }
     }
 
     fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
         Err(ReadoutError::NotImplemented)
     }
+
+    fn disk_spaces(&self) -> Result<Vec<DiskSpace>, ReadoutError> {
+        shared::disk_spaces()
+    }
+
+    fn disks(&self) -> Result<Vec<Disk>, ReadoutError> {
+        let service_name = CString::new("IOMedia").expect("Unable to create c string");
+        let matching = unsafe { IOServiceMatching(service_name.as_ptr()) };
+        let mut iterator: io_iterator_t = 0;
+
+        let kern_return = unsafe {
+            IOServiceGetMatchingServices(kIOMasterPortDefault, matching as _, &mut iterator)
+        };
+
+        if kern_return != KERN_SUCCESS {
+            return Err(ReadoutError::Other(format!(
+                "Matching the IOMedia services failed with return code: {kern_return}"
+            )));
+        }
+
+        let mut disks = Vec::new();
+
+        loop {
+            let media = unsafe { IOIteratorNext(iterator) };
+            if media == 0 {
+                break;
+            }
+
+            if let Some(disk) = MacOSGeneralReadout::whole_media_disk(media) {
+                disks.push(disk);
+            }
+
+            unsafe {
+                IOObjectRelease(media);
+            }
+        }
+
+        unsafe {
+            IOObjectRelease(iterator);
+        }
+
+        if disks.is_empty() {
+            Err(MetricNotAvailable)
+        } else {
+            Ok(disks)
+        }
+    }
+
+    fn disk_io(&self, _all: bool) -> Result<Vec<DiskIo>, ReadoutError> {
+        // IOBlockStorageDriver instances only ever sit on top of whole disks.
+        let service_name = CString::new("IOBlockStorageDriver").expect("Unable to create c string");
+        let matching = unsafe { IOServiceMatching(service_name.as_ptr()) };
+        let mut iterator: io_iterator_t = 0;
+
+        let kern_return = unsafe {
+            IOServiceGetMatchingServices(kIOMasterPortDefault, matching as _, &mut iterator)
+        };
+
+        if kern_return != KERN_SUCCESS {
+            return Err(ReadoutError::Other(format!(
+                "Matching the IOBlockStorageDriver services failed with return code: {kern_return}"
+            )));
+        }
+
+        let mut disks = Vec::new();
+
+        loop {
+            let driver = unsafe { IOIteratorNext(iterator) };
+            if driver == 0 {
+                break;
+            }
+
+            if let Some(disk) = MacOSGeneralReadout::block_storage_driver_io(driver) {
+                disks.push(disk);
+            }
+
+            unsafe {
+                IOObjectRelease(driver);
+            }
+        }
+
+        unsafe {
+            IOObjectRelease(iterator);
+        }
+
+        Ok(disks)
+    }
+
+    fn locale(&self) -> Result<String, ReadoutError> {
+        shared::locale()
+    }
+
+    fn init_system(&self) -> Result<String, ReadoutError> {
+        Ok(String::from("launchd"))
+    }
+}
+
+/// Returns the used and total bytes of the volume holding the given path, where the space
+/// that the system may purge to make room for important files counts as available.
+fn volume_capacity(path: &Path) -> Option<(u64, u64)> {
+    use core_foundation::url::{kCFURLVolumeTotalCapacityKey, CFURL};
+
+    let url = CFURL::from_path(path, true)?;
+    let resource = |key: CFStringRef| -> Option<u64> {
+        let mut value: CFTypeRef = std::ptr::null();
+        let found = unsafe {
+            CFURLCopyResourcePropertyForKey(
+                url.as_concrete_TypeRef(),
+                key,
+                &mut value,
+                std::ptr::null_mut(),
+            )
+        };
+
+        if found == 0 || value.is_null() {
+            return None;
+        }
+
+        let value = unsafe { CFType::wrap_under_create_rule(value) };
+        value
+            .downcast::<CFNumber>()?
+            .to_i64()
+            .map(|bytes| bytes as u64)
+    };
+
+    let total = resource(unsafe { kCFURLVolumeTotalCapacityKey })?;
+    let available = resource(unsafe { kCFURLVolumeAvailableCapacityForImportantUsageKey })?;
+
+    Some((total.saturating_sub(available), total))
+}
+
+/// Copies the properties of an IORegistry entry into a dictionary.
+fn registry_entry_properties(entry: io_registry_entry_t) -> Option<CFDictionary> {
+    let mut dict: CFMutableDictionaryRef = std::ptr::null_mut();
+    let kern_return =
+        unsafe { IORegistryEntryCreateCFProperties(entry, &mut dict, std::ptr::null(), 0) };
+
+    if kern_return != KERN_SUCCESS || dict.is_null() {
+        return None;
+    }
+
+    Some(unsafe { CFDictionary::wrap_under_create_rule(dict as CFDictionaryRef) })
 }
 
 impl MacOSGeneralReadout {
+    /// Reads the brightness of the built-in display from the `IODisplayParameters` of its
+    /// backlight, which is driven by AppleARMBacklight on Apple Silicon machines.
+    fn registry_brightness() -> Option<usize> {
+        let number = |dict: &CFDictionary, key: &str| -> Option<i64> {
+            let value = dict.find(&CFString::new(key).to_void())?;
+            unsafe { CFNumber::wrap_under_get_rule((*value) as CFNumberRef) }.to_i64()
+        };
+
+        for class in ["AppleARMBacklight", "IODisplayConnect"] {
+            let class = CString::new(class).expect("Unable to create c string");
+            let entry = unsafe {
+                IOServiceGetMatchingService(kIOMasterPortDefault, IOServiceMatching(class.as_ptr()))
+            };
+
+            if entry == 0 {
+                continue;
+            }
+
+            let properties = registry_entry_properties(entry);
+            unsafe { IOObjectRelease(entry) };
+
+            let brightness = properties.and_then(|properties| {
+                let parameters =
+                    properties.find(&CFString::new("IODisplayParameters").to_void())?;
+                let parameters: CFDictionary =
+                    unsafe { CFDictionary::wrap_under_get_rule((*parameters) as CFDictionaryRef) };
+                let brightness = parameters.find(&CFString::new("brightness").to_void())?;
+                let brightness: CFDictionary =
+                    unsafe { CFDictionary::wrap_under_get_rule((*brightness) as CFDictionaryRef) };
+
+                let (min, max) = (number(&brightness, "min")?, number(&brightness, "max")?);
+                let value = number(&brightness, "value")?;
+
+                if max <= min {
+                    return None;
+                }
+
+                Some(((value - min) as f64 / (max - min) as f64 * 100.0).round() as usize)
+            });
+
+            if brightness.is_some() {
+                return brightness;
+            }
+        }
+
+        None
+    }
+
+    /// Reads the statistics of an IOBlockStorageDriver, and the BSD name of the
+    /// IOMedia it drives, _e.g._ `disk0`.
+    fn block_storage_driver_io(driver: io_registry_entry_t) -> Option<DiskIo> {
+        let properties = registry_entry_properties(driver)?;
+        let statistics = properties.find(&CFString::new("Statistics").to_void())?;
+        let statistics: CFDictionary =
+            unsafe { CFDictionary::wrap_under_get_rule((*statistics) as CFDictionaryRef) };
+
+        let bytes = |key: &str| {
+            let number = statistics.find(&CFString::new(key).to_void())?;
+            unsafe { CFNumber::wrap_under_get_rule((*number) as CFNumberRef) }.to_i64()
+        };
+
+        let bytes_read = bytes("Bytes (Read)")?;
+        let bytes_written = bytes("Bytes (Write)")?;
+
+        let plane = CString::new("IOService").expect("Unable to create c string");
+        let mut media: io_registry_entry_t = 0;
+        if unsafe { IORegistryEntryGetChildEntry(driver, plane.as_ptr(), &mut media) }
+            != KERN_SUCCESS
+        {
+            return None;
+        }
+
+        let name = registry_entry_properties(media).and_then(|properties| {
+            let name = properties.find(&CFString::new("BSD Name").to_void())?;
+            Some(unsafe { CFString::wrap_under_get_rule((*name) as CFStringRef) }.to_string())
+        });
+
+        unsafe {
+            IOObjectRelease(media);
+        }
+
+        Some(DiskIo {
+            name: name?,
+            bytes_read: bytes_read as u64,
+            bytes_written: bytes_written as u64,
+        })
+    }
+
+    /// Describes a whole IOMedia, using the characteristics of the
+    /// IOBlockStorageDevice it belongs to.
+    ///
+    /// Media synthesized by the system, _e.g._ APFS containers and disk images, lack
+    /// such a device and are skipped.
+    fn whole_media_disk(media: io_registry_entry_t) -> Option<Disk> {
+        let properties = registry_entry_properties(media)?;
+
+        let whole = properties.find(&CFString::new("Whole").to_void())?;
+        let whole = unsafe { CFBoolean::wrap_under_get_rule((*whole) as CFBooleanRef) };
+        if !bool::from(whole) {
+            return None;
+        }
+
+        let name = properties.find(&CFString::new("BSD Name").to_void())?;
+        let name = unsafe { CFString::wrap_under_get_rule((*name) as CFStringRef) }.to_string();
+
+        let size = properties.find(&CFString::new("Size").to_void())?;
+        let size = unsafe { CFNumber::wrap_under_get_rule((*size) as CFNumberRef) }.to_i64()?;
+
+        // IOMedia -> IOBlockStorageDriver -> IOBlockStorageDevice
+        let plane = CString::new("IOService").expect("Unable to create c string");
+        let mut driver: io_registry_entry_t = 0;
+        if unsafe { IORegistryEntryGetParentEntry(media, plane.as_ptr(), &mut driver) }
+            != KERN_SUCCESS
+        {
+            return None;
+        }
+
+        let mut device: io_registry_entry_t = 0;
+        let kern_return =
+            unsafe { IORegistryEntryGetParentEntry(driver, plane.as_ptr(), &mut device) };
+
+        unsafe {
+            IOObjectRelease(driver);
+        }
+
+        if kern_return != KERN_SUCCESS {
+            return None;
+        }
+
+        let device_properties = registry_entry_properties(device);
+
+        unsafe {
+            IOObjectRelease(device);
+        }
+
+        let characteristics = device_properties?
+            .find(&CFString::new("Device Characteristics").to_void())
+            .map(|dict| unsafe { CFDictionary::wrap_under_get_rule((*dict) as CFDictionaryRef) })?;
+
+        let string = |key: &str| {
+            let value = characteristics.find(&CFString::new(key).to_void())?;
+            let value = unsafe { CFString::wrap_under_get_rule((*value) as CFStringRef) };
+            Some(value.to_string().trim().to_string())
+        };
+
+        Some(Disk {
+            name,
+            model: string("Product Name").filter(|model| !model.is_empty()),
+            size: size as u64,
+            rotational: string("Medium Type").map(|medium| medium == "Rotational"),
+        })
+    }
+
     fn operating_system_version(&self) -> Result<NSOperatingSystemVersion, ReadoutError> {
         let os_string = self
             .os_product_version_ctl
@@ -483,9 +1237,11 @@ impl MemoryReadout for MacOSMemoryReadout {
 
     fn used(&self) -> Result<u64, ReadoutError> {
         let vm_stats = MacOSMemoryReadout::mach_vm_stats()?;
-        let used: u64 = ((vm_stats.active_count + vm_stats.wire_count) as u64
-            * self.page_size as u64
-            / 1024) as u64;
+        // Like Activity Monitor, memory held by the compressor counts as used.
+        let used: u64 =
+            ((vm_stats.active_count + vm_stats.wire_count + vm_stats.compressor_page_count) as u64
+                * self.page_size as u64
+                / 1024) as u64;
 
         Ok(used)
     }
@@ -504,6 +1260,13 @@ impl MemoryReadout for MacOSMemoryReadout {
 }
 
 impl MacOSMemoryReadout {
+    /// Returns the amount of memory occupied by the compressor in kilobytes, which
+    /// matches the "Pages occupied by compressor" reported by `vm_stat`.
+    pub fn compressed(&self) -> Result<u64, ReadoutError> {
+        let vm_stats = MacOSMemoryReadout::mach_vm_stats()?;
+        Ok(vm_stats.compressor_page_count as u64 * self.page_size as u64 / 1024)
+    }
+
     fn mach_vm_stats() -> Result<vm_statistics64, ReadoutError> {
         use mach2::kern_return::KERN_SUCCESS;
         use mach2::message::mach_msg_type_number_t;
@@ -589,11 +1352,23 @@ impl PackageReadout for MacOSPackageReadout {
     fn count_pkgs(&self) -> Vec<(PackageManager, usize)> {
         let mut packages = Vec::new();
         if extra::which("brew") {
-            if let Some(c) = MacOSPackageReadout::count_homebrew() {
-                packages.push((PackageManager::Homebrew, c))
+            if let Some((formulae, casks)) = MacOSPackageReadout::count_homebrew() {
+                packages.push((PackageManager::Homebrew, formulae));
+
+                if casks > 0 {
+                    packages.push((PackageManager::HomebrewCask, casks))
+                }
             }
         }
 
+        if let Some(c) = MacOSPackageReadout::count_macports() {
+            packages.push((PackageManager::MacPorts, c))
+        }
+
+        if let Some(c) = home::home_dir().and_then(|home| shared::count_nix_profile(&home)) {
+            packages.push((PackageManager::Nix, c))
+        }
+
         if let Some(c) = MacOSPackageReadout::count_cargo() {
             packages.push((PackageManager::Cargo, c))
         }
@@ -603,10 +1378,10 @@ impl PackageReadout for MacOSPackageReadout {
 }
 
 impl MacOSPackageReadout {
-    /// This method returns the total entries of `/usr/local/Cellar` and `/usr/local/Caskroom` directories
-    /// which contain all installed packages of the Homebrew package manager.
+    /// This method returns the total entries of the `Cellar` and `Caskroom` directories,
+    /// which respectively contain the formulae and the casks installed through Homebrew.
     /// A manual call via `homebrew list` would be too expensive, since it is pretty slow.
-    fn count_homebrew() -> Option<usize> {
+    fn count_homebrew() -> Option<(usize, usize)> {
         use std::fs::read_dir;
         use std::path::Path;
 
@@ -645,7 +1420,53 @@ impl MacOSPackageReadout {
             Err(_) => 0,
         };
 
-        Some(cellar_count + caskroom_count + opt_cellar_count + opt_caskroom_count)
+        Some((
+            cellar_count + opt_cellar_count,
+            caskroom_count + opt_caskroom_count,
+        ))
+    }
+
+    /// Returns the number of ports that are installed through MacPorts, which keeps track of
+    /// them in its registry, as `port installed` would be too expensive.
+    fn count_macports() -> Option<usize> {
+        let macports_root = Path::new("/opt/local/var/macports");
+        if !macports_root.is_dir() {
+            return None;
+        }
+
+        let count_sqlite = 'sqlite: {
+            let db = macports_root.join("registry").join("registry.db");
+            if !db.is_file() {
+                break 'sqlite None;
+            }
+
+            let connection =
+                sqlite::Connection::open_with_flags(db, sqlite::OpenFlags::new().with_read_only());
+            if let Ok(con) = connection {
+                let statement = con.prepare("SELECT COUNT(*) FROM ports WHERE state='installed'");
+                if let Ok(mut s) = statement {
+                    if s.next().is_ok() {
+                        break 'sqlite match s.read::<Option<i64>, _>(0) {
+                            Ok(Some(count)) => Some(count as usize),
+                            _ => None,
+                        };
+                    }
+                }
+            }
+
+            None
+        };
+
+        // Every installed port has its archive stored in a directory of its own.
+        count_sqlite.or_else(|| {
+            let software = macports_root.join("software").read_dir().ok()?;
+            Some(
+                software
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .count(),
+            )
+        })
     }
 
     fn count_cargo() -> Option<usize> {
@@ -655,36 +1476,451 @@ impl MacOSPackageReadout {
 
 impl NetworkReadout for MacOSNetworkReadout {
     fn new() -> Self {
-        MacOSNetworkReadout
+        MacOSNetworkReadout {
+            default_interface: shared::DefaultInterface::default(),
+        }
     }
 
-    fn tx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        Ok(MacOSNetworkReadout::interface_statistics(ifname)?.ifi_obytes as usize)
     }
 
-    fn tx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        Ok(MacOSNetworkReadout::interface_statistics(ifname)?.ifi_opackets as usize)
     }
 
-    fn rx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        Ok(MacOSNetworkReadout::interface_statistics(ifname)?.ifi_ibytes as usize)
     }
 
-    fn rx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let ifname = self.default_interface.resolve(interface)?;
+        Ok(MacOSNetworkReadout::interface_statistics(ifname)?.ifi_ipackets as usize)
     }
 
     fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
-        shared::logical_address(interface)
+        let address = self
+            .default_interface
+            .resolve(interface)
+            .and_then(|ifname| shared::logical_address(Some(ifname)));
+
+        match interface {
+            // There may be no IPv4 default route, e.g. behind some VPNs.
+            None => address.or_else(|_| shared::primary_address()),
+            Some(_) => address,
+        }
     }
 
-    fn physical_address(&self, _: Option<&str>) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn physical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        let link = match self.default_interface.resolve(interface) {
+            Ok(ifname) if interface.is_some() || !is_virtual_interface(ifname) => {
+                shared::link_info(ifname)?
+            }
+            // Fall back to the first physical interface that is up.
+            _ => shared::link_infos()?
+                .into_iter()
+                .find(|link| {
+                    let flags = libc::IFF_UP | libc::IFF_RUNNING;
+                    link.flags & flags as libc::c_uint == flags as libc::c_uint
+                        && !is_virtual_interface(&link.name)
+                        && !link.address.is_empty()
+                })
+                .ok_or(ReadoutError::MetricNotAvailable)?,
+        };
+
+        link.mac_address()
+    }
+
+    fn ssid(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        MacOSNetworkReadout::with_wifi_interface(interface, |wifi_interface| {
+            let ssid: CFStringRef = unsafe { msg_send_id(wifi_interface, "ssid") } as CFStringRef;
+
+            // The SSID is hidden from processes that aren't granted the location permission.
+            if ssid.is_null() {
+                return Err(ReadoutError::MetricNotAvailable);
+            }
+
+            Ok(unsafe { CFString::wrap_under_get_rule(ssid) }.to_string())
+        })
+    }
+
+    fn signal_strength(&self, interface: Option<&str>) -> Result<i32, ReadoutError> {
+        MacOSNetworkReadout::with_wifi_interface(interface, |wifi_interface| {
+            let rssi_value: extern "C" fn(id, SEL) -> isize =
+                unsafe { std::mem::transmute(objc_msgSend as *const ()) };
+            let selector = CString::new("rssiValue").expect("Unable to create c string");
+            let rssi = rssi_value(wifi_interface, unsafe {
+                sel_registerName(selector.as_ptr())
+            });
+
+            // An RSSI of zero is reported by disconnected interfaces.
+            match rssi {
+                0 => Err(ReadoutError::MetricNotAvailable),
+                rssi => Ok(rssi as i32),
+            }
+        })
+    }
+
+    fn default_gateway(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        shared::default_gateway(interface)
+    }
+
+    fn dns_servers(&self) -> Result<Vec<String>, ReadoutError> {
+        // /etc/resolv.conf is only kept around for compatibility, and doesn't account for
+        // scoped resolvers, e.g. those of VPNs.
+        let store_name = CFString::new("libmacchina");
+        let dns_key = CFString::new("State:/Network/Global/DNS");
+
+        let store = unsafe {
+            SCDynamicStoreCreate(
+                std::ptr::null(),
+                store_name.as_concrete_TypeRef(),
+                std::ptr::null(),
+                std::ptr::null_mut(),
+            )
+        };
+
+        if store.is_null() {
+            return shared::dns_servers();
+        }
+
+        let store = unsafe { CFType::wrap_under_create_rule(store) };
+        let value =
+            unsafe { SCDynamicStoreCopyValue(store.as_CFTypeRef(), dns_key.as_concrete_TypeRef()) };
+
+        if value.is_null() {
+            return shared::dns_servers();
+        }
+
+        let dns_dict: CFDictionary =
+            unsafe { CFDictionary::wrap_under_create_rule(value as CFDictionaryRef) };
+        let mut servers: Vec<String> = Vec::new();
+
+        if let Some(addresses) = dns_dict.find(&CFString::new("ServerAddresses").to_void()) {
+            let addresses: CFArray<CFString> =
+                unsafe { CFArray::wrap_under_get_rule((*addresses) as CFArrayRef) };
+
+            for address in addresses.iter() {
+                let address = address.to_string();
+                if !servers.contains(&address) {
+                    servers.push(address);
+                }
+            }
+        }
+
+        Ok(servers)
+    }
+
+    fn link_speed(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::link_speed(self.default_interface.resolve(interface)?)
+    }
+}
+
+impl MacOSNetworkReadout {
+    /// Looks up the CoreWLAN `CWInterface` by its name, or the default Wi-Fi interface if none
+    /// is given, and passes it on to the given closure.
+    fn with_wifi_interface<T>(
+        interface: Option<&str>,
+        f: impl FnOnce(id) -> Result<T, ReadoutError>,
+    ) -> Result<T, ReadoutError> {
+        let class_name = CString::new("CWWiFiClient").expect("Unable to create c string");
+
+        unsafe {
+            let pool = objc_autoreleasePoolPush();
+
+            let class = objc_getClass(class_name.as_ptr());
+            let client = msg_send_id(class, "sharedWiFiClient");
+
+            let wifi_interface = match interface {
+                Some(name) if !client.is_null() => {
+                    let name = CFString::new(name);
+                    let interface_with_name: extern "C" fn(id, SEL, id) -> id =
+                        std::mem::transmute(objc_msgSend as *const ());
+                    let selector =
+                        CString::new("interfaceWithName:").expect("Unable to create c string");
+
+                    interface_with_name(
+                        client,
+                        sel_registerName(selector.as_ptr()),
+                        name.as_concrete_TypeRef() as id,
+                    )
+                }
+                None if !client.is_null() => msg_send_id(client, "interface"),
+                _ => std::ptr::null_mut(),
+            };
+
+            let result = if wifi_interface.is_null() {
+                Err(ReadoutError::MetricNotAvailable)
+            } else {
+                f(wifi_interface)
+            };
+
+            objc_autoreleasePoolPop(pool);
+
+            result
+        }
+    }
+
+    /// Returns the 64-bit statistics of the given interface, from the `RTM_IFINFO2` messages
+    /// that the `NET_RT_IFLIST2` sysctl returns. Unlike the `if_data` structures provided by
+    /// `getifaddrs(3)`, these counters don't wrap around at 4 GiB.
+    fn interface_statistics(ifname: &str) -> Result<if_data64, ReadoutError> {
+        let buffer =
+            shared::sysctl_raw(&mut [libc::CTL_NET, libc::PF_ROUTE, 0, 0, NET_RT_IFLIST2, 0])?;
+
+        let mut available = Vec::new();
+
+        for (index, statistics) in parse_iflist2(&buffer) {
+            let mut name = [0 as libc::c_char; libc::IF_NAMESIZE];
+            if unsafe { libc::if_indextoname(index as libc::c_uint, name.as_mut_ptr()) }.is_null() {
+                continue;
+            }
+
+            let name = unsafe { CStr::from_ptr(name.as_ptr()) }.to_string_lossy();
+            if name == ifname {
+                return Ok(statistics);
+            }
+
+            available.push(name.into_owned());
+        }
+
+        Err(ReadoutError::Other(format!(
+            "Interface \"{ifname}\" does not exist, available interfaces are: {}",
+            available.join(", ")
+        )))
+    }
+}
+
+/// Provides access to the process tree through libproc.
+struct LibProc;
+
+impl shared::ProcessTree for LibProc {
+    fn parent(&self, pid: i32) -> Option<i32> {
+        let mut info: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<libc::proc_bsdinfo>() as i32;
+        let ret = unsafe {
+            libc::proc_pidinfo(
+                pid,
+                libc::PROC_PIDTBSDINFO,
+                0,
+                &mut info as *mut libc::proc_bsdinfo as *mut libc::c_void,
+                size,
+            )
+        };
+
+        if ret != size {
+            return None;
+        }
+
+        Some(info.pbi_ppid as i32).filter(|&ppid| ppid > 0)
+    }
+
+    fn name(&self, pid: i32) -> Option<String> {
+        let mut name = [0u8; 64];
+        let len = unsafe {
+            libc::proc_name(
+                pid,
+                name.as_mut_ptr() as *mut libc::c_void,
+                name.len() as u32,
+            )
+        };
+
+        if len <= 0 {
+            return None;
+        }
+
+        Some(String::from_utf8_lossy(&name[..len as usize]).into_owned())
+    }
+}
+
+// This function returns the terminal inside of which the user is operating
+fn terminal_process() -> Option<shared::Terminal> {
+    use shared::ProcessTree;
+
+    let mut terminal =
+        shared::find_terminal(&LibProc, LibProc.parent(unsafe { libc::getppid() })?)?;
+
+    // Application bundles name their executable after themselves, which can
+    // be less telling than the name of the bundle.
+    let pretty_name = match terminal.name.as_str() {
+        "Terminal" => "Apple Terminal",
+        "wezterm-gui" => "WezTerm",
+        "alacritty" => "Alacritty",
+        "Hyper" => "HyperTerm",
+        "WarpTerminal" => "Warp",
+        _ => return Some(terminal),
+    };
+
+    terminal.name = pretty_name.to_string();
+    Some(terminal)
+}
+
+/// Returns whether the given interface is a virtual one, _e.g._ the Apple Wireless Direct
+/// Link interface `awdl0` or a VPN tunnel such as `utun3`.
+fn is_virtual_interface(ifname: &str) -> bool {
+    const PREFIXES: [&str; 9] = [
+        "lo", "awdl", "llw", "utun", "ipsec", "bridge", "gif", "stf", "anpi",
+    ];
+
+    PREFIXES.iter().any(|prefix| ifname.starts_with(prefix))
+}
+
+/// Returns the index and the statistics of every interface described by the `RTM_IFINFO2`
+/// messages of a `NET_RT_IFLIST2` buffer.
+fn parse_iflist2(buffer: &[u8]) -> Vec<(u16, if_data64)> {
+    let header_len = std::mem::size_of::<if_msghdr2>();
+    let mut interfaces = Vec::new();
+    let mut offset = 0;
+
+    while offset + header_len <= buffer.len() {
+        let header =
+            unsafe { std::ptr::read_unaligned(buffer[offset..].as_ptr() as *const if_msghdr2) };
+
+        let msglen = header.ifm_msglen as usize;
+        if msglen == 0 {
+            break;
+        }
+
+        offset += msglen;
+
+        // Every interface is followed by messages describing its addresses.
+        if header.ifm_type == RTM_IFINFO2 {
+            interfaces.push((header.ifm_index, header.ifm_data));
+        }
     }
+
+    interfaces
 }
 
-fn macos_version_to_name(version: &NSOperatingSystemVersion) -> &'static str {
-    match (version.major_version, version.minor_version) {
+/// Sends a message that takes no arguments and returns an object to the given receiver.
+unsafe fn msg_send_id(receiver: id, selector: &str) -> id {
+    let send: extern "C" fn(id, SEL) -> id = std::mem::transmute(objc_msgSend as *const ());
+    let selector = CString::new(selector).expect("Unable to create c string");
+
+    send(receiver, sel_registerName(selector.as_ptr()))
+}
+
+const SI_MACHINE_ATTRIBUTES: [&str; 2] = [
+    "/System/Library/PrivateFrameworks/ServerInformation.framework/Versions/A/Resources/en.lproj/SIMachineAttributes.plist",
+    "/System/Library/PrivateFrameworks/ServerInformation.framework/Versions/A/Resources/English.lproj/SIMachineAttributes.plist",
+];
+
+/// Returns the marketing name of a Mac, _e.g._ `MacBook Pro (14-inch, 2021)`, given its model
+/// identifier, _e.g._ `MacBookPro18,3`.
+fn mac_marketing_name(model: &str) -> Option<String> {
+    // Apple Silicon machines carry their marketing name in the device tree.
+    let path = CString::new("IODeviceTree:/product").expect("Unable to create c string");
+    let entry = unsafe { IORegistryEntryFromPath(kIOMasterPortDefault, path.as_ptr()) };
+
+    if entry != 0 {
+        let properties = registry_entry_properties(entry);
+        unsafe { IOObjectRelease(entry) };
+
+        let product_name = properties.and_then(|properties| {
+            let name = properties.find(&CFString::new("product-name").to_void())?;
+            let name = unsafe { CFData::wrap_under_get_rule((*name) as CFDataRef) };
+            let name = String::from_utf8_lossy(name.bytes());
+            let name = name.trim_end_matches('\0').trim();
+
+            (!name.is_empty()).then(|| name.to_string())
+        });
+
+        if product_name.is_some() {
+            return product_name;
+        }
+    }
+
+    // Intel machines have to look it up in a property list shipped with the system, which is
+    // keyed by model identifier.
+    SI_MACHINE_ATTRIBUTES
+        .iter()
+        .find_map(|path| {
+            let data = std::fs::read(path).ok()?;
+            let (plist, _) = core_foundation::propertylist::create_with_data(
+                CFData::from_buffer(&data),
+                core_foundation::propertylist::kCFPropertyListImmutable,
+            )
+            .ok()?;
+            let plist = unsafe { CFPropertyList::wrap_under_create_rule(plist as _) };
+            let attributes: CFDictionary = plist.downcast_into()?;
+
+            let machine = attributes.find(&CFString::new(model).to_void())?;
+            let machine =
+                unsafe { CFDictionary::wrap_under_get_rule((*machine) as CFDictionaryRef) };
+            let localizable = machine.find(&CFString::new("_LOCALIZABLE_").to_void())?;
+            let localizable =
+                unsafe { CFDictionary::wrap_under_get_rule((*localizable) as CFDictionaryRef) };
+            let marketing_model = localizable.find(&CFString::new("marketingModel").to_void())?;
+
+            Some(
+                unsafe { CFString::wrap_under_get_rule((*marketing_model) as CFStringRef) }
+                    .to_string(),
+            )
+        })
+        .or_else(|| mac_model_to_name(model).map(String::from))
+}
+
+fn mac_model_to_name(model: &str) -> Option<&'static str> {
+    let name = match model {
+        "MacBookAir9,1" => "MacBook Air (Retina, 13-inch, 2020)",
+        "MacBookAir10,1" => "MacBook Air (M1, 2020)",
+        "Mac14,2" => "MacBook Air (M2, 2022)",
+        "Mac14,15" => "MacBook Air (15-inch, M2, 2023)",
+        "Mac15,12" => "MacBook Air (13-inch, M3, 2024)",
+        "Mac15,13" => "MacBook Air (15-inch, M3, 2024)",
+        "MacBookPro15,1" | "MacBookPro15,3" => "MacBook Pro (15-inch, 2018)",
+        "MacBookPro16,1" | "MacBookPro16,4" => "MacBook Pro (16-inch, 2019)",
+        "MacBookPro16,2" | "MacBookPro16,3" => "MacBook Pro (13-inch, 2020)",
+        "MacBookPro17,1" => "MacBook Pro (13-inch, M1, 2020)",
+        "MacBookPro18,1" | "MacBookPro18,2" => "MacBook Pro (16-inch, 2021)",
+        "MacBookPro18,3" | "MacBookPro18,4" => "MacBook Pro (14-inch, 2021)",
+        "Mac14,7" => "MacBook Pro (13-inch, M2, 2022)",
+        "Mac14,5" | "Mac14,9" => "MacBook Pro (14-inch, 2023)",
+        "Mac14,6" | "Mac14,10" => "MacBook Pro (16-inch, 2023)",
+        "Mac15,3" | "Mac15,6" | "Mac15,8" | "Mac15,10" => "MacBook Pro (14-inch, Nov 2023)",
+        "Mac15,7" | "Mac15,9" | "Mac15,11" => "MacBook Pro (16-inch, Nov 2023)",
+        "Macmini8,1" => "Mac mini (2018)",
+        "Macmini9,1" => "Mac mini (M1, 2020)",
+        "Mac14,3" | "Mac14,12" => "Mac mini (2023)",
+        "iMac20,1" | "iMac20,2" => "iMac (Retina 5K, 27-inch, 2020)",
+        "iMac21,1" | "iMac21,2" => "iMac (24-inch, M1, 2021)",
+        "Mac15,4" | "Mac15,5" => "iMac (24-inch, 2023)",
+        "Mac13,1" | "Mac13,2" => "Mac Studio (2022)",
+        "Mac14,13" | "Mac14,14" => "Mac Studio (2023)",
+        "MacPro7,1" => "Mac Pro (2019)",
+        "Mac14,8" => "Mac Pro (2023)",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+const MACOS_LICENSE: &str =
+    "/System/Library/CoreServices/Setup Assistant.app/Contents/Resources/en.lproj/OSXSoftwareLicense.rtf";
+
+/// Extracts the name of the release from its license agreement, which reads
+/// _e.g._ `SOFTWARE LICENSE AGREEMENT FOR macOS Sonoma`.
+fn macos_name_from_license(license: &str) -> Option<String> {
+    const TITLE: &str = "SOFTWARE LICENSE AGREEMENT FOR macOS ";
+
+    let start = license.find(TITLE)? + TITLE.len();
+    let name = license[start..]
+        .split(|c| c == '\\' || c == '\n')
+        .next()?
+        .trim();
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+fn macos_version_to_name(version: &NSOperatingSystemVersion) -> Option<&'static str> {
+    let name = match (version.major_version, version.minor_version) {
         (10, 1) => "Puma",
         (10, 2) => "Jaguar",
         (10, 3) => "Panther",
@@ -705,6 +1941,108 @@ fn macos_version_to_name(version: &NSOperatingSystemVersion) -> &'static str {
         (13, _) => "Ventura",
         (14, _) => "Sonoma",
         (15, _) => "Sequoia",
-        _ => "Unknown",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iflist2() {
+        let interface = |index: u16, ibytes: u64| {
+            let header = if_msghdr2 {
+                ifm_msglen: std::mem::size_of::<if_msghdr2>() as u16,
+                ifm_type: RTM_IFINFO2,
+                ifm_index: index,
+                ifm_data: if_data64 {
+                    ifi_ibytes: ibytes,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            unsafe {
+                std::slice::from_raw_parts(
+                    &header as *const if_msghdr2 as *const u8,
+                    std::mem::size_of::<if_msghdr2>(),
+                )
+            }
+            .to_vec()
+        };
+
+        // An RTM_NEWADDR message, whose header is shorter than that of an interface.
+        let mut address = vec![0u8; 20];
+        address[..2].copy_from_slice(&20u16.to_ne_bytes());
+        address[3] = 0xc;
+
+        let buffer = [
+            interface(1, 5_000_000_000),
+            address,
+            interface(4, 1024),
+            vec![0u8; 8],
+        ]
+        .concat();
+
+        let interfaces = parse_iflist2(&buffer);
+        assert_eq!(interfaces.len(), 2);
+        assert_eq!(interfaces[0].0, 1);
+        assert_eq!({ interfaces[0].1.ifi_ibytes }, 5_000_000_000);
+        assert_eq!(interfaces[1].0, 4);
+        assert_eq!({ interfaces[1].1.ifi_ibytes }, 1024);
+
+        assert!(parse_iflist2(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_battery_health() {
+        // Apple Silicon
+        assert_eq!(battery_health(Some(6075), Some(5225), Some(100)), Some(87));
+        assert_eq!(battery_health(Some(6075), None, Some(88)), Some(88));
+        // Intel
+        assert_eq!(battery_health(Some(6669), None, Some(5336)), Some(81));
+        assert_eq!(
+            battery_health(Some(5000), Some(5100), Some(5100)),
+            Some(100)
+        );
+        assert_eq!(battery_health(None, Some(5100), Some(100)), None);
+        assert_eq!(battery_health(Some(5000), None, None), None);
+    }
+
+    #[test]
+    fn test_mac_model_to_name() {
+        assert_eq!(
+            mac_model_to_name("MacBookPro18,3"),
+            Some("MacBook Pro (14-inch, 2021)")
+        );
+        assert_eq!(mac_model_to_name("Macmini9,1"), Some("Mac mini (M1, 2020)"));
+        assert_eq!(mac_model_to_name("VirtualMac2,1"), None);
+    }
+
+    #[test]
+    fn test_macos_name() {
+        let license = "{\\rtf1\\ansi\\ansicpg1252\\cocoartf2761\n\
+                       \\f0\\b\\fs28 \\cf0 ENGLISH\\\n\
+                       \\\n\
+                       APPLE INC.\\\n\
+                       SOFTWARE LICENSE AGREEMENT FOR macOS Big Sur\\\n\
+                       For use on Apple-branded Systems\\\n";
+        assert_eq!(
+            macos_name_from_license(license),
+            Some(String::from("Big Sur"))
+        );
+        assert_eq!(macos_name_from_license("{\\rtf1\\ansi}"), None);
+
+        let version = |major_version, minor_version| NSOperatingSystemVersion {
+            major_version,
+            minor_version,
+            patch_version: 0,
+        };
+        assert_eq!(macos_version_to_name(&version(10, 15)), Some("Catalina"));
+        assert_eq!(macos_version_to_name(&version(15, 1)), Some("Sequoia"));
+        assert_eq!(macos_version_to_name(&version(16, 0)), None);
     }
 }
@@ -12,23 +12,126 @@ use std::fs::read_dir;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
-pub struct NetBSDBatteryReadout;
+mod proplib;
+
+pub struct NetBSDBatteryReadout {
+    device: Option<String>,
+}
 pub struct NetBSDKernelReadout;
-pub struct NetBSDGeneralReadout;
+pub struct NetBSDGeneralReadout {
+    boot_time: std::sync::OnceLock<Option<std::time::SystemTime>>,
+}
 pub struct NetBSDMemoryReadout;
 pub struct NetBSDProductReadout;
 pub struct NetBSDPackageReadout;
-pub struct NetBSDNetworkReadout;
+pub struct NetBSDNetworkReadout {
+    default_interface: shared::DefaultInterface,
+}
 
 impl BatteryReadout for NetBSDBatteryReadout {
     fn new() -> Self {
-        NetBSDBatteryReadout
+        NetBSDBatteryReadout { device: None }
     }
 
     fn percentage(&self) -> Result<u8, ReadoutError> {
+        let Some(envsys) = envsys_dictionary() else {
+            return NetBSDBatteryReadout::envstat_percentage(&self.fallback_device());
+        };
+
+        let charges: Vec<&proplib::Value> = self
+            .devices(&envsys)
+            .iter()
+            .filter_map(|device| battery_sensor(&envsys, device, "charge"))
+            .collect();
+
+        if charges.is_empty() {
+            return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+                "No batteries detected.",
+            )));
+        }
+
+        // Like on Linux, the charge of each pack is weighted by its capacity.
+        let (current, maximum) = charges
+            .iter()
+            .try_fold((0, 0), |(total_current, total_maximum), charge| {
+                let current = sensor_value(charge, "cur-value")?;
+                let maximum = sensor_value(charge, "max-value")?;
+
+                Some((total_current + current, total_maximum + maximum))
+            })
+            .ok_or(ReadoutError::MetricNotAvailable)?;
+
+        if maximum <= 0 {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        Ok((current as f64 / maximum as f64 * 100.0).round().min(100.0) as u8)
+    }
+
+    fn status(&self) -> Result<BatteryState, ReadoutError> {
+        let Some(envsys) = envsys_dictionary() else {
+            return NetBSDBatteryReadout::envstat_status(&self.fallback_device());
+        };
+
+        let mut state = None;
+
+        for device in self.devices(&envsys) {
+            let Some(charging) = battery_sensor(&envsys, &device, "charging") else {
+                continue;
+            };
+
+            match sensor_value(charging, "cur-value") {
+                Some(0) => state = Some(BatteryState::Discharging),
+                Some(_) => return Ok(BatteryState::Charging),
+                None => (),
+            }
+        }
+
+        state.ok_or_else(|| {
+            ReadoutError::MetricUnavailableForHardware(String::from("No batteries detected."))
+        })
+    }
+
+    fn health(&self) -> Result<u8, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+}
+
+impl NetBSDBatteryReadout {
+    /// Creates a readout which reads from the given battery sensor device, _e.g._
+    /// `acpibat1`, instead of the first one that is found.
+    pub fn with_device(device: &str) -> Self {
+        NetBSDBatteryReadout {
+            device: Some(device.to_string()),
+        }
+    }
+
+    /// Returns the configured sensor device, or every `acpibat*` device registered
+    /// with envsys.
+    fn devices(&self, envsys: &proplib::Value) -> Vec<String> {
+        if let Some(device) = &self.device {
+            return vec![device.to_owned()];
+        }
+
+        envsys
+            .keys()
+            .filter(|device| device.starts_with("acpibat"))
+            .map(String::from)
+            .sorted()
+            .collect()
+    }
+
+    /// Returns the sensor device envstat is asked about when envsys can't be queried.
+    fn fallback_device(&self) -> String {
+        self.device
+            .clone()
+            .unwrap_or_else(|| String::from("acpibat0"))
+    }
+
+    fn envstat_percentage(device: &str) -> Result<u8, ReadoutError> {
         if extra::which("envstat") {
             let envstat = Command::new("envstat")
-                .args(["-s", "acpibat0:charge"])
+                .args(["-s", &format!("{device}:charge")])
                 .stdout(Stdio::piped())
                 .output()
                 .expect("ERROR: failed to spawn \"envstat\" process");
@@ -36,7 +139,9 @@ impl BatteryReadout for NetBSDBatteryReadout {
             let envstat_out = String::from_utf8(envstat.stdout)
                 .expect("ERROR: \"envstat\" process stdout was not valid UTF-8");
             if envstat_out.is_empty() {
-                return Err(ReadoutError::MetricNotAvailable);
+                return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+                    "No batteries detected.",
+                )));
             } else {
                 let re = Regex::new(r"\(([^()]*)\)").unwrap();
                 let caps = re.captures(&envstat_out);
@@ -59,10 +164,10 @@ impl BatteryReadout for NetBSDBatteryReadout {
         Err(ReadoutError::MetricNotAvailable)
     }
 
-    fn status(&self) -> Result<BatteryState, ReadoutError> {
+    fn envstat_status(device: &str) -> Result<BatteryState, ReadoutError> {
         if extra::which("envstat") {
             let envstat = Command::new("envstat")
-                .args(["-s", "acpibat0:charging"])
+                .args(["-s", &format!("{device}:charging")])
                 .stdout(Stdio::piped())
                 .output()
                 .expect("ERROR: failed to spawn \"envstat\" process");
@@ -71,7 +176,9 @@ impl BatteryReadout for NetBSDBatteryReadout {
                 .expect("ERROR: \"envstat\" process stdout was not valid UTF-8");
 
             if envstat_out.is_empty() {
-                return Err(ReadoutError::MetricNotAvailable);
+                return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+                    "No batteries detected.",
+                )));
             } else if envstat_out.contains("TRUE") {
                 return Ok(BatteryState::Charging);
             } else {
@@ -81,10 +188,103 @@ impl BatteryReadout for NetBSDBatteryReadout {
 
         Err(ReadoutError::Other("envstat is not installed".to_owned()))
     }
+}
 
-    fn health(&self) -> Result<u8, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+/// Calls `sysctlbyname(3)`, returning whether it succeeded.
+fn sysctl_by_name(name: &str, value: *mut libc::c_void, len: &mut libc::size_t) -> bool {
+    let Ok(name) = CString::new(name) else {
+        return false;
+    };
+
+    unsafe { libc::sysctlbyname(name.as_ptr(), value, len, std::ptr::null(), 0) == 0 }
+}
+
+/// Reads a string-valued MIB, _e.g._ `kern.ostype`.
+fn sysctl_string(name: &str) -> Result<String, ReadoutError> {
+    let error = || ReadoutError::Other(format!("Could not read \"{name}\" through sysctl."));
+
+    let mut len: libc::size_t = 0;
+    if !sysctl_by_name(name, std::ptr::null_mut(), &mut len) {
+        return Err(error());
+    }
+
+    let mut buffer = vec![0u8; len];
+    if !sysctl_by_name(name, buffer.as_mut_ptr() as *mut libc::c_void, &mut len) {
+        return Err(error());
+    }
+
+    buffer.truncate(len);
+
+    // The value is NUL-terminated.
+    let value = match buffer.iter().position(|&b| b == 0) {
+        Some(end) => &buffer[..end],
+        None => &buffer[..],
+    };
+
+    Ok(String::from_utf8_lossy(value).into_owned())
+}
+
+/// Mirrors `struct plistref`, declared in `prop/plistref.h`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+struct plistref {
+    pref_plist: *mut libc::c_void,
+    pref_len: libc::size_t,
+}
+
+/// `_IOWR('E', 0, struct plistref)`, following the encoding of `sys/ioccom.h`. The size of
+/// the structure is part of it, and differs between 32-bit and 64-bit ports.
+const ENVSYS_GETDICTIONARY: libc::c_ulong = 0xC000_0000
+    | ((std::mem::size_of::<plistref>() as libc::c_ulong & 0x1fff) << 16)
+    | ((b'E' as libc::c_ulong) << 8);
+
+/// Returns the dictionary of every sensor registered with envsys, keyed by device.
+fn envsys_dictionary() -> Option<proplib::Value> {
+    use std::os::unix::io::AsRawFd;
+
+    let sysmon = fs::File::open("/dev/sysmon").ok()?;
+    let mut pref = plistref {
+        pref_plist: std::ptr::null_mut(),
+        pref_len: 0,
+    };
+
+    if unsafe { libc::ioctl(sysmon.as_raw_fd(), ENVSYS_GETDICTIONARY as _, &mut pref) } != 0
+        || pref.pref_plist.is_null()
+    {
+        return None;
+    }
+
+    // The kernel maps the externalized dictionary into our address space.
+    let xml = unsafe { std::slice::from_raw_parts(pref.pref_plist as *const u8, pref.pref_len) };
+    let dictionary = std::str::from_utf8(xml)
+        .ok()
+        .and_then(|xml| proplib::parse(xml.trim_end_matches('\0')));
+
+    unsafe {
+        libc::munmap(pref.pref_plist, pref.pref_len);
+    }
+
+    dictionary
+}
+
+/// Returns the sensor of the given device with the given description, _e.g._ `charge`.
+fn battery_sensor<'a>(
+    envsys: &'a proplib::Value,
+    device: &str,
+    description: &str,
+) -> Option<&'a proplib::Value> {
+    envsys.get(device)?.as_array()?.iter().find(|sensor| {
+        sensor.get("description").and_then(proplib::Value::as_str) == Some(description)
+    })
+}
+
+/// Returns the given value of a sensor, unless its state is invalid.
+fn sensor_value(sensor: &proplib::Value, key: &str) -> Option<i64> {
+    if sensor.get("state").and_then(proplib::Value::as_str) == Some("invalid") {
+        return None;
     }
+
+    sensor.get(key)?.as_integer()
 }
 
 impl KernelReadout for NetBSDKernelReadout {
@@ -93,27 +293,11 @@ impl KernelReadout for NetBSDKernelReadout {
     }
 
     fn os_release(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "kern.osrelease"])
-            .output()
-            .expect("ERROR: failed to fetch \"kernel.osrelease\" using \"sysctl\"");
-
-        let osrelease = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
-
-        Ok(osrelease)
+        sysctl_string("kern.osrelease")
     }
 
     fn os_type(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "kern.ostype"])
-            .output()
-            .expect("ERROR: failed to fetch \"kernel.ostype\" using \"sysctl\"");
-
-        let osrelease = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
-
-        Ok(osrelease)
+        sysctl_string("kern.ostype")
     }
 
     fn pretty_kernel(&self) -> Result<String, ReadoutError> {
@@ -121,39 +305,46 @@ impl KernelReadout for NetBSDKernelReadout {
             "This information is provided by the OperatingSystem readout on NetBSD.",
         )))
     }
+
+    fn architecture(&self) -> Result<String, ReadoutError> {
+        shared::architecture()
+    }
 }
 
 impl GeneralReadout for NetBSDGeneralReadout {
     fn new() -> Self {
-        NetBSDGeneralReadout
+        NetBSDGeneralReadout {
+            boot_time: std::sync::OnceLock::new(),
+        }
     }
 
     fn resolution(&self) -> Result<String, ReadoutError> {
         shared::resolution()
     }
 
-    fn backlight(&self) -> Result<usize, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "hw.acpi.acpiout0.brightness"])
-            .output()
-            .expect("ERROR: failed to fetch \"hw.acpi.acpiout0.brightness\" using \"sysctl\"");
-
-        let backlight = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
+    fn displays(&self) -> Result<Vec<Display>, ReadoutError> {
+        shared::x11_displays()
+    }
 
-        if backlight.is_empty() {
+    fn backlight(&self) -> Result<usize, ReadoutError> {
+        let mut brightness: libc::c_int = 0;
+        let mut len = std::mem::size_of::<libc::c_int>();
+
+        if !sysctl_by_name(
+            "hw.acpi.acpiout0.brightness",
+            &mut brightness as *mut libc::c_int as *mut libc::c_void,
+            &mut len,
+        ) {
             return Err(ReadoutError::Other(String::from(
                 "Could not obtain backlight value through sysctl, is ACPIVGA driver installed?",
             )));
         }
 
-        if let Ok(val) = extra::pop_newline(backlight).parse::<usize>() {
-            return Ok(val);
-        }
-
-        Err(ReadoutError::Other(String::from(
-            "Could not parse the obtained backlight value.",
-        )))
+        usize::try_from(brightness).map_err(|_| {
+            ReadoutError::Other(String::from(
+                "Could not parse the obtained backlight value.",
+            ))
+        })
     }
 
     fn machine(&self) -> Result<String, ReadoutError> {
@@ -163,14 +354,7 @@ impl GeneralReadout for NetBSDGeneralReadout {
         let vendor = product_readout.vendor()?;
         let product = product_readout.product()?;
 
-        let new_product =
-            format!("{vendor} {family} {product}").replace("To be filled by O.E.M.", "");
-
-        if product == new_product && product == vendor {
-            return Ok(vendor);
-        }
-
-        Ok(new_product.split_whitespace().unique().join(" "))
+        shared::machine_name(&vendor, &family, &product, "")
     }
 
     fn username(&self) -> Result<String, ReadoutError> {
@@ -200,6 +384,10 @@ impl GeneralReadout for NetBSDGeneralReadout {
         shared::desktop_environment()
     }
 
+    fn desktop_environment_version(&self) -> Result<String, ReadoutError> {
+        shared::desktop_environment_version()
+    }
+
     fn session(&self) -> Result<String, ReadoutError> {
         shared::session()
     }
@@ -209,62 +397,9 @@ impl GeneralReadout for NetBSDGeneralReadout {
     }
 
     fn terminal(&self) -> Result<String, ReadoutError> {
-        // This function returns the PPID of a given PID:
-        //  - The file used to extract this data: /proc/<pid>/status
-        //  - The format of the file is: command_name command_pid command_ppid ...
-        fn get_parent(pid: i32) -> i32 {
-            let process_path = PathBuf::from("/proc").join(pid.to_string()).join("status");
-            if let Ok(content) = fs::read_to_string(process_path) {
-                if let Some(val) = content.split_whitespace().nth(2) {
-                    if let Ok(c) = val.parse::<i32>() {
-                        return c;
-                    }
-                }
-
-                return -1;
-            }
-
-            -1
-        }
-
-        // This function returns the name associated with a given PPID
-        fn terminal_name() -> String {
-            let mut terminal_pid = get_parent(unsafe { libc::getppid() });
-
-            let path = PathBuf::from("/proc")
-                .join(terminal_pid.to_string())
-                .join("status");
-
-            // The below loop will traverse /proc to find the
-            // terminal inside of which the user is operating
-            if let Ok(mut terminal_name) = fs::read_to_string(path) {
-                terminal_name = terminal_name.split_whitespace().next().unwrap().to_owned();
-
-                // Any command_name we find that matches
-                // one of the elements within this table
-                // is effectively ignored
-                while extra::common_shells().contains(&terminal_name.as_str()) {
-                    let ppid = get_parent(terminal_pid);
-                    terminal_pid = ppid;
-
-                    let path = PathBuf::from("/proc")
-                        .join(terminal_pid.to_string())
-                        .join("status");
-
-                    if let Ok(status) = fs::read_to_string(path) {
-                        if let Some(name) = status.split_whitespace().next() {
-                            terminal_name = name.to_string();
-                        }
-                    }
-                }
-
-                return terminal_name;
-            }
-
-            String::new()
-        }
-
-        let terminal = terminal_name();
+        let terminal = terminal_process()
+            .map(|terminal| terminal.to_string())
+            .unwrap_or_default();
 
         if terminal.is_empty() {
             return Err(ReadoutError::Other(
@@ -275,6 +410,18 @@ impl GeneralReadout for NetBSDGeneralReadout {
         Ok(terminal)
     }
 
+    fn terminal_version(&self) -> Result<String, ReadoutError> {
+        let terminal = terminal_process().ok_or(ReadoutError::MetricNotAvailable)?;
+        let exe = fs::read_link(
+            PathBuf::from("/proc")
+                .join(terminal.pid.to_string())
+                .join("exe"),
+        )
+        .ok();
+
+        shared::terminal_version(&terminal.name, exe.as_deref())
+    }
+
     fn shell(&self, shorthand: ShellFormat, kind: ShellKind) -> Result<String, ReadoutError> {
         shared::shell(shorthand, kind)
     }
@@ -295,8 +442,20 @@ impl GeneralReadout for NetBSDGeneralReadout {
         shared::cpu_usage()
     }
 
+    fn load_average(&self) -> Result<(f64, f64, f64), ReadoutError> {
+        shared::load_average()
+    }
+
     fn uptime(&self) -> Result<usize, ReadoutError> {
-        shared::uptime()
+        // /proc/uptime is only available when procfs is mounted.
+        let Some(boot_time) = *self.boot_time.get_or_init(NetBSDGeneralReadout::boot_time) else {
+            return shared::uptime();
+        };
+
+        match std::time::SystemTime::now().duration_since(boot_time) {
+            Ok(duration) => Ok(duration.as_secs() as usize),
+            Err(_) => shared::uptime(),
+        }
     }
 
     fn os_name(&self) -> Result<String, ReadoutError> {
@@ -346,6 +505,18 @@ impl GeneralReadout for NetBSDGeneralReadout {
     fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
         Err(ReadoutError::NotImplemented)
     }
+
+    fn disk_spaces(&self) -> Result<Vec<DiskSpace>, ReadoutError> {
+        shared::disk_spaces()
+    }
+
+    fn locale(&self) -> Result<String, ReadoutError> {
+        shared::locale()
+    }
+
+    fn init_system(&self) -> Result<String, ReadoutError> {
+        Ok(String::from("init"))
+    }
 }
 
 impl MemoryReadout for NetBSDMemoryReadout {
@@ -399,39 +570,15 @@ impl ProductReadout for NetBSDProductReadout {
     }
 
     fn product(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "machdep.dmi.system-version"])
-            .output()
-            .expect("ERROR: failed to start \"sysctl\" process");
-
-        let sysver = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
-
-        Ok(sysver)
+        sysctl_string("machdep.dmi.system-version")
     }
 
     fn vendor(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "machdep.dmi.system-vendor"])
-            .output()
-            .expect("ERROR: failed to start \"sysctl\" process");
-
-        let sysven = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
-
-        Ok(sysven)
+        sysctl_string("machdep.dmi.system-vendor")
     }
 
     fn family(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "machdep.dmi.system-product"])
-            .output()
-            .expect("ERROR: failed to start \"sysctl\" process");
-
-        let sysprod = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
-
-        Ok(sysprod)
+        sysctl_string("machdep.dmi.system-product")
     }
 }
 
@@ -455,6 +602,34 @@ impl PackageReadout for NetBSDPackageReadout {
     }
 }
 
+impl NetBSDGeneralReadout {
+    /// Returns the time the system booted at, from `kern.boottime`.
+    fn boot_time() -> Option<std::time::SystemTime> {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        // Unlike on the other BSDs, kern.boottime is a timespec rather than a timeval.
+        let mut boot_time = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let mut len = std::mem::size_of::<libc::timespec>();
+
+        if !sysctl_by_name(
+            "kern.boottime",
+            &mut boot_time as *mut libc::timespec as *mut libc::c_void,
+            &mut len,
+        ) {
+            return None;
+        }
+
+        Some(
+            UNIX_EPOCH
+                + Duration::from_secs(boot_time.tv_sec as u64)
+                + Duration::from_nanos(boot_time.tv_nsec as u64),
+        )
+    }
+}
+
 impl NetBSDPackageReadout {
     fn count_pkgin() -> Option<usize> {
         if let Some(pkg_dbdir) = dirs::pkgdb_dir() {
@@ -479,7 +654,9 @@ impl NetBSDPackageReadout {
 
 impl NetworkReadout for NetBSDNetworkReadout {
     fn new() -> Self {
-        NetBSDNetworkReadout
+        NetBSDNetworkReadout {
+            default_interface: shared::DefaultInterface::default(),
+        }
     }
 
     fn tx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
@@ -499,10 +676,65 @@ impl NetworkReadout for NetBSDNetworkReadout {
     }
 
     fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
-        shared::logical_address(interface)
+        let address = self
+            .default_interface
+            .resolve(interface)
+            .and_then(|ifname| shared::logical_address(Some(ifname)));
+
+        match interface {
+            // There may be no IPv4 default route, e.g. behind some VPNs.
+            None => address.or_else(|_| shared::primary_address()),
+            Some(_) => address,
+        }
     }
 
     fn physical_address(&self, _: Option<&str>) -> Result<String, ReadoutError> {
         Err(ReadoutError::NotImplemented)
     }
+
+    fn default_gateway(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        shared::default_gateway(interface)
+    }
+
+    fn dns_servers(&self) -> Result<Vec<String>, ReadoutError> {
+        shared::dns_servers()
+    }
+}
+
+// This function returns the PPID of a given PID:
+//  - The file used to extract this data: /proc/<pid>/status
+//  - The format of the file is: command_name command_pid command_ppid ...
+fn get_parent(pid: i32) -> i32 {
+    let process_path = PathBuf::from("/proc").join(pid.to_string()).join("status");
+    if let Ok(content) = fs::read_to_string(process_path) {
+        if let Some(val) = content.split_whitespace().nth(2) {
+            if let Ok(c) = val.parse::<i32>() {
+                return c;
+            }
+        }
+
+        return -1;
+    }
+
+    -1
+}
+
+/// Provides access to the process tree through /proc/<pid>/status.
+struct ProcFs;
+
+impl shared::ProcessTree for ProcFs {
+    fn parent(&self, pid: i32) -> Option<i32> {
+        Some(get_parent(pid)).filter(|&ppid| ppid > 0)
+    }
+
+    fn name(&self, pid: i32) -> Option<String> {
+        let status =
+            fs::read_to_string(PathBuf::from("/proc").join(pid.to_string()).join("status"));
+        Some(status.ok()?.split_whitespace().next()?.to_string())
+    }
+}
+
+// This function returns the terminal inside of which the user is operating
+fn terminal_process() -> Option<shared::Terminal> {
+    shared::find_terminal(&ProcFs, get_parent(unsafe { libc::getppid() }))
 }
@@ -0,0 +1,241 @@
+//! A minimal parser for the XML externalization of proplib objects, such as the
+//! dictionary returned by the `ENVSYS_GETDICTIONARY` ioctl of `/dev/sysmon`.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Dict(Vec<(String, Value)>),
+    Array(Vec<Value>),
+    String(String),
+    Integer(i64),
+    Bool(bool),
+}
+
+impl Value {
+    /// Returns the value associated with the given key, if this is a dictionary.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Dict(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns the keys of this dictionary, in order of appearance.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        let entries = match self {
+            Value::Dict(entries) => entries.as_slice(),
+            _ => &[],
+        };
+
+        entries.iter().map(|(key, _)| key.as_str())
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the object held by the `<plist>` element of the given document.
+pub fn parse(xml: &str) -> Option<Value> {
+    let mut parser = Parser { rest: xml };
+
+    loop {
+        if let Tag::Open(name) = parser.next_tag()? {
+            if name == "plist" {
+                break;
+            }
+        }
+    }
+
+    let tag = parser.next_tag()?;
+    parser.value(tag)
+}
+
+enum Tag<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    Empty(&'a str),
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    /// Returns the next element tag, skipping text, declarations and comments.
+    fn next_tag(&mut self) -> Option<Tag<'a>> {
+        loop {
+            let start = self.rest.find('<')?;
+            let end = start + self.rest[start..].find('>')?;
+            let tag = &self.rest[start + 1..end];
+            self.rest = &self.rest[end + 1..];
+
+            if tag.starts_with('?') || tag.starts_with('!') {
+                continue;
+            }
+
+            if let Some(name) = tag.strip_prefix('/') {
+                return Some(Tag::Close(name.trim()));
+            }
+
+            let (tag, empty) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+
+            let name = tag.split_whitespace().next()?;
+
+            return Some(if empty {
+                Tag::Empty(name)
+            } else {
+                Tag::Open(name)
+            });
+        }
+    }
+
+    /// Returns the text preceding the next tag.
+    fn text(&mut self) -> String {
+        let end = self.rest.find('<').unwrap_or(self.rest.len());
+        let text = &self.rest[..end];
+        self.rest = &self.rest[end..];
+
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    }
+
+    /// Reads the text of a leaf element, up to and including its closing tag.
+    fn leaf(&mut self, name: &str) -> Option<String> {
+        let text = self.text();
+        match self.next_tag()? {
+            Tag::Close(close) if close == name => Some(text),
+            _ => None,
+        }
+    }
+
+    fn value(&mut self, tag: Tag<'a>) -> Option<Value> {
+        match tag {
+            Tag::Empty("true") => Some(Value::Bool(true)),
+            Tag::Empty("false") => Some(Value::Bool(false)),
+            Tag::Empty("dict") => Some(Value::Dict(Vec::new())),
+            Tag::Empty("array") => Some(Value::Array(Vec::new())),
+            Tag::Empty(_) => Some(Value::String(String::new())),
+            Tag::Open("dict") => {
+                let mut entries = Vec::new();
+
+                loop {
+                    match self.next_tag()? {
+                        Tag::Close("dict") => break,
+                        Tag::Open("key") => {
+                            let key = self.leaf("key")?;
+                            let tag = self.next_tag()?;
+                            entries.push((key, self.value(tag)?));
+                        }
+                        _ => return None,
+                    }
+                }
+
+                Some(Value::Dict(entries))
+            }
+            Tag::Open("array") => {
+                let mut values = Vec::new();
+
+                loop {
+                    match self.next_tag()? {
+                        Tag::Close("array") => break,
+                        tag => values.push(self.value(tag)?),
+                    }
+                }
+
+                Some(Value::Array(values))
+            }
+            Tag::Open("integer") => {
+                // Unsigned numbers are externalized in hexadecimal.
+                let text = self.leaf("integer")?;
+                let text = text.trim();
+                let value = match text.strip_prefix("0x") {
+                    Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+                    None => text.parse::<i64>().ok()?,
+                };
+
+                Some(Value::Integer(value))
+            }
+            Tag::Open(name) => Some(Value::String(self.leaf(name)?)),
+            Tag::Close(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple Computer//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>acpibat0</key>
+	<array>
+		<dict>
+			<key>cur-value</key>
+			<integer>0x3a98</integer>
+			<key>description</key>
+			<string>charge</string>
+			<key>max-value</key>
+			<integer>60000</integer>
+			<key>want-percentage</key>
+			<true/>
+		</dict>
+		<dict>
+			<key>description</key>
+			<string>AT&amp;T</string>
+		</dict>
+	</array>
+	<key>empty</key>
+	<array/>
+</dict>
+</plist>
+"#;
+
+        let value = parse(xml).unwrap();
+        assert_eq!(value.keys().collect::<Vec<_>>(), vec!["acpibat0", "empty"]);
+
+        let sensors = value.get("acpibat0").and_then(Value::as_array).unwrap();
+        assert_eq!(sensors.len(), 2);
+        assert_eq!(
+            sensors[0].get("cur-value").and_then(Value::as_integer),
+            Some(15000)
+        );
+        assert_eq!(
+            sensors[0].get("max-value").and_then(Value::as_integer),
+            Some(60000)
+        );
+        assert_eq!(sensors[0].get("want-percentage"), Some(&Value::Bool(true)));
+        assert_eq!(
+            sensors[1].get("description").and_then(Value::as_str),
+            Some("AT&T")
+        );
+        assert_eq!(value.get("empty"), Some(&Value::Array(Vec::new())));
+        assert_eq!(parse("<plist><dict><key>a</key></dict></plist>"), None);
+    }
+}
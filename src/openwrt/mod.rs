@@ -27,7 +27,9 @@ pub struct OpenWrtMemoryReadout {
 
 pub struct OpenWrtProductReadout;
 pub struct OpenWrtPackageReadout;
-pub struct OpenWrtNetworkReadout;
+pub struct OpenWrtNetworkReadout {
+    default_interface: shared::DefaultInterface,
+}
 
 impl BatteryReadout for OpenWrtBatteryReadout {
     fn new() -> Self {
@@ -70,6 +72,10 @@ impl KernelReadout for OpenWrtKernelReadout {
             .ok_or(ReadoutError::MetricNotAvailable)?
             .value_string()?)
     }
+
+    fn architecture(&self) -> Result<String, ReadoutError> {
+        shared::architecture()
+    }
 }
 
 impl GeneralReadout for OpenWrtGeneralReadout {
@@ -104,8 +110,27 @@ impl GeneralReadout for OpenWrtGeneralReadout {
             }
         }
 
+        // Only some MIPS targets list the machine in /proc/cpuinfo, ARM
+        // routers have to be identified through the board data instead.
+        if let Some(model) = board_json()
+            .as_ref()
+            .and_then(|board| board["model"]["name"].as_str())
+            .filter(|model| !model.is_empty())
+        {
+            return Ok(model.to_string());
+        }
+
+        for path in ["/proc/device-tree/model", "/tmp/sysinfo/model"] {
+            if let Ok(model) = fs::read_to_string(path) {
+                let model = model.trim_end_matches('\0').trim();
+                if !model.is_empty() {
+                    return Ok(model.to_string());
+                }
+            }
+        }
+
         Err(ReadoutError::Other(String::from(
-            "Machine information not available in /proc/cpuinfo",
+            "Machine information not available in /proc/cpuinfo, /etc/board.json or the device tree",
         )))
     }
 
@@ -123,6 +148,11 @@ impl GeneralReadout for OpenWrtGeneralReadout {
 
     fn distribution(&self) -> Result<String, ReadoutError> {
         use os_release::OsRelease;
+
+        if let Some(description) = release_description() {
+            return Ok(description);
+        }
+
         let content = OsRelease::new()?;
         if !content.version_id.is_empty() {
             return Ok(format!("{} {}", content.name, content.version_id));
@@ -195,6 +225,24 @@ impl GeneralReadout for OpenWrtGeneralReadout {
         }
     }
 
+    fn load_average(&self) -> Result<(f64, f64, f64), ReadoutError> {
+        let mut info = self.sysinfo;
+        let info_ptr: *mut sysinfo = &mut info;
+        let ret = unsafe { sysinfo(info_ptr) };
+        if ret != -1 {
+            let f_load = 1f64 / (1 << libc::SI_LOAD_SHIFT) as f64;
+            Ok((
+                info.loads[0] as f64 * f_load,
+                info.loads[1] as f64 * f_load,
+                info.loads[2] as f64 * f_load,
+            ))
+        } else {
+            Err(ReadoutError::Other(String::from(
+                "sysinfo struct returned an error.",
+            )))
+        }
+    }
+
     fn uptime(&self) -> Result<usize, ReadoutError> {
         let mut info = self.sysinfo;
         let info_ptr: *mut sysinfo = &mut info;
@@ -213,12 +261,23 @@ impl GeneralReadout for OpenWrtGeneralReadout {
     }
 
     fn disk_space(&self, path: &Path) -> Result<(u64, u64), ReadoutError> {
+        // The root filesystem is an overlay of the read-only squashfs image and
+        // the writable /overlay partition, which is what actually fills up.
+        // Systems running entirely from a ramdisk have no such mount.
+        if path == Path::new("/") && is_mount_point("/overlay") {
+            return shared::disk_space_as_df(Path::new("/overlay"));
+        }
+
         shared::disk_space(path)
     }
 
     fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
         Err(ReadoutError::NotImplemented)
     }
+
+    fn locale(&self) -> Result<String, ReadoutError> {
+        shared::locale()
+    }
 }
 
 impl MemoryReadout for OpenWrtMemoryReadout {
@@ -343,30 +402,73 @@ impl OpenWrtPackageReadout {
 
 impl NetworkReadout for OpenWrtNetworkReadout {
     fn new() -> Self {
-        OpenWrtNetworkReadout
+        OpenWrtNetworkReadout {
+            default_interface: shared::DefaultInterface::default(),
+        }
     }
 
-    fn tx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "tx_bytes")
     }
 
-    fn tx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "tx_packets")
     }
 
-    fn rx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "rx_bytes")
     }
 
-    fn rx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        shared::interface_statistic(self.default_interface.resolve(interface)?, "rx_packets")
     }
 
     fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
-        shared::logical_address(interface)
+        shared::logical_address(Some(self.default_interface.resolve(interface)?))
     }
 
-    fn physical_address(&self, _: Option<&str>) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn physical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        shared::interface_address(self.default_interface.resolve(interface)?)
     }
 }
+
+/// Returns `true` if something is mounted on the given path, according to `/proc/mounts`.
+fn is_mount_point(path: &str) -> bool {
+    fs::read_to_string("/proc/mounts")
+        .map(|mounts| {
+            mounts
+                .lines()
+                .any(|line| line.split_whitespace().nth(1) == Some(path))
+        })
+        .unwrap_or(false)
+}
+
+/// Parses `/etc/board.json`, the board data that `ubus call system board` is built from.
+fn board_json() -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string("/etc/board.json").ok()?).ok()
+}
+
+/// Returns the full release description, _e.g._ `OpenWrt 23.05.3 r23809-234f1a2efa`.
+fn release_description() -> Option<String> {
+    if let Some(description) = board_json()
+        .as_ref()
+        .and_then(|board| board["release"]["description"].as_str())
+    {
+        return Some(description.to_string());
+    }
+
+    // ubus fills in the release section from /etc/openwrt_release, which is
+    // where it's to be found when the board data on disk lacks it.
+    let content = fs::read_to_string("/etc/openwrt_release").ok()?;
+
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("DISTRIB_DESCRIPTION="))
+        .map(|value| {
+            value
+                .trim()
+                .trim_matches(|c| c == '\'' || c == '"')
+                .to_string()
+        })
+        .filter(|description| !description.is_empty())
+}
@@ -2,7 +2,17 @@
 #![allow(unused_imports)]
 #![allow(clippy::unnecessary_cast)]
 
-use crate::traits::{ReadoutError, ShellFormat, ShellKind};
+use crate::traits::{DiskSpace, ReadoutError, ShellFormat, ShellKind};
+
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+use crate::traits::Display;
+
+#[cfg(feature = "public-ip")]
+use crate::traits::IpVersion;
 
 use std::fs::read_dir;
 use std::fs::read_to_string;
@@ -13,10 +23,20 @@ use std::{env, fs};
 use std::{ffi::CStr, path::PathBuf};
 
 use std::ffi::CString;
-#[cfg(any(target_os = "linux", target_os = "macos", target_os = "android"))]
+#[cfg(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "android",
+    target_os = "freebsd"
+))]
 use sysctl::SysctlError;
 
-#[cfg(any(target_os = "linux", target_os = "macos", target_os = "android"))]
+#[cfg(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "android",
+    target_os = "freebsd"
+))]
 impl From<SysctlError> for ReadoutError {
     fn from(e: SysctlError) -> Self {
         ReadoutError::Other(format!("Could not access sysctl: {e:?}"))
@@ -59,7 +79,11 @@ pub(crate) fn desktop_environment() -> Result<String, ReadoutError> {
                 )));
             }
 
-            Ok(crate::extra::ucfirst(de))
+            normalize_desktop_environment(&de).ok_or_else(|| {
+                ReadoutError::Other(String::from(
+                    "You appear to be only running a window manager.",
+                ))
+            })
         }
         Err(_) => Err(ReadoutError::Other(String::from(
             "You appear to be only running a window manager.",
@@ -67,6 +91,43 @@ pub(crate) fn desktop_environment() -> Result<String, ReadoutError> {
     }
 }
 
+/// Maps the value of `XDG_CURRENT_DESKTOP`, which may hold several colon-separated
+/// identifiers, _e.g._ `ubuntu:GNOME`, to the display name of the desktop environment.
+#[cfg(not(any(
+    feature = "openwrt",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "windows"
+)))]
+fn normalize_desktop_environment(value: &str) -> Option<String> {
+    // Distributions prepend their own name to the desktop they ship.
+    const VENDORS: [&str; 4] = ["ubuntu", "pop", "zorin", "elementary"];
+
+    let identifier = value
+        .split(':')
+        .map(|identifier| identifier.trim().trim_start_matches("X-"))
+        .find(|identifier| {
+            !identifier.is_empty() && !VENDORS.contains(&identifier.to_lowercase().as_str())
+        })?;
+
+    let name = match identifier.to_lowercase().as_str() {
+        "gnome" => "GNOME",
+        "kde" | "plasma" => "Plasma",
+        "xfce" => "XFCE",
+        "lxqt" => "LXQt",
+        "lxde" => "LXDE",
+        "mate" => "MATE",
+        "cinnamon" => "Cinnamon",
+        "budgie" | "budgie-desktop" => "Budgie",
+        "deepin" => "Deepin",
+        "pantheon" => "Pantheon",
+        "unity" => "Unity",
+        _ => return Some(crate::extra::ucfirst(identifier)),
+    };
+
+    Some(name.to_string())
+}
+
 #[cfg(not(any(
     feature = "openwrt",
     target_os = "android",
@@ -74,11 +135,94 @@ pub(crate) fn desktop_environment() -> Result<String, ReadoutError> {
     target_os = "windows"
 )))]
 pub(crate) fn session() -> Result<String, ReadoutError> {
-    match env::var("XDG_SESSION_TYPE") {
-        Ok(s) => Ok(crate::extra::ucfirst(s)),
-        Err(_) => Err(ReadoutError::Other(String::from(
-            "No graphical session detected.",
-        ))),
+    if let Some(session) = env::var("XDG_SESSION_TYPE")
+        .ok()
+        .and_then(|kind| normalize_session_type(&kind))
+    {
+        return Ok(session);
+    }
+
+    // Sessions started with startx are registered as TTY sessions by logind, so the
+    // environment is a better indicator when a display server is running.
+    let has_display = env::var_os("WAYLAND_DISPLAY").is_some() || env::var_os("DISPLAY").is_some();
+
+    if let Some(session) = logind_session_type().filter(|s| s != "TTY" || !has_display) {
+        return Ok(session);
+    }
+
+    if env::var_os("WAYLAND_DISPLAY").is_some() {
+        Ok(String::from("Wayland"))
+    } else if env::var_os("DISPLAY").is_some() {
+        Ok(String::from("X11"))
+    } else {
+        Ok(String::from("TTY"))
+    }
+}
+
+/// Returns the type of the current session as recorded by systemd-logind, looking the
+/// session up by `XDG_SESSION_ID`, or by the controlling terminal otherwise.
+#[cfg(not(any(
+    feature = "openwrt",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "windows"
+)))]
+fn logind_session_type() -> Option<String> {
+    let sessions = Path::new("/run/systemd/sessions");
+
+    if let Some(id) = env::var_os("XDG_SESSION_ID") {
+        if let Ok(session) = read_to_string(sessions.join(id)) {
+            return parse_logind_session(&session, "TYPE").and_then(|t| normalize_session_type(&t));
+        }
+    }
+
+    // e.g. "/dev/pts/0"
+    let tty = unsafe { libc::ttyname(libc::STDIN_FILENO) };
+    if tty.is_null() {
+        return None;
+    }
+
+    let tty = unsafe { CStr::from_ptr(tty) }.to_str().ok()?;
+    let tty = tty.strip_prefix("/dev/").unwrap_or(tty);
+
+    read_dir(sessions)
+        .ok()?
+        .filter_map(|entry| read_to_string(entry.ok()?.path()).ok())
+        .find(|session| parse_logind_session(session, "TTY").as_deref() == Some(tty))
+        .and_then(|session| parse_logind_session(&session, "TYPE"))
+        .and_then(|kind| normalize_session_type(&kind))
+}
+
+/// Returns the value of `key` in a session file found under `/run/systemd/sessions`.
+#[cfg(not(any(
+    feature = "openwrt",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "windows"
+)))]
+fn parse_logind_session(session: &str, key: &str) -> Option<String> {
+    session
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Maps a session type, _e.g._ `x11`, to the name returned by `session()`.
+#[cfg(not(any(
+    feature = "openwrt",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "windows"
+)))]
+fn normalize_session_type(kind: &str) -> Option<String> {
+    match kind.trim().to_lowercase().as_str() {
+        "" | "unspecified" => None,
+        "x11" => Some(String::from("X11")),
+        "wayland" => Some(String::from("Wayland")),
+        "tty" => Some(String::from("TTY")),
+        "mir" => Some(String::from("Mir")),
+        _ => Some(crate::extra::ucfirst(kind.trim())),
     }
 }
 
@@ -89,29 +233,485 @@ pub(crate) fn window_manager() -> Result<String, ReadoutError> {
     match session()?.as_str() {
         "Wayland" => detect_wayland_window_manager(),
         "X11" => detect_xorg_window_manager(),
+        "TTY" => Err(ReadoutError::Warning(String::from(
+            "No window manager is running in a TTY session.",
+        ))),
         _ => Err(ReadoutError::MetricNotAvailable),
     }
 }
 
 #[cfg(any(target_os = "netbsd", target_os = "freebsd"))]
 pub(crate) fn resolution() -> Result<String, ReadoutError> {
+    Ok(x11_displays()?
+        .iter()
+        .map(|display| match display.refresh_rate {
+            Some(refresh_rate) => format!(
+                "{}x{}@{}Hz",
+                display.width,
+                display.height,
+                refresh_rate.round()
+            ),
+            None => format!("{}x{}", display.width, display.height),
+        })
+        .collect::<Vec<_>>()
+        .join(", "))
+}
+
+/// Returns the current mode of every output connected to the X11 server, using
+/// the RandR extension if it is available and the size of each screen otherwise.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+pub(crate) fn x11_displays() -> Result<Vec<Display>, ReadoutError> {
     use x11rb::connection::Connection;
+    use x11rb::protocol::randr::{self, ConnectionExt};
 
-    let mut resolution: Vec<String> = vec![];
-    if let Ok(conn) = x11rb::connect(None) {
-        let screens = &conn.0.setup().roots;
-        for s in screens {
-            let width = s.width_in_pixels;
-            let height = s.height_in_pixels;
-            resolution.push(width.to_string() + "x" + &height.to_string())
+    let (conn, _) = x11rb::connect(None).map_err(|_| {
+        ReadoutError::Warning(String::from(
+            "Could not open a connection to the X11 server.",
+        ))
+    })?;
+
+    // GetScreenResourcesCurrent was introduced in RandR 1.3.
+    let has_randr = conn
+        .randr_query_version(1, 3)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .is_some_and(|version| (version.major_version, version.minor_version) >= (1, 3));
+
+    let mut displays: Vec<Display> = vec![];
+
+    for screen in &conn.setup().roots {
+        let resources = if has_randr {
+            conn.randr_get_screen_resources_current(screen.root)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+        } else {
+            None
+        };
+
+        // Without RandR, multiple monitors are lumped into a single screen.
+        let resources = match resources {
+            Some(resources) => resources,
+            None => {
+                displays.push(Display {
+                    name: None,
+                    width: screen.width_in_pixels as u32,
+                    height: screen.height_in_pixels as u32,
+                    refresh_rate: None,
+                    scaled_width: None,
+                    scaled_height: None,
+                });
+                continue;
+            }
+        };
+
+        for output in &resources.outputs {
+            let info = match conn
+                .randr_get_output_info(*output, resources.config_timestamp)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+            {
+                Some(info) if info.connection == randr::Connection::CONNECTED && info.crtc != 0 => {
+                    info
+                }
+                _ => continue,
+            };
+
+            let crtc = match conn
+                .randr_get_crtc_info(info.crtc, resources.config_timestamp)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+            {
+                Some(crtc) if crtc.mode != 0 => crtc,
+                _ => continue,
+            };
+
+            let refresh_rate = resources
+                .modes
+                .iter()
+                .find(|mode| mode.id == crtc.mode)
+                .and_then(randr_refresh_rate);
+
+            displays.push(Display {
+                name: Some(String::from_utf8_lossy(&info.name).into_owned()),
+                width: crtc.width as u32,
+                height: crtc.height as u32,
+                refresh_rate,
+                scaled_width: None,
+                scaled_height: None,
+            });
         }
+    }
+
+    Ok(displays)
+}
+
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+fn randr_refresh_rate(mode: &x11rb::protocol::randr::ModeInfo) -> Option<f32> {
+    use x11rb::protocol::randr::ModeFlag;
 
-        return Ok(resolution.join(", "));
+    let total = mode.htotal as f32 * mode.vtotal as f32;
+    if mode.dot_clock == 0 || total == 0.0 {
+        return None;
     }
 
-    Err(ReadoutError::Warning(String::from(
-        "Could not open a connection to the X11 server.",
-    )))
+    let mut refresh_rate = mode.dot_clock as f32 / total;
+
+    if mode.mode_flags.contains(ModeFlag::INTERLACE) {
+        refresh_rate *= 2.0;
+    }
+
+    if mode.mode_flags.contains(ModeFlag::DOUBLE_SCAN) {
+        refresh_rate /= 2.0;
+    }
+
+    Some(refresh_rate)
+}
+
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd",
+    target_os = "macos"
+))]
+/// Provides access to the process tree, see `find_terminal()`.
+pub(crate) trait ProcessTree {
+    /// Returns the PID of the parent of the given process.
+    fn parent(&self, pid: i32) -> Option<i32>;
+
+    /// Returns the name of the given process, _e.g._ `kitty`
+    fn name(&self, pid: i32) -> Option<String>;
+
+    /// Returns the PID of a client attached to the given terminal multiplexer.
+    fn multiplexer_client(&self, multiplexer: &str) -> Option<i32> {
+        match multiplexer {
+            "tmux" => {
+                // $TMUX points to the server's socket, which makes the query target the
+                // session we're running in.
+                env::var_os("TMUX")?;
+
+                // A stale socket can make tmux hang, hence the timeout.
+                command_output(
+                    Path::new("tmux"),
+                    &["display-message", "-p", "#{client_pid}"],
+                )
+                .ok()?
+                .trim()
+                .parse()
+                .ok()
+            }
+            // The server daemonizes itself, whereas clients are started from a shell.
+            "screen" => read_dir("/proc")
+                .ok()?
+                .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<i32>().ok())
+                .find(|&pid| {
+                    self.name(pid).as_deref() == Some("screen") && self.parent(pid) != Some(1)
+                }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd",
+    target_os = "macos"
+))]
+/// The terminal emulator inside of which the user is operating.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Terminal {
+    pub pid: i32,
+    pub name: String,
+    /// The terminal multiplexer the user is running inside of, _e.g._ `tmux`
+    pub multiplexer: Option<&'static str>,
+}
+
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd",
+    target_os = "macos"
+))]
+impl std::fmt::Display for Terminal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Some terminals run as a differently named server or wrapper process.
+        let name = match self.name.as_str() {
+            "gnome-terminal" | "gnome-terminal-server" => "GNOME Terminal",
+            "kgx" => "GNOME Console",
+            "xfce4-terminal-wrapper" => "xfce4-terminal",
+            name => name,
+        };
+
+        match self.multiplexer {
+            Some(multiplexer) => write!(f, "{name} (via {multiplexer})"),
+            None => write!(f, "{name}"),
+        }
+    }
+}
+
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd",
+    target_os = "macos"
+))]
+fn multiplexer(name: &str) -> Option<&'static str> {
+    match name {
+        "tmux" | "tmux: server" | "tmux: client" => Some("tmux"),
+        "screen" | "SCREEN" => Some("screen"),
+        _ => None,
+    }
+}
+
+/// Walks up the process tree from `pid`, skipping shells, until the terminal emulator is
+/// found. Terminal multiplexers are resolved to the terminal their client is running in.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd",
+    target_os = "macos"
+))]
+pub(crate) fn find_terminal(tree: &impl ProcessTree, mut pid: i32) -> Option<Terminal> {
+    let mut via: Option<&'static str> = None;
+
+    // Guards against cycles in a process tree that changed while we were walking it.
+    for _ in 0..64 {
+        let name = tree.name(pid)?;
+
+        if crate::extra::common_shells().contains(&name.as_str()) {
+            pid = tree.parent(pid)?;
+            continue;
+        }
+
+        if let Some(multiplexer) = multiplexer(&name) {
+            // The server is a child of PID 1, so continue from the client instead.
+            if via.is_none() {
+                via = Some(multiplexer);
+
+                if let Some(client) = tree.multiplexer_client(multiplexer) {
+                    pid = tree.parent(client)?;
+                    continue;
+                }
+            }
+
+            return Some(Terminal {
+                pid,
+                name: multiplexer.to_string(),
+                multiplexer: None,
+            });
+        }
+
+        return Some(Terminal {
+            pid,
+            name,
+            multiplexer: via,
+        });
+    }
+
+    None
+}
+
+/// Returns the version of a known terminal emulator by running `<exe> --version`, killing
+/// the process if it doesn't exit in time.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+pub(crate) fn terminal_version(name: &str, exe: Option<&Path>) -> Result<String, ReadoutError> {
+    let program = match name {
+        "kitty" | "alacritty" | "wezterm" | "wezterm-gui" | "foot" | "footclient" => {
+            exe.map_or_else(|| PathBuf::from(name), Path::to_path_buf)
+        }
+        // The terminal runs as a D-Bus activated server (org.gnome.Terminal) which doesn't
+        // take any arguments, unlike its client. Its name is truncated to 15 characters
+        // when read from /proc/<pid>/comm.
+        "gnome-terminal" | "gnome-terminal-server" | "gnome-terminal-" => {
+            PathBuf::from("gnome-terminal")
+        }
+        _ => return Err(ReadoutError::MetricNotAvailable),
+    };
+
+    parse_version(&version_output(&program)?).ok_or(ReadoutError::MetricNotAvailable)
+}
+
+/// Returns the standard output of `<program> --version`, killing the process if it doesn't
+/// exit in time.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+fn version_output(program: &Path) -> Result<String, ReadoutError> {
+    command_output(program, &["--version"])
+}
+
+/// Returns the standard output of the given command, killing the process if it doesn't
+/// exit within a second.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "freebsd",
+    target_os = "android"
+))]
+pub(crate) fn command_output(program: &Path, args: &[&str]) -> Result<String, ReadoutError> {
+    use std::io::Read;
+    use std::time::{Duration, Instant};
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|_| ReadoutError::MetricNotAvailable)?;
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if start.elapsed() < TIMEOUT => std::thread::sleep(Duration::from_millis(10)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(ReadoutError::Other(format!(
+                    "\"{} {}\" did not exit in time.",
+                    program.display(),
+                    args.join(" ")
+                )));
+            }
+        }
+    }
+
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout.read_to_string(&mut output)?;
+    }
+
+    Ok(output)
+}
+
+/// Returns the name and version of the running desktop environment, preferring files and
+/// environment variables over spawning processes.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+pub(crate) fn desktop_environment_version() -> Result<String, ReadoutError> {
+    let desktop = desktop_environment()?.to_lowercase();
+    let version_of = |program: &str| {
+        version_output(Path::new(program))
+            .ok()
+            .and_then(|output| parse_version(&output))
+    };
+
+    if desktop.contains("kde") || desktop.contains("plasma") {
+        // The desktop shell package ships its version in its metadata.
+        let shell = Path::new("/usr/share/plasma/shells/org.kde.plasma.desktop");
+        let version = read_to_string(shell.join("metadata.json"))
+            .ok()
+            .and_then(|metadata| parse_json_string(&metadata, "Version"))
+            .or_else(|| {
+                read_to_string(shell.join("metadata.desktop"))
+                    .ok()?
+                    .lines()
+                    .find_map(|line| line.strip_prefix("X-KDE-PluginInfo-Version="))
+                    .map(|version| version.trim().to_string())
+            })
+            .or_else(|| version_of("plasmashell"))
+            .or_else(|| env::var("KDE_SESSION_VERSION").ok());
+
+        return version
+            .map(|version| format!("Plasma {version}"))
+            .ok_or(ReadoutError::MetricNotAvailable);
+    }
+
+    let version = if desktop.contains("gnome") {
+        read_to_string("/usr/share/gnome/gnome-version.xml")
+            .ok()
+            .and_then(|xml| parse_gnome_version(&xml))
+            .or_else(|| version_of("gnome-shell"))
+            .map(|version| format!("GNOME {version}"))
+    } else if desktop.contains("cinnamon") {
+        read_to_string("/usr/share/cinnamon/cinnamon-version")
+            .ok()
+            .map(|version| version.trim().to_string())
+            .filter(|version| !version.is_empty())
+            .or_else(|| version_of("cinnamon"))
+            .map(|version| format!("Cinnamon {version}"))
+    } else if desktop.contains("xfce") {
+        version_of("xfce4-session").map(|version| format!("Xfce {version}"))
+    } else if desktop.contains("mate") {
+        version_of("mate-session").map(|version| format!("MATE {version}"))
+    } else {
+        None
+    };
+
+    version.ok_or(ReadoutError::MetricNotAvailable)
+}
+
+/// Returns the value of the first string field named `key` in a JSON document.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+pub(crate) fn parse_json_string(json: &str, key: &str) -> Option<String> {
+    let (_, rest) = json.split_once(&format!("\"{key}\""))?;
+    let (_, value) = rest.trim_start().strip_prefix(':')?.split_once('"')?;
+    let (value, _) = value.split_once('"')?;
+
+    Some(value.to_string())
+}
+
+/// Parses `gnome-version.xml`, _e.g._ `<platform>46</platform><minor>0</minor>` yields `46.0`.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+fn parse_gnome_version(xml: &str) -> Option<String> {
+    let tag = |name: &str| -> Option<&str> {
+        let (_, rest) = xml.split_once(&format!("<{name}>"))?;
+        let (value, _) = rest.split_once(&format!("</{name}>"))?;
+        Some(value.trim()).filter(|value| !value.is_empty())
+    };
+
+    let platform = tag("platform")?;
+
+    match tag("minor") {
+        Some(minor) => Some(format!("{platform}.{minor}")),
+        None => Some(platform.to_string()),
+    }
+}
+
+/// Returns the first version-looking token of `output`, _e.g._ `0.26.5` in
+/// `kitty 0.26.5 created by Kovid Goyal`.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+fn parse_version(output: &str) -> Option<String> {
+    output.split_whitespace().find_map(|token| {
+        let token = token
+            .trim_start_matches('v')
+            .trim_end_matches([',', ';', ')']);
+        let starts_with_digit = token.starts_with(|c: char| c.is_ascii_digit());
+
+        (starts_with_digit && token.contains(['.', '-'])).then(|| token.to_string())
+    })
 }
 
 #[cfg(target_family = "unix")]
@@ -174,16 +774,27 @@ pub(crate) fn shell(shorthand: ShellFormat, kind: ShellKind) -> Result<String, R
             )))
         }
         ShellKind::Current => {
-            if cfg!(target_os = "macos") {
-                Err(ReadoutError::Other(String::from(
-                    "Retrieving the current shell is not supported on macOS.",
-                )))
-            } else {
-                let path = PathBuf::from("/proc")
-                    .join(unsafe { libc::getppid() }.to_string())
-                    .join("comm");
+            #[cfg(target_os = "macos")]
+            {
+                let path = macos_current_shell().ok_or_else(|| {
+                    ReadoutError::Other(String::from("Unable to read current shell."))
+                })?;
 
-                if let Ok(shell) = read_to_string(path) {
+                match shorthand {
+                    ShellFormat::Relative => path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .map(String::from)
+                        .ok_or_else(|| {
+                            ReadoutError::Other(String::from("Unable to read current shell."))
+                        }),
+                    ShellFormat::Absolute => Ok(path.to_string_lossy().into_owned()),
+                }
+            }
+
+            #[cfg(not(target_os = "macos"))]
+            {
+                if let Some(shell) = process_name(Path::new("/proc"), unsafe { libc::getppid() }) {
                     return Ok(shell);
                 }
 
@@ -195,6 +806,99 @@ pub(crate) fn shell(shorthand: ShellFormat, kind: ShellKind) -> Result<String, R
     }
 }
 
+/// Returns the path of the executable of the shell the user is currently running, skipping
+/// over any intermediate processes, _e.g._ `sudo` or `env`, between the shell and us.
+#[cfg(target_os = "macos")]
+fn macos_current_shell() -> Option<PathBuf> {
+    fn process_path(pid: libc::c_int) -> Option<PathBuf> {
+        let mut buffer = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+        let len =
+            unsafe { libc::proc_pidpath(pid, buffer.as_mut_ptr() as *mut _, buffer.len() as u32) };
+
+        if len <= 0 {
+            return None;
+        }
+
+        buffer.truncate(len as usize);
+        Some(PathBuf::from(String::from_utf8(buffer).ok()?))
+    }
+
+    fn parent(pid: libc::c_int) -> Option<libc::c_int> {
+        let mut info: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+        let ret = unsafe {
+            libc::proc_pidinfo(
+                pid,
+                libc::PROC_PIDTBSDINFO,
+                0,
+                &mut info as *mut _ as *mut _,
+                size,
+            )
+        };
+
+        (ret == size).then_some(info.pbi_ppid as libc::c_int)
+    }
+
+    let mut pid = unsafe { libc::getppid() };
+    let parent_path = process_path(pid)?;
+
+    // Bounded, in case the process tree changes while it is being walked.
+    for _ in 0..16 {
+        if pid <= 1 {
+            break;
+        }
+
+        if let Some(path) = process_path(pid) {
+            let name = path.file_name().and_then(|name| name.to_str());
+            if name.is_some_and(|name| crate::extra::common_shells().contains(&name)) {
+                return Some(path);
+            }
+        }
+
+        pid = parent(pid)?;
+    }
+
+    // Nothing that looks like a shell was found, so report our parent.
+    Some(parent_path)
+}
+
+/// Returns the name of a process from the basename of `<proc>/<pid>/exe`, falling back to the
+/// first argument in `<proc>/<pid>/cmdline`, then `<proc>/<pid>/comm`, which is truncated to 15
+/// characters.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub(crate) fn process_name(proc: &Path, pid: i32) -> Option<String> {
+    let process = proc.join(pid.to_string());
+
+    let basename = |path: &str| -> Option<String> {
+        // Executables that were replaced while running are marked as such, e.g. after an upgrade.
+        let path = path.trim_end_matches(" (deleted)");
+        // Login shells are prefixed with a dash, e.g. "-zsh".
+        let name = Path::new(path.trim_start_matches('-'))
+            .file_name()?
+            .to_str()?;
+        Some(name.to_string()).filter(|name| !name.is_empty())
+    };
+
+    if let Some(name) = fs::read_link(process.join("exe"))
+        .ok()
+        .and_then(|exe| basename(exe.to_str()?))
+    {
+        return Some(name);
+    }
+
+    if let Some(name) = fs::read(process.join("cmdline")).ok().and_then(|cmdline| {
+        let arg0 = cmdline.split(|&b| b == 0).next()?;
+        basename(std::str::from_utf8(arg0).ok()?.split_whitespace().next()?)
+    }) {
+        return Some(name);
+    }
+
+    read_to_string(process.join("comm"))
+        .ok()
+        .map(|comm| comm.trim_end().to_string())
+        .filter(|comm| !comm.is_empty())
+}
+
 #[cfg(not(any(target_os = "macos", target_os = "windows")))]
 pub(crate) fn cpu_model_name() -> String {
     use std::io::{BufRead, BufReader};
@@ -217,6 +921,22 @@ pub(crate) fn cpu_model_name() -> String {
     }
 }
 
+/// Maps the vendor identifier reported by the `CPUID` instruction, _e.g._
+/// `GenuineIntel`, to the vendor's name.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+pub(crate) fn cpu_vendor_name(vendor_id: &str) -> String {
+    match vendor_id.trim() {
+        "GenuineIntel" | "GenuineIotel" => String::from("Intel"),
+        "AuthenticAMD" | "AMDisbetter!" => String::from("AMD"),
+        "HygonGenuine" => String::from("Hygon"),
+        "CentaurHauls" => String::from("Centaur"),
+        "Shanghai" => String::from("Zhaoxin"),
+        "CyrixInstead" => String::from("Cyrix"),
+        "VIA VIA VIA" => String::from("VIA"),
+        other => other.to_string(),
+    }
+}
+
 #[cfg(any(target_os = "freebsd", target_os = "macos", target_os = "netbsd"))]
 pub(crate) fn cpu_usage() -> Result<usize, ReadoutError> {
     let nelem: i32 = 1;
@@ -233,6 +953,51 @@ pub(crate) fn cpu_usage() -> Result<usize, ReadoutError> {
     )))
 }
 
+/// Returns the `machine` field of `uname(2)`, _e.g._ `x86_64`.
+#[cfg(target_family = "unix")]
+pub(crate) fn architecture() -> Result<String, ReadoutError> {
+    let mut utsname: libc::utsname = unsafe { std::mem::zeroed() };
+
+    if unsafe { libc::uname(&mut utsname) } == -1 {
+        return Err(ReadoutError::Other(String::from(
+            "Call to \"uname\" failed.",
+        )));
+    }
+
+    Ok(unsafe { CStr::from_ptr(utsname.machine.as_ptr()) }
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Returns the locale set in the environment, following the precedence of `LC_ALL`,
+/// `LC_MESSAGES` and `LANG`.
+#[cfg(target_family = "unix")]
+pub(crate) fn locale() -> Result<String, ReadoutError> {
+    // Empty values of the LC_* variables are ignored, as per POSIX.
+    for var in ["LC_ALL", "LC_MESSAGES"] {
+        if let Ok(locale) = env::var(var) {
+            if !locale.is_empty() {
+                return Ok(locale);
+            }
+        }
+    }
+
+    env::var("LANG").map_err(|_| ReadoutError::MetricNotAvailable)
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "macos", target_os = "netbsd"))]
+pub(crate) fn load_average() -> Result<(f64, f64, f64), ReadoutError> {
+    let nelem: i32 = 3;
+    let mut values: [f64; 3] = [0.0; 3];
+    let ret = unsafe { libc::getloadavg(values.as_mut_ptr(), nelem) };
+    if ret == nelem {
+        return Ok((values[0], values[1], values[2]));
+    }
+    Err(ReadoutError::Other(format!(
+        "getloadavg failed with return code: {ret}"
+    )))
+}
+
 #[cfg(target_family = "unix")]
 pub(crate) fn cpu_cores() -> Result<usize, ReadoutError> {
     Ok(num_cpus::get())
@@ -244,7 +1009,7 @@ pub(crate) fn cpu_physical_cores() -> Result<usize, ReadoutError> {
 }
 
 #[cfg(not(any(target_os = "netbsd", target_os = "windows")))]
-pub(crate) fn disk_space(path: &Path) -> Result<(u64, u64), ReadoutError> {
+fn statfs(path: &Path) -> Result<libc::statfs, ReadoutError> {
     use std::os::unix::ffi::OsStrExt;
 
     if !path.is_dir() || !path.is_absolute() {
@@ -259,28 +1024,139 @@ pub(crate) fn disk_space(path: &Path) -> Result<(u64, u64), ReadoutError> {
         .expect("Could not create C string for disk usage path.");
 
     if unsafe { libc::statfs(path.as_ptr(), s.as_mut_ptr()) } == 0 {
-        #[cfg(target_pointer_width = "32")]
-        type UInt = u32;
-        #[cfg(target_pointer_width = "64")]
-        type UInt = u64;
+        return Ok(unsafe { s.assume_init() });
+    }
 
-        let stats: libc::statfs = unsafe { s.assume_init() };
+    Err(ReadoutError::Other(String::from(
+        "Error while trying to get statfs structure.",
+    )))
+}
+
+#[cfg(not(any(target_os = "netbsd", target_os = "windows")))]
+pub(crate) fn disk_space(path: &Path) -> Result<(u64, u64), ReadoutError> {
+    let stats = statfs(path)?;
 
-        let disk_size = stats.f_blocks as UInt * stats.f_bsize as UInt;
-        let free = stats.f_bavail as UInt * stats.f_bsize as UInt;
+    #[cfg(target_pointer_width = "32")]
+    type UInt = u32;
+    #[cfg(target_pointer_width = "64")]
+    type UInt = u64;
 
-        let used_byte = disk_size - free;
-        let disk_size_byte = disk_size;
+    let disk_size = stats.f_blocks as UInt * stats.f_bsize as UInt;
+    let free = stats.f_bavail as UInt * stats.f_bsize as UInt;
+
+    let used_byte = disk_size - free;
+    let disk_size_byte = disk_size;
+
+    #[cfg(target_pointer_width = "32")]
+    return Ok((used_byte.into(), disk_size_byte.into()));
+    #[cfg(target_pointer_width = "64")]
+    return Ok((used_byte, disk_size_byte));
+}
 
-        #[cfg(target_pointer_width = "32")]
-        return Ok((used_byte.into(), disk_size_byte.into()));
-        #[cfg(target_pointer_width = "64")]
-        return Ok((used_byte, disk_size_byte));
+/// Returns the used and total space of the filesystem mounted at the given path the way
+/// `df(1)` accounts for them: the blocks reserved for the superuser are neither used nor
+/// available, so the total is the sum of the used and available space.
+#[cfg(all(target_os = "linux", feature = "openwrt"))]
+pub(crate) fn disk_space_as_df(path: &Path) -> Result<(u64, u64), ReadoutError> {
+    let stats = statfs(path)?;
+    let block_size = stats.f_bsize as u64;
+
+    let used = (stats.f_blocks as u64 - stats.f_bfree as u64) * block_size;
+    let available = stats.f_bavail as u64 * block_size;
+
+    Ok((used, used + available))
+}
+
+/// Returns `true` if the given filesystem type does not represent actual storage.
+pub(crate) fn is_pseudo_filesystem(fs_type: &str) -> bool {
+    matches!(
+        fs_type,
+        "proc"
+            | "procfs"
+            | "sysfs"
+            | "tmpfs"
+            | "devtmpfs"
+            | "devpts"
+            | "devfs"
+            | "cgroup"
+            | "cgroup2"
+            | "securityfs"
+            | "pstore"
+            | "bpf"
+            | "debugfs"
+            | "tracefs"
+            | "configfs"
+            | "fusectl"
+            | "mqueue"
+            | "hugetlbfs"
+            | "autofs"
+            | "binfmt_misc"
+            | "efivarfs"
+            | "nsfs"
+            | "ramfs"
+            | "rpc_pipefs"
+            | "selinuxfs"
+            | "fdescfs"
+            | "kernfs"
+            | "ptyfs"
+            | "nullfs"
+    )
+}
+
+#[cfg(any(target_os = "macos", target_os = "freebsd", target_os = "netbsd"))]
+pub(crate) fn disk_spaces() -> Result<Vec<DiskSpace>, ReadoutError> {
+    #[cfg(target_os = "netbsd")]
+    type StatFs = libc::statvfs;
+    #[cfg(not(target_os = "netbsd"))]
+    type StatFs = libc::statfs;
+
+    // The buffer returned by getmntinfo(3) is managed by libc and must not be freed.
+    let mut mounts: *mut StatFs = std::ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut mounts, libc::MNT_NOWAIT) };
+
+    if count <= 0 || mounts.is_null() {
+        return Err(ReadoutError::Other(String::from(
+            "Could not retrieve the list of mounted filesystems.",
+        )));
     }
 
-    Err(ReadoutError::Other(String::from(
-        "Error while trying to get statfs structure.",
-    )))
+    let mounts = unsafe { std::slice::from_raw_parts(mounts, count as usize) };
+    let mut devices: Vec<String> = Vec::new();
+    let mut disks: Vec<DiskSpace> = Vec::new();
+
+    for mount in mounts {
+        let fs_type = unsafe { CStr::from_ptr(mount.f_fstypename.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+        let device = unsafe { CStr::from_ptr(mount.f_mntfromname.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+        let mount_point = unsafe { CStr::from_ptr(mount.f_mntonname.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+
+        if is_pseudo_filesystem(&fs_type) || devices.contains(&device) {
+            continue;
+        }
+
+        #[cfg(target_os = "netbsd")]
+        let block_size = mount.f_frsize as u64;
+        #[cfg(not(target_os = "netbsd"))]
+        let block_size = mount.f_bsize as u64;
+
+        let total = mount.f_blocks as u64 * block_size;
+        let free = (mount.f_bavail as i64).max(0) as u64 * block_size;
+
+        devices.push(device);
+        disks.push(DiskSpace {
+            mount_point: PathBuf::from(mount_point),
+            fs_type,
+            used: total.saturating_sub(free),
+            total,
+        });
+    }
+
+    Ok(disks)
 }
 
 /// Obtain the value of a specified field from `/proc/meminfo` needed to calculate memory usage
@@ -329,6 +1205,537 @@ pub(crate) fn logical_address(interface: Option<&str>) -> Result<String, Readout
     )))
 }
 
+/// Returns the local IPv4 address that outgoing connections are made from, by "connecting" a
+/// UDP socket to a public address, which doesn't send any packets, and reading the address
+/// it was bound to.
+#[cfg(any(target_os = "macos", target_os = "freebsd", target_os = "netbsd"))]
+pub(crate) fn primary_address() -> Result<String, ReadoutError> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0")?;
+    socket.connect("1.1.1.1:53")?;
+
+    match socket.local_addr()?.ip() {
+        ip if ip.is_unspecified() || ip.is_loopback() => Err(ReadoutError::MetricNotAvailable),
+        ip => Ok(ip.to_string()),
+    }
+}
+
+/// Caches the interface carrying the default route, which the network readouts query when
+/// they're not given an interface.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+#[derive(Debug, Default)]
+pub(crate) struct DefaultInterface(std::sync::OnceLock<Option<String>>);
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+impl DefaultInterface {
+    /// Returns the given interface, or the one carrying the default route if none is given.
+    pub(crate) fn resolve<'a>(
+        &'a self,
+        interface: Option<&'a str>,
+    ) -> Result<&'a str, ReadoutError> {
+        if let Some(ifname) = interface {
+            return Ok(ifname);
+        }
+
+        self.0
+            .get_or_init(|| default_interface().ok())
+            .as_deref()
+            .ok_or(ReadoutError::MetricNotAvailable)
+    }
+}
+
+/// Returns the interface carrying the default route with the lowest metric.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn default_interface() -> Result<String, ReadoutError> {
+    parse_proc_net_route(&fs::read_to_string("/proc/net/route")?, None)
+        .map(|(ifname, _)| ifname)
+        .ok_or(ReadoutError::MetricNotAvailable)
+}
+
+/// Reads one of the counters under `/sys/class/net/<interface>/statistics`, _e.g._ `rx_bytes`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn interface_statistic(ifname: &str, statistic: &str) -> Result<usize, ReadoutError> {
+    let path = Path::new("/sys/class/net")
+        .join(ifname)
+        .join("statistics")
+        .join(statistic);
+    let content = fs::read_to_string(path)?;
+
+    Ok(crate::extra::pop_newline(content)
+        .parse::<usize>()
+        .unwrap_or_default())
+}
+
+/// Reads the hardware address of the given interface from `/sys/class/net/<interface>/address`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn interface_address(ifname: &str) -> Result<String, ReadoutError> {
+    let content = fs::read_to_string(Path::new("/sys/class/net").join(ifname).join("address"))?;
+
+    Ok(crate::extra::pop_newline(content))
+}
+
+/// Parses the interface and gateway of the default route with the lowest metric, optionally
+/// restricted to the given interface, from the contents of `/proc/net/route`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn parse_proc_net_route(
+    content: &str,
+    interface: Option<&str>,
+) -> Option<(String, std::net::Ipv4Addr)> {
+    const RTF_UP: u16 = 0x1;
+    const RTF_GATEWAY: u16 = 0x2;
+
+    // Addresses are written as hexadecimal numbers in host byte order, which /proc/net/route
+    // always renders as little-endian.
+    let parse_addr = |hex: &str| u32::from_str_radix(hex, 16).ok().map(u32::to_le_bytes);
+
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            // Fields: Iface, Destination, Gateway, Flags, RefCnt, Use, Metric, Mask, ...
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (ifname, destination, gateway, flags, metric, mask) = (
+                *fields.first()?,
+                parse_addr(fields.get(1)?)?,
+                parse_addr(fields.get(2)?)?,
+                u16::from_str_radix(fields.get(3)?, 16).ok()?,
+                fields.get(6)?.parse::<u32>().ok()?,
+                parse_addr(fields.get(7)?)?,
+            );
+
+            let is_default = destination == [0; 4] && mask == [0; 4];
+            let is_usable = flags & RTF_UP != 0 && flags & RTF_GATEWAY != 0;
+
+            if !is_default || !is_usable || interface.is_some_and(|i| i != ifname) {
+                return None;
+            }
+
+            Some((metric, ifname, std::net::Ipv4Addr::from(gateway)))
+        })
+        .min_by_key(|(metric, _, _)| *metric)
+        .map(|(_, ifname, gateway)| (ifname.to_string(), gateway))
+}
+
+/// Reads the raw value of the given MIB, _e.g._ a dump of the routing table.
+#[cfg(any(target_os = "macos", target_os = "netbsd", target_os = "freebsd"))]
+pub(crate) fn sysctl_raw(mib: &mut [libc::c_int]) -> Result<Vec<u8>, ReadoutError> {
+    let mut sysctl = |buffer: *mut libc::c_void, len: &mut libc::size_t| unsafe {
+        libc::sysctl(
+            mib.as_mut_ptr(),
+            mib.len() as libc::c_uint,
+            buffer,
+            len,
+            std::ptr::null_mut(),
+            0,
+        ) == 0
+    };
+
+    let mut len: libc::size_t = 0;
+    if !sysctl(std::ptr::null_mut(), &mut len) {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    // The value may grow in between both calls.
+    len += len / 8;
+
+    let mut buffer = vec![0u8; len];
+    if !sysctl(buffer.as_mut_ptr() as *mut libc::c_void, &mut len) {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    buffer.truncate(len);
+
+    Ok(buffer)
+}
+
+/// Mirrors the `rt_msghdr` structure declared in `net/route.h`.
+#[cfg(target_os = "freebsd")]
+#[allow(non_camel_case_types, dead_code)]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct rt_msghdr {
+    rtm_msglen: u16,
+    rtm_version: u8,
+    rtm_type: u8,
+    rtm_index: u16,
+    _rtm_spare1: u16,
+    rtm_flags: libc::c_int,
+    rtm_addrs: libc::c_int,
+    rtm_pid: libc::pid_t,
+    rtm_seq: libc::c_int,
+    rtm_errno: libc::c_int,
+    rtm_fmask: libc::c_int,
+    rtm_inits: libc::c_ulong,
+    rtm_rmx: [libc::c_ulong; 14],
+}
+
+/// Mirrors the `rt_msghdr` structure declared in `net/route.h`, whose members are
+/// aligned to 64 bits on every architecture.
+#[cfg(target_os = "netbsd")]
+#[allow(non_camel_case_types, dead_code)]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct rt_msghdr {
+    rtm_msglen: u16,
+    rtm_version: u8,
+    rtm_type: u8,
+    _rtm_pad0: u32,
+    rtm_index: u16,
+    rtm_flags: libc::c_int,
+    rtm_addrs: libc::c_int,
+    rtm_pid: libc::pid_t,
+    rtm_seq: libc::c_int,
+    rtm_errno: libc::c_int,
+    rtm_use: libc::c_int,
+    _rtm_pad1: u32,
+    rtm_inits: libc::c_int,
+    _rtm_pad2: u32,
+    rtm_rmx: [u64; 10],
+}
+
+#[cfg(target_os = "macos")]
+use libc::rt_msghdr;
+
+/// Returns the interface carrying the IPv4 default route, from the routing table that the
+/// `PF_ROUTE` sysctl dumps.
+#[cfg(any(target_os = "macos", target_os = "netbsd", target_os = "freebsd"))]
+pub(crate) fn default_interface() -> Result<String, ReadoutError> {
+    let buffer = sysctl_raw(&mut [
+        libc::CTL_NET,
+        libc::PF_ROUTE,
+        0,
+        libc::AF_INET,
+        libc::NET_RT_FLAGS,
+        libc::RTF_GATEWAY,
+    ])
+    .map_err(|_| ReadoutError::Other(String::from("Could not read the routing table.")))?;
+
+    let header_len = std::mem::size_of::<rt_msghdr>();
+    let mut offset = 0;
+
+    while offset + header_len <= buffer.len() {
+        let header =
+            unsafe { std::ptr::read_unaligned(buffer[offset..].as_ptr() as *const rt_msghdr) };
+
+        let msglen = header.rtm_msglen as usize;
+        if msglen == 0 {
+            break;
+        }
+
+        // The destination is the first of the socket addresses following the header.
+        let dst_offset = offset + header_len;
+        let is_default = header.rtm_flags & libc::RTF_UP != 0
+            && header.rtm_flags & libc::RTF_HOST == 0
+            && header.rtm_addrs & libc::RTA_DST != 0
+            && dst_offset + std::mem::size_of::<libc::sockaddr_in>() <= buffer.len()
+            && {
+                let dst = unsafe {
+                    std::ptr::read_unaligned(
+                        buffer[dst_offset..].as_ptr() as *const libc::sockaddr_in
+                    )
+                };
+
+                i32::from(dst.sin_family) == libc::AF_INET && dst.sin_addr.s_addr == 0
+            };
+
+        if is_default {
+            let mut name = [0 as libc::c_char; libc::IF_NAMESIZE];
+            let index = header.rtm_index as libc::c_uint;
+            let ifname = unsafe { libc::if_indextoname(index, name.as_mut_ptr()) };
+
+            if !ifname.is_null() {
+                let ifname = unsafe { CStr::from_ptr(ifname) };
+                return Ok(ifname.to_string_lossy().into_owned());
+            }
+        }
+
+        offset += msglen;
+    }
+
+    Err(ReadoutError::MetricNotAvailable)
+}
+
+/// Returns the gateway of the default route reported by `route(8)`, optionally restricted to
+/// the given interface.
+#[cfg(any(target_os = "freebsd", target_os = "macos", target_os = "netbsd"))]
+pub(crate) fn default_gateway(interface: Option<&str>) -> Result<String, ReadoutError> {
+    let mut args = vec!["-n", "get"];
+
+    // Only macOS is able to look up the scoped default route of a specific interface.
+    #[cfg(target_os = "macos")]
+    if let Some(ifname) = interface {
+        args.extend(["-ifscope", ifname]);
+    }
+
+    args.push("default");
+
+    let output = command_output(Path::new("route"), &args)?;
+    let field = |name: &str| {
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(name)?.strip_prefix(':'))
+            .map(str::trim)
+    };
+
+    match (field("gateway"), field("interface")) {
+        (Some(gateway), Some(ifname)) if interface.is_none() || interface == Some(ifname) => {
+            Ok(gateway.to_string())
+        }
+        _ => Err(ReadoutError::MetricNotAvailable),
+    }
+}
+
+/// Returns the `nameserver` entries of `/etc/resolv.conf`, or those of the upstream servers
+/// if the system uses the stub resolver of systemd-resolved.
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn dns_servers() -> Result<Vec<String>, ReadoutError> {
+    let servers = parse_resolv_conf(&fs::read_to_string("/etc/resolv.conf")?);
+
+    if servers == ["127.0.0.53"] {
+        if let Ok(content) = fs::read_to_string("/run/systemd/resolve/resolv.conf") {
+            return Ok(parse_resolv_conf(&content));
+        }
+    }
+
+    Ok(servers)
+}
+
+/// Parses the deduplicated `nameserver` entries out of the contents of `resolv.conf(5)`.
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn parse_resolv_conf(content: &str) -> Vec<String> {
+    let mut servers: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        if fields.next() != Some("nameserver") {
+            continue;
+        }
+
+        if let Some(server) = fields.next() {
+            if !servers.iter().any(|s| s == server) {
+                servers.push(server.to_string());
+            }
+        }
+    }
+
+    servers
+}
+
+#[cfg(feature = "public-ip")]
+pub(crate) fn public_address(version: IpVersion) -> Result<String, ReadoutError> {
+    use std::net::{SocketAddr, ToSocketAddrs};
+    use std::time::Duration;
+
+    let url = env::var("LIBMACCHINA_PUBLIC_IP_URL")
+        .unwrap_or_else(|_| String::from("https://icanhazip.com"));
+
+    // The endpoint reports the address it was reached from, so the protocol version is
+    // picked by only connecting to addresses of that version.
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(1))
+        .resolver(move |netloc: &str| -> std::io::Result<Vec<SocketAddr>> {
+            Ok(netloc
+                .to_socket_addrs()?
+                .filter(|addr| match version {
+                    IpVersion::V4 => addr.is_ipv4(),
+                    IpVersion::V6 => addr.is_ipv6(),
+                })
+                .collect())
+        })
+        .build();
+
+    let response = agent
+        .get(&url)
+        .call()
+        .map_err(|e| ReadoutError::Warning(format!("Unable to reach \"{url}\": {e}")))?;
+
+    let address = response.into_string().map_err(|e| {
+        ReadoutError::Warning(format!("Unable to read the response of \"{url}\": {e}"))
+    })?;
+
+    match address.trim() {
+        "" => Err(ReadoutError::Warning(format!(
+            "\"{url}\" returned an empty response."
+        ))),
+        address => Ok(address.to_string()),
+    }
+}
+
+/// The statistics and hardware address of a network interface, as reported by the
+/// link-level entry that `getifaddrs(3)` returns for it.
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+pub(crate) struct LinkInfo {
+    pub name: String,
+    pub flags: libc::c_uint,
+    pub data: libc::if_data,
+    pub address: Vec<u8>,
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+impl LinkInfo {
+    /// Returns the hardware address as colon-separated hexadecimal octets, _e.g._
+    /// `52:9a:d2:d3:b5:fd`.
+    pub(crate) fn mac_address(&self) -> Result<String, ReadoutError> {
+        if self.address.is_empty() {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        Ok(self
+            .address
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(":"))
+    }
+}
+
+/// Returns the link-level entry of every interface reported by `getifaddrs(3)`.
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+pub(crate) fn link_infos() -> Result<Vec<LinkInfo>, ReadoutError> {
+    let mut ifaddrs: *mut libc::ifaddrs = std::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut ifaddrs) } == -1 {
+        return Err(ReadoutError::Other(String::from(
+            "Call to \"getifaddrs\" failed.",
+        )));
+    }
+
+    let mut links = Vec::new();
+    let mut current = ifaddrs;
+
+    while let Some(ifaddr) = unsafe { current.as_ref() } {
+        current = ifaddr.ifa_next;
+
+        let is_link = !ifaddr.ifa_addr.is_null()
+            && i32::from(unsafe { (*ifaddr.ifa_addr).sa_family }) == libc::AF_LINK;
+
+        if !is_link || ifaddr.ifa_data.is_null() {
+            continue;
+        }
+
+        let name = unsafe { CStr::from_ptr(ifaddr.ifa_name) };
+        let data = unsafe { *(ifaddr.ifa_data as *const libc::if_data) };
+
+        // The hardware address follows the interface name, see LLADDR() in net/if_dl.h.
+        // `sockaddr_dl` is variable-length, so `sdl_data` may extend past its declared size.
+        let sdl = ifaddr.ifa_addr as *const libc::sockaddr_dl;
+        let (len, nlen, alen) = unsafe {
+            (
+                (*sdl).sdl_len as usize,
+                (*sdl).sdl_nlen as usize,
+                (*sdl).sdl_alen as usize,
+            )
+        };
+
+        let sdl_data = unsafe { std::ptr::addr_of!((*sdl).sdl_data) } as *const u8;
+        let available = len.saturating_sub(sdl_data as usize - sdl as usize);
+        let address = if nlen + alen <= available {
+            unsafe { std::slice::from_raw_parts(sdl_data.add(nlen), alen) }.to_vec()
+        } else {
+            Vec::new()
+        };
+
+        links.push(LinkInfo {
+            name: name.to_string_lossy().into_owned(),
+            flags: ifaddr.ifa_flags,
+            data,
+            address,
+        });
+    }
+
+    unsafe { libc::freeifaddrs(ifaddrs) };
+
+    Ok(links)
+}
+
+/// Returns the link-level entry of the given interface.
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+pub(crate) fn link_info(ifname: &str) -> Result<LinkInfo, ReadoutError> {
+    let mut links = link_infos()?;
+
+    match links.iter().position(|link| link.name == ifname) {
+        Some(index) => Ok(links.swap_remove(index)),
+        None => Err(ReadoutError::Other(format!(
+            "Interface \"{ifname}\" does not exist, available interfaces are: {}",
+            links
+                .iter()
+                .map(|link| link.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Returns the link speed of the given interface, in Mbit/s, from the `if_data` structure
+/// that `getifaddrs(3)` provides for link-level addresses.
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+pub(crate) fn link_speed(ifname: &str) -> Result<usize, ReadoutError> {
+    let link = link_info(ifname)?;
+
+    // Interfaces that aren't running report their last, or maximum, speed.
+    if link.flags & libc::IFF_RUNNING as libc::c_uint == 0 {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    match link.data.ifi_baudrate as u64 {
+        0 => Err(ReadoutError::MetricNotAvailable),
+        baudrate => Ok((baudrate / 1_000_000) as usize),
+    }
+}
+
+/// Builds a machine name out of the product information reported by the firmware,
+/// discarding placeholder values and words that are repeated across fields.
+#[cfg(any(
+    all(target_os = "linux", not(feature = "openwrt")),
+    target_os = "netbsd",
+    target_os = "freebsd"
+))]
+pub(crate) fn machine_name(
+    vendor: &str,
+    family: &str,
+    product: &str,
+    version: &str,
+) -> Result<String, ReadoutError> {
+    // If one field is generic, the others are likely the same, so fail the readout.
+    if vendor.eq_ignore_ascii_case("system manufacturer") {
+        return Err(ReadoutError::Other(String::from(
+            "Your manufacturer may have not specified your machine's product information.",
+        )));
+    }
+
+    if !family.is_empty() && family == product && family == version {
+        return Ok(family.to_string());
+    } else if version.len() > 22 {
+        return Ok(version.to_string());
+    }
+
+    let new_product =
+        format!("{vendor} {family} {product} {version}").replace("To be filled by O.E.M.", "");
+
+    let mut words: Vec<&str> = Vec::new();
+    for word in new_product.split_whitespace() {
+        if !words.contains(&word) {
+            words.push(word);
+        }
+    }
+
+    if words.is_empty() {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    Ok(words.join(" "))
+}
+
 pub(crate) fn count_cargo() -> Option<usize> {
     let bin = home::cargo_home().ok()?.join("bin");
     let read_dir = read_dir(bin).ok()?;
@@ -338,3 +1745,402 @@ pub(crate) fn count_cargo() -> Option<usize> {
         pkgs => Some(pkgs),
     }
 }
+
+/// Returns the number of packages installed in the user's Nix profile, reading the manifest
+/// of either a `nix profile` or a `nix-env` profile, as `nix-env -q` is pretty slow.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub(crate) fn count_nix_profile(home: &Path) -> Option<usize> {
+    let nix_profile = home.join(".nix-profile");
+    let manifests = [
+        home.join(".local/state/nix/profiles/profile/manifest.json"),
+        nix_profile.join("manifest.json"),
+    ];
+
+    for manifest in manifests {
+        if let Some(count) = read_to_string(manifest)
+            .ok()
+            .and_then(|content| parse_nix_manifest_json(&content))
+        {
+            return Some(count);
+        }
+    }
+
+    let manifest = read_to_string(nix_profile.join("manifest.nix")).ok()?;
+    Some(parse_nix_manifest_nix(&manifest))
+}
+
+/// Counts the elements of a `nix profile` manifest, which are held in an array up until
+/// version 2 of its format, and in an object keyed by their name afterwards.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub(crate) fn parse_nix_manifest_json(content: &str) -> Option<usize> {
+    let manifest: serde_json::Value = serde_json::from_str(content).ok()?;
+
+    match manifest.get("elements")? {
+        serde_json::Value::Array(elements) => Some(elements.len()),
+        serde_json::Value::Object(elements) => Some(elements.len()),
+        _ => None,
+    }
+}
+
+/// Counts the derivations listed in a `nix-env` manifest, which is a Nix expression rather
+/// than JSON.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub(crate) fn parse_nix_manifest_nix(content: &str) -> usize {
+    content.matches("type = \"derivation\";").count()
+}
+
+/// A directory created under the system's temporary directory for the fixtures of a test,
+/// which is removed along with its contents once dropped, even if the test panics.
+#[cfg(test)]
+pub(crate) struct TempDir(PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    /// Creates a new, empty directory whose name starts with `libmacchina-{name}`, and is
+    /// unique to this guard so that tests running in parallel never share their fixtures.
+    pub(crate) fn new(name: &str) -> Self {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = env::temp_dir().join(format!(
+            "libmacchina-{name}-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        TempDir(path)
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_os = "windows"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resolv_conf() {
+        let content = "# Generated by NetworkManager\n\
+                       search lan\n\
+                       nameserver 192.168.1.1\n\
+                       nameserver fe80::1%wlp2s0\n\
+                       nameserver 192.168.1.1\n\
+                       options edns0 trust-ad\n";
+
+        assert_eq!(
+            parse_resolv_conf(content),
+            vec![String::from("192.168.1.1"), String::from("fe80::1%wlp2s0")]
+        );
+        assert!(parse_resolv_conf("search lan\n").is_empty());
+    }
+
+    #[test]
+    #[cfg(any(
+        all(target_os = "linux", not(feature = "openwrt")),
+        target_os = "netbsd",
+        target_os = "freebsd"
+    ))]
+    fn test_parse_version() {
+        for (output, version) in [
+            ("kitty 0.26.5 created by Kovid Goyal\n", "0.26.5"),
+            ("alacritty 0.12.2 (1:0.12.2-1)\n", "0.12.2"),
+            (
+                "wezterm 20230712-072601-f4abf8fd\n",
+                "20230712-072601-f4abf8fd",
+            ),
+            (
+                "foot version: 1.15.3 +pgo +ime +graphemes -assertions\n",
+                "1.15.3",
+            ),
+            (
+                "# GNOME Terminal 3.44.0 using VTE 0.68.0 +BIDI +GNUTLS\n",
+                "3.44.0",
+            ),
+        ] {
+            assert_eq!(parse_version(output).as_deref(), Some(version));
+        }
+
+        assert_eq!(parse_version("unknown option --version"), None);
+    }
+
+    #[cfg(any(
+        all(target_os = "linux", not(feature = "openwrt")),
+        target_os = "netbsd",
+        target_os = "freebsd"
+    ))]
+    struct FakeProcessTree {
+        processes: Vec<(i32, i32, &'static str)>,
+        client: Option<i32>,
+    }
+
+    #[cfg(any(
+        all(target_os = "linux", not(feature = "openwrt")),
+        target_os = "netbsd",
+        target_os = "freebsd"
+    ))]
+    impl ProcessTree for FakeProcessTree {
+        fn parent(&self, pid: i32) -> Option<i32> {
+            self.processes.iter().find(|p| p.0 == pid).map(|p| p.1)
+        }
+
+        fn name(&self, pid: i32) -> Option<String> {
+            self.processes
+                .iter()
+                .find(|p| p.0 == pid)
+                .map(|p| p.2.to_string())
+        }
+
+        fn multiplexer_client(&self, _: &str) -> Option<i32> {
+            self.client
+        }
+    }
+
+    #[test]
+    #[cfg(any(
+        all(target_os = "linux", not(feature = "openwrt")),
+        target_os = "netbsd",
+        target_os = "freebsd"
+    ))]
+    fn test_find_terminal() {
+        // (pid, ppid, name)
+        let mut tree = FakeProcessTree {
+            processes: vec![
+                (100, 1, "kitty"),
+                (101, 100, "zsh"),
+                (102, 101, "tmux: client"),
+                (200, 1, "tmux: server"),
+                (201, 200, "bash"),
+            ],
+            client: Some(102),
+        };
+
+        assert_eq!(
+            find_terminal(&tree, 101),
+            Some(Terminal {
+                pid: 100,
+                name: String::from("kitty"),
+                multiplexer: None,
+            })
+        );
+
+        let terminal = find_terminal(&tree, 201).unwrap();
+        assert_eq!(terminal.pid, 100);
+        assert_eq!(terminal.to_string(), "kitty (via tmux)");
+
+        // Without a client, the multiplexer is the best we can do.
+        tree.client = None;
+        assert_eq!(find_terminal(&tree, 201).unwrap().to_string(), "tmux");
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn test_process_name() {
+        let proc = TempDir::new("proc");
+
+        // Only the truncated comm is readable.
+        let process = proc.join("100");
+        fs::create_dir_all(&process).unwrap();
+        fs::write(process.join("comm"), "gnome-terminal-\n").unwrap();
+        assert_eq!(process_name(&proc, 100).as_deref(), Some("gnome-terminal-"));
+
+        // The cmdline takes precedence over comm.
+        fs::write(
+            process.join("cmdline"),
+            "/usr/libexec/gnome-terminal-server\0--app-id\0",
+        )
+        .unwrap();
+        assert_eq!(
+            process_name(&proc, 100).as_deref(),
+            Some("gnome-terminal-server")
+        );
+
+        // Login shells are prefixed with a dash.
+        let process = proc.join("101");
+        fs::create_dir_all(&process).unwrap();
+        fs::write(process.join("cmdline"), "-zsh\0").unwrap();
+        assert_eq!(process_name(&proc, 101).as_deref(), Some("zsh"));
+
+        // The executable takes precedence over everything else.
+        std::os::unix::fs::symlink("/usr/bin/xfce4-terminal", process.join("exe")).unwrap();
+        assert_eq!(process_name(&proc, 101).as_deref(), Some("xfce4-terminal"));
+
+        assert_eq!(process_name(&proc, 102), None);
+    }
+
+    #[test]
+    #[cfg(any(
+        all(target_os = "linux", not(feature = "openwrt")),
+        target_os = "netbsd",
+        target_os = "freebsd"
+    ))]
+    fn test_desktop_environment_version_sources() {
+        let metadata = r#"{
+            "KPlugin": {
+                "Id": "org.kde.plasma.desktop",
+                "Version": "6.0.4"
+            }
+        }"#;
+        assert_eq!(
+            parse_json_string(metadata, "Version").as_deref(),
+            Some("6.0.4")
+        );
+        assert_eq!(parse_json_string(metadata, "Name"), None);
+
+        let xml = "<gnome-version>\n  <platform>46</platform>\n  <minor>0</minor>\n  \
+                   <micro></micro>\n</gnome-version>\n";
+        assert_eq!(parse_gnome_version(xml).as_deref(), Some("46.0"));
+        assert_eq!(parse_gnome_version("<gnome-version/>"), None);
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "openwrt", target_os = "android", target_os = "macos")))]
+    fn test_normalize_desktop_environment() {
+        for (value, expected) in [
+            ("ubuntu:GNOME", "GNOME"),
+            ("Budgie:GNOME", "Budgie"),
+            ("X-Cinnamon", "Cinnamon"),
+            ("KDE", "Plasma"),
+            ("XFCE", "XFCE"),
+            ("LXQt", "LXQt"),
+            ("pop:GNOME", "GNOME"),
+            ("Hyprland", "Hyprland"),
+            ("sway", "Sway"),
+        ] {
+            assert_eq!(
+                normalize_desktop_environment(value).as_deref(),
+                Some(expected)
+            );
+        }
+
+        assert_eq!(normalize_desktop_environment("ubuntu:"), None);
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "openwrt", target_os = "android", target_os = "macos")))]
+    fn test_logind_session() {
+        let session = "# This is private data. Do not parse.\nUID=1000\nUSER=user\n\
+                       ACTIVE=1\nTYPE=tty\nTTY=tty1\nDISPLAY=\n";
+
+        assert_eq!(
+            parse_logind_session(session, "TTY").as_deref(),
+            Some("tty1")
+        );
+        assert_eq!(parse_logind_session(session, "DISPLAY"), None);
+        assert_eq!(
+            parse_logind_session(session, "TYPE")
+                .and_then(|kind| normalize_session_type(&kind))
+                .as_deref(),
+            Some("TTY")
+        );
+
+        assert_eq!(normalize_session_type("x11").as_deref(), Some("X11"));
+        assert_eq!(
+            normalize_session_type("wayland").as_deref(),
+            Some("Wayland")
+        );
+        assert_eq!(normalize_session_type("unspecified"), None);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn test_cpu_vendor_name() {
+        assert_eq!(cpu_vendor_name("GenuineIntel"), "Intel");
+        assert_eq!(cpu_vendor_name("AuthenticAMD"), "AMD");
+        assert_eq!(cpu_vendor_name("  Shanghai  "), "Zhaoxin");
+        assert_eq!(cpu_vendor_name("SomeVendor"), "SomeVendor");
+    }
+
+    #[test]
+    #[cfg(any(
+        all(target_os = "linux", not(feature = "openwrt")),
+        target_os = "netbsd",
+        target_os = "freebsd"
+    ))]
+    fn test_machine_name() {
+        assert_eq!(
+            machine_name("LENOVO", "ThinkPad T480", "20L5CTO1WW", "ThinkPad T480").unwrap(),
+            "LENOVO ThinkPad T480 20L5CTO1WW"
+        );
+        assert_eq!(
+            machine_name(
+                "ASUS",
+                "To be filled by O.E.M.",
+                "PRIME B450M-A",
+                "To be filled by O.E.M."
+            )
+            .unwrap(),
+            "ASUS PRIME B450M-A"
+        );
+        assert_eq!(machine_name("QEMU", "pc", "pc", "pc").unwrap(), "pc");
+        assert!(machine_name("System manufacturer", "", "System Product Name", "").is_err());
+        assert!(machine_name("", "", "", "").is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_proc_net_route() {
+        let content =
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+                       wlp2s0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0\n\
+                       enp3s0\t00000000\t010010AC\t0003\t0\t0\t100\t00000000\t0\t0\t0\n\
+                       enp3s0\t000010AC\t00000000\t0001\t0\t0\t100\t0000FFFF\t0\t0\t0\n";
+
+        assert_eq!(
+            parse_proc_net_route(content, None),
+            Some((
+                String::from("enp3s0"),
+                std::net::Ipv4Addr::new(172, 16, 0, 1)
+            ))
+        );
+        assert_eq!(
+            parse_proc_net_route(content, Some("wlp2s0")),
+            Some((
+                String::from("wlp2s0"),
+                std::net::Ipv4Addr::new(192, 168, 1, 1)
+            ))
+        );
+        assert_eq!(parse_proc_net_route(content, Some("lo")), None);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_interface_statistic() {
+        assert!(interface_statistic("lo", "rx_bytes").is_ok());
+        assert!(interface_statistic("nonexistent0", "rx_bytes").is_err());
+        assert!(interface_address("nonexistent0").is_err());
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn test_parse_nix_manifest() {
+        let v2 = r#"{"version":2,"elements":[{"active":true,"storePaths":["/nix/store/a-hello-2.12.1"]},{"active":true,"storePaths":["/nix/store/b-ripgrep-14.1.0"]}]}"#;
+        let v3 = r#"{"version":3,"elements":{"hello":{"active":true},"ripgrep":{"active":true},"jq":{"active":true}}}"#;
+
+        assert_eq!(parse_nix_manifest_json(v2), Some(2));
+        assert_eq!(parse_nix_manifest_json(v3), Some(3));
+        assert_eq!(parse_nix_manifest_json(r#"{"version":3}"#), None);
+        assert_eq!(parse_nix_manifest_json("[ ]"), None);
+
+        let manifest = r#"[ { meta = { }; name = "hello-2.12.1"; out = { outPath = "/nix/store/a-hello-2.12.1"; }; outPath = "/nix/store/a-hello-2.12.1"; outputs = [ "out" ]; system = "x86_64-linux"; type = "derivation"; } { meta = { }; name = "jq-1.7"; outPath = "/nix/store/c-jq-1.7"; outputs = [ "out" ]; system = "x86_64-linux"; type = "derivation"; } ]"#;
+        assert_eq!(parse_nix_manifest_nix(manifest), 2);
+    }
+}
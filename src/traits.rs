@@ -2,7 +2,7 @@
 //! different readouts from various operating systems. For each operating system, there must be an implementation of these traits.
 #![allow(unused_variables)]
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// This enum contains possible error types when doing sensor & variable readouts.
 #[derive(Debug, Clone)]
@@ -15,6 +15,11 @@ pub enum ReadoutError {
     /// The default error for any readout that is not implemented by a particular platform.
     NotImplemented,
 
+    /// The hardware required by a readout is not present on the host (e. g. querying the
+    /// battery percentage on a desktop). \
+    /// This is an expected situation and should not be treated as a failure.
+    MetricUnavailableForHardware(String),
+
     /// A readout for a metric might be available, but fails due to missing dependencies or other
     /// unsatisfied requirements.
     Other(String),
@@ -33,6 +38,7 @@ impl std::fmt::Display for ReadoutError {
             ReadoutError::NotImplemented => {
                 write!(f, "This metric is not available on this platform or is not yet implemented by libmacchina.")
             }
+            ReadoutError::MetricUnavailableForHardware(s) => write!(f, "{}", s),
             ReadoutError::Other(s) => write!(f, "{}", s),
             ReadoutError::Warning(s) => write!(f, "{}", s),
         }
@@ -98,6 +104,33 @@ pub trait BatteryReadout {
 
     /// This function is used for querying the current battery's health in percentage.
     fn health(&self) -> Result<u8, ReadoutError>;
+
+    /// This function is used for querying a qualitative description of the battery's health as
+    /// reported by the system, _e.g._ `Good` or `Overheat`.
+    fn health_status(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for querying the estimated time remaining in seconds. If the
+    /// battery is discharging, this is the time until it is empty, otherwise it is the time until
+    /// it is fully charged.
+    fn time_remaining(&self) -> Result<usize, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for querying the current temperature of the battery in degrees
+    /// Celsius, _e.g._ `31.4`.
+    fn temperature(&self) -> Result<f32, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for querying the rate at which energy flows out of the battery in
+    /// watts, _e.g._ `8.52`.
+    ///
+    /// The value is positive while discharging, and negative while charging.
+    fn power_draw(&self) -> Result<f32, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
 }
 
 /**
@@ -150,6 +183,13 @@ pub trait KernelReadout {
 
         Err(ReadoutError::MetricNotAvailable)
     }
+
+    /// This function should return the hardware architecture of the host as reported by the
+    /// kernel, _e.g._ `x86_64` or `aarch64`, regardless of the architecture the calling process
+    /// was compiled for.
+    fn architecture(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
 }
 
 /**
@@ -199,6 +239,21 @@ impl MemoryReadout for MacOSMemoryReadout {
         // Get the currently used memory.
         Ok(256 * 1024) // Return 256mb in kilobytes.
     }
+
+    fn swap_total(&self) -> Result<u64, ReadoutError> {
+        // Get the total amount of swap
+        Ok(1024 * 1024) // Return 1gb in kilobytes.
+    }
+
+    fn swap_free(&self) -> Result<u64, ReadoutError> {
+        // Get the amount of free swap
+        Ok(768 * 1024) // Return 768mb in kilobytes.
+    }
+
+    fn swap_used(&self) -> Result<u64, ReadoutError> {
+        // Get the currently used swap.
+        Ok(256 * 1024) // Return 256mb in kilobytes.
+    }
 }
 
 ```
@@ -233,6 +288,27 @@ pub trait MemoryReadout {
 
     /// This function should return the amount of currently used swap in kilobytes.
     fn swap_used(&self) -> Result<u64, ReadoutError>;
+
+    /// This function returns the percentage of memory in use, rounded to the
+    /// nearest integer, _e.g._ `42`.
+    fn perc_used(&self) -> Result<u8, ReadoutError> {
+        percentage(self.used()?, self.total()?)
+    }
+
+    /// This function returns the percentage of swap in use, rounded to the
+    /// nearest integer, _e.g._ `7`.
+    fn swap_perc_used(&self) -> Result<u8, ReadoutError> {
+        percentage(self.swap_used()?, self.swap_total()?)
+    }
+}
+
+fn percentage(used: u64, total: u64) -> Result<u8, ReadoutError> {
+    if total == 0 {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    let percentage = (used as f64 / total as f64 * 100.0).round();
+    Ok(percentage.clamp(0.0, 100.0) as u8)
 }
 
 /**
@@ -272,6 +348,10 @@ pub trait PackageReadout {
 /**
 This trait provides an interface to various networking statistics about the host system.
 
+Unless documented otherwise, methods that are given `None` as their interface query the
+interface carrying the default route. It is looked up once and cached by the readout
+instance, so that reading several counters doesn't re-read the routing table every time.
+
 # Example
 
 ```
@@ -343,6 +423,59 @@ pub trait NetworkReadout {
     ///
     /// _e.g._ `52:9a:d2:d3:b5:fd`
     fn physical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError>;
+
+    /// This function should return the SSID of the wireless network the specified interface is
+    /// connected to, or that of the first connected wireless interface if none is specified.
+    ///
+    /// _e.g._ `HomeNetwork`
+    fn ssid(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the signal strength of the wireless network the specified
+    /// interface is connected to in dBm.
+    ///
+    /// _e.g._ `-56`
+    fn signal_strength(&self, interface: Option<&str>) -> Result<i32, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the IPv4 address of the default gateway of the specified
+    /// interface, or that of the default route with the lowest metric if none is specified.
+    ///
+    /// _e.g._ `192.168.1.1`
+    fn default_gateway(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the IPv4 and IPv6 addresses of the DNS servers used by the
+    /// host, in the order they're queried in.
+    ///
+    /// _e.g._ `["1.1.1.1", "2606:4700:4700::1111"]`
+    fn dns_servers(&self) -> Result<Vec<String>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the negotiated link speed of the specified interface in
+    /// Mbit/s.
+    ///
+    /// _e.g._ `1000`
+    fn link_speed(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the public IP address of the host, as seen by the
+    /// endpoint set in the `LIBMACCHINA_PUBLIC_IP_URL` environment variable, or
+    /// `https://icanhazip.com` by default.
+    ///
+    /// This is the only readout that performs a network request, and is thus only available
+    /// with the `public-ip` feature enabled.
+    ///
+    /// _e.g._ `203.0.113.42`
+    #[cfg(feature = "public-ip")]
+    fn public_address(&self, version: IpVersion) -> Result<String, ReadoutError> {
+        crate::shared::public_address(version)
+    }
 }
 
 /**
@@ -513,6 +646,14 @@ pub trait GeneralReadout {
     /// _e.g._ `100`
     fn backlight(&self) -> Result<usize, ReadoutError>;
 
+    /// This function should return the name and backlight (brightness) value of every
+    /// backlight device of the machine.
+    ///
+    /// _e.g._ `[("intel_backlight", 100), ("ddcci13", 50)]`
+    fn backlights(&self) -> Result<Vec<(String, usize)>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
     /// This function should return the display resolution of the machine.
     ///
     /// _e.g. `1920x1080`
@@ -548,11 +689,42 @@ pub trait GeneralReadout {
     /// _e.g._ `KWin`
     fn window_manager(&self) -> Result<String, ReadoutError>;
 
+    /// This function should return the name and version of the used desktop environment.
+    ///
+    /// _e.g._ `Plasma 6.0.4`
+    fn desktop_environment_version(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the name of the active GTK or desktop theme.
+    ///
+    /// _e.g._ `Adwaita-dark`
+    fn theme(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the name of the active icon theme.
+    ///
+    /// _e.g._ `Papirus`
+    fn icon_theme(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
     /// This function should return the name of the used terminal emulator.
     ///
     /// _e.g._ `kitty`
     fn terminal(&self) -> Result<String, ReadoutError>;
 
+    /// This function should return the version of the used terminal emulator.
+    ///
+    /// Unlike `terminal()`, this may spawn the terminal emulator's executable, which is why it is
+    /// a separate readout.
+    ///
+    /// _e.g._ `0.26.5`
+    fn terminal_version(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
     /**
     This function should return the currently running shell depending on the `_shorthand` value.
 
@@ -564,7 +736,6 @@ pub trait GeneralReadout {
 
     _e.g._ /bin/bash, /bin/zsh, etc.
     */
-
     fn shell(&self, _shorthand: ShellFormat, kind: ShellKind) -> Result<String, ReadoutError>;
 
     /// This function should return the model name of the CPU \
@@ -572,7 +743,27 @@ pub trait GeneralReadout {
     /// _e.g._ `Intel(R) Core(TM) i5-8265U CPU @ 1.60GHz`
     fn cpu_model_name(&self) -> Result<String, ReadoutError>;
 
-    /// This function should return the average CPU usage over the last minute.
+    /// This function should return the vendor of the host's processor.
+    ///
+    /// _e.g._ `AMD`
+    fn cpu_vendor(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the lowercase, sorted feature flags of the host's
+    /// processor.
+    ///
+    /// _e.g._ `["avx", "avx2", "sse4_2"]`
+    fn cpu_flags(&self) -> Result<Vec<String>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the CPU usage in percent.
+    ///
+    /// On Linux and Windows, this is the usage over a 200 ms window, during which the call
+    /// blocks while it samples the time the processor spent busy. Other platforms, as well as
+    /// Linux when `/proc/stat` can't be read, derive it from the load average over the last
+    /// minute instead, without blocking.
     fn cpu_usage(&self) -> Result<usize, ReadoutError>;
 
     /// This function should return the number of physical cores of the host's processor.
@@ -581,6 +772,34 @@ pub trait GeneralReadout {
     /// This function should return the number of logical cores of the host's processor.
     fn cpu_cores(&self) -> Result<usize, ReadoutError>;
 
+    /// This function should return the number of physical cores of each kind of a processor
+    /// with a heterogeneous architecture, _e.g._ `4P+4E` on an Apple M1.
+    ///
+    /// Processors whose cores are all alike should use
+    /// [`ReadoutError::MetricUnavailableForHardware`].
+    fn cpu_core_layout(&self) -> Result<CpuCoreLayout, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the maximum frequency of the host's processor in MHz.
+    ///
+    /// _e.g._ `3900`
+    fn cpu_frequency(&self) -> Result<usize, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the current temperature of the CPU package in degrees
+    /// Celsius, _e.g._ `46.5`.
+    fn cpu_temperature(&self) -> Result<f32, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the system load averages over the last 1, 5 and 15
+    /// minutes, _e.g._ `(0.52, 0.61, 0.70)`.
+    fn load_average(&self) -> Result<(f64, f64, f64), ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
     /// This function should return the uptime of the OS in seconds.
     fn uptime(&self) -> Result<usize, ReadoutError>;
 
@@ -602,6 +821,123 @@ pub trait GeneralReadout {
 
     /// This function should return the device names of any _GPU(s)_ connected to the host machine.
     fn gpus(&self) -> Result<Vec<String>, ReadoutError>;
+
+    /// This function should return the kernel driver bound to each _GPU_ connected to the host
+    /// machine, along with its version, in the same order as `gpus()`.
+    ///
+    /// _e.g._ `["amdgpu (6.7.9)", "(no driver)"]`
+    fn gpu_drivers(&self) -> Result<Vec<String>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the device name and total video memory, in bytes, of any
+    /// _GPU(s)_ with dedicated memory connected to the host machine.
+    ///
+    /// _e.g._ `[("Radeon RX 6800", 17163091968)]`
+    fn gpu_memory(&self) -> Result<Vec<(String, u64)>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the disk space usage of every mounted filesystem, skipping
+    /// pseudo-filesystems and duplicate mounts of the same device.
+    fn disk_spaces(&self) -> Result<Vec<DiskSpace>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the cumulative amount of bytes read from and written to
+    /// each disk since boot.
+    ///
+    /// Partitions, loop and RAM devices are skipped, unless `_all` is `true`.
+    fn disk_io(&self, _all: bool) -> Result<Vec<DiskIo>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the physical disks attached to the host, skipping
+    /// virtual block devices.
+    fn disks(&self) -> Result<Vec<Disk>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the active locale of the system, _e.g._ `en_US.UTF-8`.
+    ///
+    /// The `C` and `POSIX` locales are returned as-is.
+    fn locale(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the displays connected to the host, along with their
+    /// current mode.
+    fn displays(&self) -> Result<Vec<Display>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the name of the init system, _e.g._ `systemd` or `OpenRC`.
+    fn init_system(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+}
+
+/// Holds the current mode of a display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Display {
+    /// The name of the connector or monitor, _e.g._ `HDMI-A-1`
+    pub name: Option<String>,
+    /// The width in pixels.
+    pub width: u32,
+    /// The height in pixels.
+    pub height: u32,
+    /// The refresh rate in Hz, _e.g._ `59.95`
+    pub refresh_rate: Option<f32>,
+    /// The width in points, if the display is scaled.
+    pub scaled_width: Option<u32>,
+    /// The height in points, if the display is scaled.
+    pub scaled_height: Option<u32>,
+}
+
+/// Holds the disk space usage of a mounted filesystem.
+#[derive(Debug, Clone)]
+pub struct DiskSpace {
+    /// The path the filesystem is mounted on, _e.g._ `/home`
+    pub mount_point: PathBuf,
+    /// The type of the filesystem, _e.g._ `ext4`
+    pub fs_type: String,
+    /// The amount of used bytes.
+    pub used: u64,
+    /// The total amount of bytes.
+    pub total: u64,
+}
+
+/// Holds the description of a physical disk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disk {
+    /// The name of the device, _e.g._ `nvme0n1`
+    pub name: String,
+    /// The model of the disk, _e.g._ `Samsung SSD 980 PRO 1TB`
+    pub model: Option<String>,
+    /// The capacity in bytes.
+    pub size: u64,
+    /// Whether the disk is rotational, _i.e._ a hard drive, if known.
+    pub rotational: Option<bool>,
+}
+
+/// Holds the I/O statistics of a disk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskIo {
+    /// The name of the device, _e.g._ `nvme0n1`
+    pub name: String,
+    /// The amount of bytes read since boot.
+    pub bytes_read: u64,
+    /// The amount of bytes written since boot.
+    pub bytes_written: u64,
+}
+
+/// Holds the number of physical cores of each kind of a heterogeneous processor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuCoreLayout {
+    /// The number of performance cores.
+    pub performance: usize,
+    /// The number of efficiency cores.
+    pub efficiency: usize,
 }
 
 /// Holds the possible variants for battery status.
@@ -648,9 +984,18 @@ pub enum ShellKind {
     Default,
 }
 
+/// The IP protocol version to use when querying the public address of the host.
+#[cfg(feature = "public-ip")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
+
 /// The supported package managers whose packages can be extracted.
 pub enum PackageManager {
     Homebrew,
+    HomebrewCask,
     MacPorts,
     Pacman,
     Portage,
@@ -665,15 +1010,19 @@ pub enum PackageManager {
     Flatpak,
     Snap,
     Android,
+    AndroidSystem,
     Pkg,
     Scoop,
     Nix,
+    Winget,
+    Chocolatey,
 }
 
 impl std::fmt::Display for PackageManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             PackageManager::Homebrew => write!(f, "Homebrew"),
+            PackageManager::HomebrewCask => write!(f, "Homebrew Cask"),
             PackageManager::MacPorts => write!(f, "MacPorts"),
             PackageManager::Pacman => write!(f, "pacman"),
             PackageManager::Portage => write!(f, "portage"),
@@ -688,9 +1037,76 @@ impl std::fmt::Display for PackageManager {
             PackageManager::Flatpak => write!(f, "flatpak"),
             PackageManager::Snap => write!(f, "snap"),
             PackageManager::Android => write!(f, "Android"),
+            PackageManager::AndroidSystem => write!(f, "Android (system)"),
             PackageManager::Pkg => write!(f, "pkg"),
             PackageManager::Scoop => write!(f, "Scoop"),
             PackageManager::Nix => write!(f, "nix"),
+            PackageManager::Winget => write!(f, "winget"),
+            PackageManager::Chocolatey => write!(f, "Chocolatey"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeMemoryReadout {
+        used: u64,
+        total: u64,
+    }
+
+    impl MemoryReadout for FakeMemoryReadout {
+        fn new() -> Self {
+            FakeMemoryReadout { used: 0, total: 0 }
+        }
+
+        fn total(&self) -> Result<u64, ReadoutError> {
+            Ok(self.total)
+        }
+
+        fn free(&self) -> Result<u64, ReadoutError> {
+            Ok(self.total - self.used)
         }
+
+        fn buffers(&self) -> Result<u64, ReadoutError> {
+            Err(ReadoutError::NotImplemented)
+        }
+
+        fn cached(&self) -> Result<u64, ReadoutError> {
+            Err(ReadoutError::NotImplemented)
+        }
+
+        fn reclaimable(&self) -> Result<u64, ReadoutError> {
+            Err(ReadoutError::NotImplemented)
+        }
+
+        fn used(&self) -> Result<u64, ReadoutError> {
+            Ok(self.used)
+        }
+
+        fn swap_total(&self) -> Result<u64, ReadoutError> {
+            Ok(self.total)
+        }
+
+        fn swap_free(&self) -> Result<u64, ReadoutError> {
+            Err(ReadoutError::NotImplemented)
+        }
+
+        fn swap_used(&self) -> Result<u64, ReadoutError> {
+            Err(ReadoutError::NotImplemented)
+        }
+    }
+
+    #[test]
+    fn test_perc_used() {
+        let readout = |used, total| FakeMemoryReadout { used, total };
+
+        assert_eq!(readout(1, 3).perc_used().unwrap(), 33);
+        assert_eq!(readout(2, 3).perc_used().unwrap(), 67);
+        assert_eq!(readout(1, 200).perc_used().unwrap(), 1);
+        assert_eq!(readout(16, 8).perc_used().unwrap(), 100);
+        assert!(readout(0, 0).perc_used().is_err());
+        assert!(readout(1, 2).swap_perc_used().is_err());
     }
 }
@@ -7,15 +7,24 @@ use wmi::WMIResult;
 use wmi::{COMLibrary, Variant, WMIConnection};
 
 use windows::{
-    core::PSTR, Win32::System::Power::GetSystemPowerStatus,
-    Win32::System::Power::SYSTEM_POWER_STATUS,
-    Win32::System::SystemInformation::GetComputerNameExA,
+    core::PWSTR, Win32::NetworkManagement::IpHelper::MIB_IF_ROW2,
+    Win32::NetworkManagement::WiFi::WLAN_CONNECTION_ATTRIBUTES,
+    Win32::System::Power::GetSystemPowerStatus, Win32::System::Power::SYSTEM_POWER_STATUS,
+    Win32::System::ProcessStatus::K32GetPerformanceInfo,
+    Win32::System::ProcessStatus::PERFORMANCE_INFORMATION,
+    Win32::System::SystemInformation::GetComputerNameExW,
     Win32::System::SystemInformation::GetTickCount64,
     Win32::System::SystemInformation::GlobalMemoryStatusEx,
     Win32::System::SystemInformation::MEMORYSTATUSEX,
-    Win32::System::WindowsProgramming::GetUserNameA,
+    Win32::System::WindowsProgramming::GetUserNameW,
 };
 
+impl From<sqlite::Error> for ReadoutError {
+    fn from(e: sqlite::Error) -> Self {
+        ReadoutError::Other(e.to_string())
+    }
+}
+
 impl From<wmi::WMIError> for ReadoutError {
     fn from(e: wmi::WMIError) -> Self {
         ReadoutError::Other(e.to_string())
@@ -34,7 +43,7 @@ impl BatteryReadout for WindowsBatteryReadout {
 
         match power_state.BatteryLifePercent {
             s if s != 255 => Ok(s),
-            s => Err(ReadoutError::Warning(format!(
+            s => Err(ReadoutError::MetricUnavailableForHardware(format!(
                 "Windows reported a battery percentage of {s}, which means there is \
                 no battery available. Are you on a desktop system?"
             ))),
@@ -54,7 +63,90 @@ impl BatteryReadout for WindowsBatteryReadout {
     }
 
     fn health(&self) -> Result<u8, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let wmi_con = wmi_connection("ROOT\\WMI")?;
+
+        const WBEM_E_NOT_FOUND: i32 = 0x80041002_u32 as i32;
+        const WBEM_E_INVALID_CLASS: i32 = 0x80041010_u32 as i32;
+
+        // Both classes hold one instance per battery, and neither exists without one.
+        let capacity = |class: &str, property: &str| -> Result<u64, ReadoutError> {
+            let results: Vec<HashMap<String, Variant>> =
+                match wmi_con.raw_query(format!("SELECT {property} FROM {class}")) {
+                    Ok(results) => results,
+                    Err(wmi::WMIError::HResultError { hres })
+                        if hres == WBEM_E_NOT_FOUND || hres == WBEM_E_INVALID_CLASS =>
+                    {
+                        Vec::new()
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+            let capacities: Vec<u64> = results
+                .iter()
+                .filter_map(|battery| match battery.get(property) {
+                    Some(Variant::UI4(capacity)) => Some(*capacity as u64),
+                    _ => None,
+                })
+                .collect();
+
+            if capacities.is_empty() {
+                return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+                    "Windows did not report any battery capacity, which means there is \
+                    no battery available. Are you on a desktop system?",
+                )));
+            }
+
+            Ok(capacities.iter().sum())
+        };
+
+        let design = capacity("BatteryStaticData", "DesignedCapacity")?;
+        let full = capacity("BatteryFullChargedCapacity", "FullChargedCapacity")?;
+
+        if design == 0 {
+            return Err(ReadoutError::Other(
+                "Error calculating battery health.".to_string(),
+            ));
+        }
+
+        Ok((full.min(design) as f64 / design as f64 * 100.0).ceil() as u8)
+    }
+
+    fn time_remaining(&self) -> Result<usize, ReadoutError> {
+        let power_state = WindowsBatteryReadout::get_power_status()?;
+
+        if power_state.BatteryLifePercent == 255 {
+            return Err(ReadoutError::MetricUnavailableForHardware(String::from(
+                "Windows reported a battery percentage of 255, which means there is \
+                no battery available. Are you on a desktop system?",
+            )));
+        }
+
+        // Windows reports u32::MAX when the remaining time is unknown,
+        // e.g. when the battery is charging.
+        match power_state.BatteryLifeTime {
+            u32::MAX => Err(ReadoutError::MetricNotAvailable),
+            s => Ok(s as usize),
+        }
+    }
+
+    fn temperature(&self) -> Result<f32, ReadoutError> {
+        let wmi_con = wmi_connection("ROOT\\WMI")?;
+
+        // Only a handful of battery drivers implement this class, querying it fails otherwise.
+        let results: Vec<HashMap<String, Variant>> = wmi_con
+            .raw_query("SELECT CurrentTemperature FROM BatteryTemperature")
+            .map_err(|_| ReadoutError::MetricNotAvailable)?;
+
+        // The temperature is expressed in tenths of a kelvin.
+        match results
+            .first()
+            .and_then(|battery| battery.get("CurrentTemperature"))
+        {
+            Some(Variant::UI4(temperature)) if *temperature != 0 => {
+                Ok(*temperature as f32 / 10.0 - 273.15)
+            }
+            _ => Err(ReadoutError::MetricNotAvailable),
+        }
     }
 }
 
@@ -80,13 +172,7 @@ impl KernelReadout for WindowsKernelReadout {
     }
 
     fn os_release(&self) -> Result<String, ReadoutError> {
-        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-        let current_windows_not =
-            hklm.open_subkey("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion")?;
-
-        let nt_build: String = current_windows_not.get_value("CurrentBuild")?;
-
-        Ok(nt_build)
+        Ok(WindowsVersion::current()?.build_string())
     }
 
     fn os_type(&self) -> Result<String, ReadoutError> {
@@ -96,6 +182,116 @@ impl KernelReadout for WindowsKernelReadout {
     fn pretty_kernel(&self) -> Result<String, ReadoutError> {
         Ok(format!("{} {}", self.os_type()?, self.os_release()?))
     }
+
+    fn architecture(&self) -> Result<String, ReadoutError> {
+        use windows::Win32::System::Diagnostics::Debug::{
+            PROCESSOR_ARCHITECTURE_AMD64, PROCESSOR_ARCHITECTURE_ARM, PROCESSOR_ARCHITECTURE_INTEL,
+        };
+        use windows::Win32::System::SystemInformation::{
+            GetNativeSystemInfo, IMAGE_FILE_MACHINE, IMAGE_FILE_MACHINE_AMD64,
+            IMAGE_FILE_MACHINE_ARM64, IMAGE_FILE_MACHINE_ARMNT, IMAGE_FILE_MACHINE_I386,
+            SYSTEM_INFO,
+        };
+        use windows::Win32::System::SystemServices::PROCESSOR_ARCHITECTURE_ARM64;
+        use windows::Win32::System::Threading::{GetCurrentProcess, IsWow64Process2};
+
+        // IsWow64Process2 sees through the emulation layer of ARM64 hosts, unlike
+        // GetNativeSystemInfo, which reports an x86 host to x86 processes running on ARM64.
+        let mut process_machine = IMAGE_FILE_MACHINE::default();
+        let mut native_machine = IMAGE_FILE_MACHINE::default();
+        if unsafe {
+            IsWow64Process2(
+                GetCurrentProcess(),
+                &mut process_machine,
+                &mut native_machine,
+            )
+        }
+        .as_bool()
+        {
+            match native_machine {
+                IMAGE_FILE_MACHINE_AMD64 => return Ok(String::from("x86_64")),
+                IMAGE_FILE_MACHINE_ARM64 => return Ok(String::from("aarch64")),
+                IMAGE_FILE_MACHINE_ARMNT => return Ok(String::from("arm")),
+                IMAGE_FILE_MACHINE_I386 => return Ok(String::from("x86")),
+                _ => (),
+            }
+        }
+
+        let mut system_info = SYSTEM_INFO::default();
+        unsafe { GetNativeSystemInfo(&mut system_info) };
+
+        match unsafe { system_info.Anonymous.Anonymous.wProcessorArchitecture } {
+            PROCESSOR_ARCHITECTURE_AMD64 => Ok(String::from("x86_64")),
+            PROCESSOR_ARCHITECTURE_ARM => Ok(String::from("arm")),
+            PROCESSOR_ARCHITECTURE_INTEL => Ok(String::from("x86")),
+            arch if arch.0 as u32 == PROCESSOR_ARCHITECTURE_ARM64 => Ok(String::from("aarch64")),
+            _ => Err(ReadoutError::MetricNotAvailable),
+        }
+    }
+}
+
+/// The version of the running system, as recorded under the `CurrentVersion` registry key.
+struct WindowsVersion {
+    build: String,
+    /// The feature update, _e.g._ `23H2`, which older builds only record as `ReleaseId`, _e.g._
+    /// `1909`.
+    display_version: Option<String>,
+    /// The update build revision, which is bumped by every cumulative update.
+    ubr: Option<u32>,
+}
+
+impl WindowsVersion {
+    fn current() -> Result<Self, ReadoutError> {
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let current_version =
+            hklm.open_subkey("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion")?;
+
+        let display_version = current_version
+            .get_value::<String, _>("DisplayVersion")
+            .or_else(|_| current_version.get_value::<String, _>("ReleaseId"))
+            .ok()
+            .filter(|version| !version.is_empty());
+
+        Ok(WindowsVersion {
+            build: current_version.get_value("CurrentBuild")?,
+            display_version,
+            ubr: current_version.get_value("UBR").ok(),
+        })
+    }
+
+    /// Returns the build number along with its revision, _e.g._ `22631.3593`.
+    fn build_string(&self) -> String {
+        match self.ubr {
+            Some(ubr) => format!("{}.{ubr}", self.build),
+            None => self.build.to_owned(),
+        }
+    }
+
+    /// Composes a name such as `Windows 11 Pro 23H2 (build 22631.3593)` from the caption
+    /// reported by WMI, _e.g._ `Microsoft Windows 11 Pro`.
+    fn name(&self, caption: &str) -> String {
+        let caption = caption.trim();
+        let mut name = caption
+            .strip_prefix("Microsoft ")
+            .unwrap_or(caption)
+            .to_owned();
+
+        // Early builds of Windows 11 still call themselves Windows 10, which the build number
+        // gives away.
+        if self
+            .build
+            .parse::<u32>()
+            .map_or(false, |build| build >= 22000)
+        {
+            name = name.replacen("Windows 10", "Windows 11", 1);
+        }
+
+        if let Some(display_version) = &self.display_version {
+            name = format!("{name} {display_version}");
+        }
+
+        format!("{name} (build {})", self.build_string())
+    }
 }
 
 pub struct WindowsMemoryReadout;
@@ -111,19 +307,22 @@ impl MemoryReadout for WindowsMemoryReadout {
     }
 
     fn free(&self) -> Result<u64, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let memory_status = WindowsMemoryReadout::get_memory_status()?;
+        Ok(memory_status.ullAvailPhys / 1024u64)
     }
 
     fn buffers(&self) -> Result<u64, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        // Windows doesn't keep a separate buffer cache.
+        Err(ReadoutError::MetricNotAvailable)
     }
 
     fn cached(&self) -> Result<u64, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let performance_info = WindowsMemoryReadout::get_performance_info()?;
+        Ok((performance_info.SystemCache as u64 * performance_info.PageSize as u64) / 1024u64)
     }
 
     fn reclaimable(&self) -> Result<u64, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        Err(ReadoutError::MetricNotAvailable)
     }
 
     fn used(&self) -> Result<u64, ReadoutError> {
@@ -132,15 +331,18 @@ impl MemoryReadout for WindowsMemoryReadout {
     }
 
     fn swap_total(&self) -> Result<u64, ReadoutError> {
-        return Err(ReadoutError::NotImplemented);
+        let (total, _) = WindowsMemoryReadout::get_pagefile()?;
+        Ok(total / 1024u64)
     }
 
     fn swap_free(&self) -> Result<u64, ReadoutError> {
-        return Err(ReadoutError::NotImplemented);
+        let (_, free) = WindowsMemoryReadout::get_pagefile()?;
+        Ok(free / 1024u64)
     }
 
     fn swap_used(&self) -> Result<u64, ReadoutError> {
-        return Err(ReadoutError::NotImplemented);
+        let (total, free) = WindowsMemoryReadout::get_pagefile()?;
+        Ok((total - free) / 1024u64)
     }
 }
 
@@ -159,15 +361,129 @@ impl WindowsMemoryReadout {
 
         Ok(memory_status)
     }
+
+    /// Returns the total and free size of the pagefile(s), in bytes.
+    fn get_pagefile() -> Result<(u64, u64), ReadoutError> {
+        let memory_status = WindowsMemoryReadout::get_memory_status()?;
+
+        // The commit limit reported as the "page file" includes physical memory,
+        // and systems without a pagefile may report less than the latter.
+        let total = memory_status
+            .ullTotalPageFile
+            .saturating_sub(memory_status.ullTotalPhys);
+        let free = memory_status
+            .ullAvailPageFile
+            .saturating_sub(memory_status.ullAvailPhys)
+            .min(total);
+
+        Ok((total, free))
+    }
+
+    fn get_performance_info() -> Result<PERFORMANCE_INFORMATION, ReadoutError> {
+        let mut performance_info = PERFORMANCE_INFORMATION::default();
+        let size = std::mem::size_of_val(&performance_info) as u32;
+        performance_info.cb = size;
+
+        if !unsafe { K32GetPerformanceInfo(&mut performance_info, size) }.as_bool() {
+            return Err(ReadoutError::Other(String::from(
+                "K32GetPerformanceInfo returned a zero return code.",
+            )));
+        }
+
+        Ok(performance_info)
+    }
 }
 
 thread_local! {
     static COM_LIB: COMLibrary = COMLibrary::new().unwrap();
 }
 
-fn wmi_connection() -> WMIResult<WMIConnection> {
+/// Connects to the given WMI namespace, _e.g._ `ROOT\CIMV2`, which holds the `Win32_*`
+/// classes, or `ROOT\WMI`, which holds the classes provided by drivers.
+fn wmi_connection(namespace: &str) -> WMIResult<WMIConnection> {
     let com_lib = COM_LIB.with(|com| *com);
-    WMIConnection::new(com_lib)
+    WMIConnection::with_namespace_path(namespace, com_lib)
+}
+
+/// Converts a buffer filled in by a wide-char API to a string, dropping the null terminator
+/// and anything that follows it.
+fn wide_to_string(buffer: &[u16]) -> String {
+    let end = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..end])
+}
+
+/// The processes that were running when the snapshot was taken, as reported by the Toolhelp
+/// API, mapping their PID to that of their parent and to the name of their executable.
+struct ProcessSnapshot(HashMap<u32, (u32, String)>);
+
+impl ProcessSnapshot {
+    fn new() -> Result<Self, ReadoutError> {
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::Diagnostics::ToolHelp::{
+            CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+            TH32CS_SNAPPROCESS,
+        };
+
+        let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }
+            .map_err(|e| ReadoutError::Other(e.to_string()))?;
+
+        let mut processes = HashMap::new();
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+
+        let mut found = unsafe { Process32FirstW(snapshot, &mut entry) }.as_bool();
+        while found {
+            let name = String::from_utf16_lossy(&entry.szExeFile);
+            processes.insert(
+                entry.th32ProcessID,
+                (
+                    entry.th32ParentProcessID,
+                    name.trim_end_matches('\0').to_string(),
+                ),
+            );
+
+            found = unsafe { Process32NextW(snapshot, &mut entry) }.as_bool();
+        }
+
+        unsafe { CloseHandle(snapshot) };
+
+        Ok(ProcessSnapshot(processes))
+    }
+
+    /// Returns whether a process running the given executable, _e.g._ `komorebi.exe`, exists.
+    fn is_running(&self, executable: &str) -> bool {
+        self.0
+            .values()
+            .any(|(_, name)| name.eq_ignore_ascii_case(executable))
+    }
+
+    /// Returns the executable names of the ancestors of the current process, starting with
+    /// its parent.
+    fn ancestors(&self) -> Vec<&str> {
+        let mut ancestors = Vec::new();
+        let mut visited = vec![std::process::id()];
+        let mut pid = std::process::id();
+
+        // The walk stops at processes that have already exited, and PIDs are only visited once
+        // since the parent of an exited process may have been reused by one of its descendants.
+        while let Some((parent, _)) = self.0.get(&pid) {
+            if visited.contains(parent) {
+                break;
+            }
+
+            let Some((_, name)) = self.0.get(parent) else {
+                break;
+            };
+
+            visited.push(*parent);
+            ancestors.push(name.as_str());
+            pid = *parent;
+        }
+
+        ancestors
+    }
 }
 
 pub struct WindowsGeneralReadout;
@@ -178,47 +494,140 @@ impl GeneralReadout for WindowsGeneralReadout {
     }
 
     fn backlight(&self) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let wmi_con = wmi_connection("ROOT\\WMI")?;
+
+        // Only internal panels expose this class, querying it fails otherwise.
+        let results: Vec<HashMap<String, Variant>> = wmi_con
+            .raw_query("SELECT CurrentBrightness FROM WmiMonitorBrightness WHERE Active = TRUE")
+            .map_err(|_| ReadoutError::MetricNotAvailable)?;
+
+        match results
+            .first()
+            .and_then(|monitor| monitor.get("CurrentBrightness"))
+        {
+            Some(Variant::UI1(brightness)) => Ok((*brightness as usize).min(100)),
+            _ => Err(ReadoutError::MetricNotAvailable),
+        }
     }
 
     fn resolution(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let output: Vec<String> = self
+            .displays()?
+            .iter()
+            .map(|display| {
+                let mut mode = format!("{}x{}", display.width, display.height);
+
+                if let Some(refresh_rate) = display.refresh_rate {
+                    mode = format!("{mode}@{refresh_rate}Hz");
+                }
+
+                if let (Some(scaled_width), Some(scaled_height)) =
+                    (display.scaled_width, display.scaled_height)
+                {
+                    mode = format!("{mode} (as {scaled_width}x{scaled_height})");
+                }
+
+                match &display.name {
+                    Some(name) => format!("{name}: {mode}"),
+                    None => mode,
+                }
+            })
+            .collect();
+
+        Ok(output.join(", "))
+    }
+
+    fn displays(&self) -> Result<Vec<Display>, ReadoutError> {
+        use windows::core::PCWSTR;
+        use windows::Win32::Graphics::Gdi::{
+            EnumDisplayDevicesW, EnumDisplaySettingsW, DEVMODEW, DISPLAY_DEVICEW,
+            DISPLAY_DEVICE_ATTACHED_TO_DESKTOP, ENUM_CURRENT_SETTINGS,
+        };
+
+        let mut displays: Vec<Display> = Vec::new();
+        let monitor_dpis = WindowsGeneralReadout::monitor_dpis();
+
+        for index in 0.. {
+            let mut device = DISPLAY_DEVICEW {
+                cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+                ..Default::default()
+            };
+
+            if !unsafe { EnumDisplayDevicesW(PCWSTR::null(), index, &mut device, 0) }.as_bool() {
+                break;
+            }
+
+            if device.StateFlags & DISPLAY_DEVICE_ATTACHED_TO_DESKTOP == 0 {
+                continue;
+            }
+
+            let mut mode = DEVMODEW {
+                dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+                ..Default::default()
+            };
+
+            if !unsafe {
+                EnumDisplaySettingsW(
+                    PCWSTR(device.DeviceName.as_ptr()),
+                    ENUM_CURRENT_SETTINGS,
+                    &mut mode,
+                )
+            }
+            .as_bool()
+            {
+                continue;
+            }
+
+            // A frequency of 0 or 1 means that the hardware's default is used.
+            let refresh_rate = match mode.dmDisplayFrequency {
+                0 | 1 => None,
+                frequency => Some(frequency as f32),
+            };
+
+            // Device names look like "\\.\DISPLAY1".
+            let device_name = wide_to_string(&device.DeviceName);
+
+            // The logical size is derived from the effective DPI of the monitor the device
+            // drives, 96 being the DPI of a display that isn't scaled.
+            let (scaled_width, scaled_height) = match monitor_dpis.get(&device_name) {
+                Some(&dpi) if dpi != 0 && dpi != 96 => (
+                    Some(mode.dmPelsWidth * 96 / dpi),
+                    Some(mode.dmPelsHeight * 96 / dpi),
+                ),
+                _ => (None, None),
+            };
+
+            displays.push(Display {
+                name: Some(device_name.trim_start_matches("\\\\.\\").to_string()),
+                width: mode.dmPelsWidth,
+                height: mode.dmPelsHeight,
+                refresh_rate,
+                scaled_width,
+                scaled_height,
+            });
+        }
+
+        Ok(displays)
     }
 
     fn username(&self) -> Result<String, ReadoutError> {
         let mut size = 0;
-        unsafe { GetUserNameA(PSTR(std::ptr::null_mut()), &mut size) };
+        unsafe { GetUserNameW(PWSTR(std::ptr::null_mut()), &mut size) };
 
         if size == 0 {
             return Err(ReadoutError::Other(
-                "Call to \"GetUserNameA\" failed.".to_string(),
+                "Call to \"GetUserNameW\" failed.".to_string(),
             ));
         }
 
-        let mut username = Vec::with_capacity(size as usize);
-        if !unsafe { GetUserNameA(PSTR(username.as_mut_ptr()), &mut size) }.as_bool() {
+        let mut username = vec![0u16; size as usize];
+        if !unsafe { GetUserNameW(PWSTR(username.as_mut_ptr()), &mut size) }.as_bool() {
             return Err(ReadoutError::Other(
-                "Call to \"GetUserNameA\" failed.".to_string(),
+                "Call to \"GetUserNameW\" failed.".to_string(),
             ));
         }
 
-        unsafe {
-            username.set_len(size as usize);
-        }
-
-        let mut str = match String::from_utf8(username) {
-            Ok(str) => str,
-            Err(e) => {
-                return Err(ReadoutError::Other(format!(
-                    "String from \"GetUserNameA\" \
-            was not valid UTF-8: {e}"
-                )))
-            }
-        };
-
-        str.pop(); //remove null terminator from string.
-
-        Ok(str)
+        Ok(wide_to_string(&username))
     }
 
     fn hostname(&self) -> Result<String, ReadoutError> {
@@ -226,46 +635,35 @@ impl GeneralReadout for WindowsGeneralReadout {
 
         let mut size = 0;
         unsafe {
-            GetComputerNameExA(
+            GetComputerNameExW(
                 ComputerNameDnsHostname,
-                PSTR(std::ptr::null_mut()),
+                PWSTR(std::ptr::null_mut()),
                 &mut size,
             )
         };
 
         if size == 0 {
             return Err(ReadoutError::Other(String::from(
-                "Call to \"GetComputerNameExA\" failed.",
+                "Call to \"GetComputerNameExW\" failed.",
             )));
         }
 
-        let mut hostname = Vec::with_capacity(size as usize);
-        if unsafe {
-            GetComputerNameExA(
+        let mut hostname = vec![0u16; size as usize];
+        if !unsafe {
+            GetComputerNameExW(
                 ComputerNameDnsHostname,
-                PSTR(hostname.as_mut_ptr()),
+                PWSTR(hostname.as_mut_ptr()),
                 &mut size,
             )
-        } == false
+        }
+        .as_bool()
         {
             return Err(ReadoutError::Other(String::from(
-                "Call to \"GetComputerNameExA\" failed.",
+                "Call to \"GetComputerNameExW\" failed.",
             )));
         }
 
-        unsafe { hostname.set_len(size as usize) };
-
-        let str = match String::from_utf8(hostname) {
-            Ok(str) => str,
-            Err(e) => {
-                return Err(ReadoutError::Other(format!(
-                    "String from \"GetComputerNameExA\" \
-            was not valid UTF-8: {e}"
-                )))
-            }
-        };
-
-        Ok(str)
+        Ok(wide_to_string(&hostname))
     }
 
     fn distribution(&self) -> Result<String, ReadoutError> {
@@ -273,7 +671,38 @@ impl GeneralReadout for WindowsGeneralReadout {
     }
 
     fn desktop_environment(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        const WINLOGON: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Winlogon";
+
+        // A per-user shell takes precedence over the system-wide one.
+        let shell: String = [HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE]
+            .into_iter()
+            .find_map(|hkey| {
+                let winlogon = RegKey::predef(hkey).open_subkey(WINLOGON).ok()?;
+                winlogon
+                    .get_value::<String, _>("Shell")
+                    .ok()
+                    .filter(|shell| !shell.trim().is_empty())
+            })
+            .unwrap_or_else(|| String::from("explorer.exe"));
+
+        // The shell is a command line, e.g. "C:\Program Files\Cairo Shell\CairoDesktop.exe".
+        let executable = match shell.trim().strip_prefix('"') {
+            Some(quoted) => quoted.split('"').next().unwrap_or_default(),
+            None => shell.split_whitespace().next().unwrap_or_default(),
+        };
+
+        let name = Path::new(executable)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        match name.to_ascii_lowercase().as_str() {
+            "" => Err(ReadoutError::MetricNotAvailable),
+            "explorer" => Ok(String::from("Explorer")),
+            "cairodesktop" => Ok(String::from("Cairo")),
+            "litestep" => Ok(String::from("LiteStep")),
+            _ => Ok(name),
+        }
     }
 
     fn session(&self) -> Result<String, ReadoutError> {
@@ -281,11 +710,54 @@ impl GeneralReadout for WindowsGeneralReadout {
     }
 
     fn window_manager(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        use windows::Win32::Graphics::Dwm::DwmIsCompositionEnabled;
+
+        // Composition is always enabled since Windows 8, but may be disabled on Windows 7.
+        let mut window_manager = match unsafe { DwmIsCompositionEnabled() } {
+            Ok(enabled) if enabled.as_bool() => String::from("Desktop Window Manager"),
+            _ => String::from("Windows Explorer"),
+        };
+
+        // Tiling window managers run on top of the compositor.
+        if let Ok(snapshot) = ProcessSnapshot::new() {
+            for (executable, name) in [("komorebi.exe", "komorebi"), ("glazewm.exe", "GlazeWM")] {
+                if snapshot.is_running(executable) {
+                    window_manager.push_str(" + ");
+                    window_manager.push_str(name);
+                }
+            }
+        }
+
+        Ok(window_manager)
     }
 
     fn terminal(&self) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        if std::env::var_os("WT_SESSION").is_some() {
+            return Ok(String::from("Windows Terminal"));
+        }
+
+        // Set by Visual Studio Code and some terminal emulators, e.g. WezTerm.
+        if let Some(program) = std::env::var("TERM_PROGRAM").ok().filter(|p| !p.is_empty()) {
+            return Ok(program);
+        }
+
+        if std::env::var_os("ConEmuANSI").is_some() {
+            return Ok(String::from("ConEmu"));
+        }
+
+        ProcessSnapshot::new()?
+            .ancestors()
+            .into_iter()
+            .find_map(|name| match name.to_ascii_lowercase().as_str() {
+                "windowsterminal.exe" => Some("Windows Terminal"),
+                "alacritty.exe" => Some("Alacritty"),
+                "wezterm-gui.exe" => Some("WezTerm"),
+                "mintty.exe" => Some("mintty"),
+                "conhost.exe" => Some("Windows Console"),
+                _ => None,
+            })
+            .map(String::from)
+            .ok_or(ReadoutError::MetricNotAvailable)
     }
 
     fn shell(&self, _shorthand: ShellFormat, _: ShellKind) -> Result<String, ReadoutError> {
@@ -302,16 +774,120 @@ impl GeneralReadout for WindowsGeneralReadout {
         Ok(processor_name)
     }
 
+    fn cpu_vendor(&self) -> Result<String, ReadoutError> {
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let central_processor =
+            hklm.open_subkey("HARDWARE\\DESCRIPTION\\System\\CentralProcessor\\0")?;
+
+        let vendor_identifier: String = central_processor.get_value("VendorIdentifier")?;
+
+        Ok(crate::shared::cpu_vendor_name(&vendor_identifier))
+    }
+
     fn cpu_usage(&self) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let first = CpuTimes::now()?;
+
+        // The usage is averaged over a short interval, which is as long as this call blocks.
+        std::thread::sleep(std::time::Duration::from_millis(200));
+
+        first
+            .usage_until(&CpuTimes::now()?)
+            .ok_or(ReadoutError::MetricNotAvailable)
     }
 
     fn cpu_physical_cores(&self) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        use windows::Win32::Foundation::{GetLastError, ERROR_INSUFFICIENT_BUFFER};
+        use windows::Win32::System::SystemInformation::{
+            GetLogicalProcessorInformationEx, RelationProcessorCore,
+            SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX,
+        };
+
+        let error = || {
+            ReadoutError::Other(String::from(
+                "Call to \"GetLogicalProcessorInformationEx\" failed.",
+            ))
+        };
+
+        // The first call only fills in the required buffer size.
+        let mut length = 0u32;
+        unsafe {
+            GetLogicalProcessorInformationEx(
+                RelationProcessorCore,
+                std::ptr::null_mut(),
+                &mut length,
+            )
+        };
+
+        // The buffer is made out of u64s so that it is suitably aligned for the structures it
+        // will hold.
+        let mut buffer: Vec<u64>;
+
+        loop {
+            buffer = vec![0; length as usize / 8 + 1];
+            length = (buffer.len() * 8) as u32;
+
+            if unsafe {
+                GetLogicalProcessorInformationEx(
+                    RelationProcessorCore,
+                    buffer.as_mut_ptr() as *mut SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX,
+                    &mut length,
+                )
+            }
+            .as_bool()
+            {
+                break;
+            }
+
+            if unsafe { GetLastError() } != ERROR_INSUFFICIENT_BUFFER {
+                return Err(error());
+            }
+        }
+
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                buffer.as_ptr() as *const u8,
+                (length as usize).min(buffer.len() * 8),
+            )
+        };
+
+        // Records are variable-length, starting with their relationship and size. Each of them
+        // describes a single physical core, regardless of its kind on hybrid processors.
+        let mut offset = 0;
+        let mut cores = 0;
+
+        while let Some(header) = bytes.get(offset..offset + 8) {
+            let relationship = i32::from_ne_bytes([header[0], header[1], header[2], header[3]]);
+            let size = u32::from_ne_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+            if size == 0 {
+                break;
+            }
+
+            if relationship == RelationProcessorCore.0 {
+                cores += 1;
+            }
+
+            offset += size;
+        }
+
+        match cores {
+            0 => Err(error()),
+            cores => Ok(cores),
+        }
     }
 
     fn cpu_cores(&self) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        use windows::Win32::System::SystemServices::ALL_PROCESSOR_GROUPS;
+        use windows::Win32::System::Threading::GetActiveProcessorCount;
+
+        // Unlike std::thread::available_parallelism(), this counts the processors of every
+        // processor group rather than only those of the current one.
+        match unsafe { GetActiveProcessorCount(ALL_PROCESSOR_GROUPS as u16) } {
+            0 => Err(ReadoutError::Other(String::from(
+                "Call to \"GetActiveProcessorCount\" failed.",
+            ))),
+            count => Ok(count as usize),
+        }
     }
 
     fn uptime(&self) -> Result<usize, ReadoutError> {
@@ -332,14 +908,17 @@ impl GeneralReadout for WindowsGeneralReadout {
     }
 
     fn os_name(&self) -> Result<String, ReadoutError> {
-        let wmi_con = wmi_connection()?;
+        let wmi_con = wmi_connection("ROOT\\CIMV2")?;
 
         let results: Vec<HashMap<String, Variant>> =
             wmi_con.raw_query("SELECT Caption FROM Win32_OperatingSystem")?;
 
         if let Some(os) = results.first() {
             if let Some(Variant::String(caption)) = os.get("Caption") {
-                return Ok(caption.to_string());
+                return match WindowsVersion::current() {
+                    Ok(version) => Ok(version.name(caption)),
+                    Err(_) => Ok(caption.to_string()),
+                };
             }
         }
 
@@ -351,11 +930,257 @@ impl GeneralReadout for WindowsGeneralReadout {
     }
 
     fn disk_space(&self, path: &Path) -> Result<(u64, u64), ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        use windows::core::HSTRING;
+        use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+        if !path.exists() {
+            return Err(ReadoutError::Other(format!(
+                "\"{}\" does not exist.",
+                path.display()
+            )));
+        }
+
+        // Only directories can be queried, and the root of a UNC share, e.g.
+        // "\\server\share\", must end with a backslash.
+        let mut directory = if path.is_dir() {
+            path.as_os_str().to_owned()
+        } else {
+            path.parent().unwrap_or(path).as_os_str().to_owned()
+        };
+
+        if !directory.to_string_lossy().ends_with(['\\', '/']) {
+            directory.push("\\");
+        }
+
+        let (mut total, mut free) = (0u64, 0u64);
+
+        if !unsafe {
+            GetDiskFreeSpaceExW(
+                &HSTRING::from(directory.as_os_str()),
+                std::ptr::null_mut(),
+                &mut total,
+                &mut free,
+            )
+        }
+        .as_bool()
+        {
+            return Err(ReadoutError::Other(format!(
+                "Could not query the disk space of \"{}\": {}",
+                path.display(),
+                std::io::Error::last_os_error()
+            )));
+        }
+
+        Ok((total.saturating_sub(free), total))
+    }
+
+    fn disks(&self) -> Result<Vec<Disk>, ReadoutError> {
+        let wmi_con = wmi_connection("ROOT\\CIMV2")?;
+
+        let results: Vec<HashMap<String, Variant>> =
+            wmi_con.raw_query("SELECT DeviceID, Model, Size FROM Win32_DiskDrive")?;
+
+        let disks: Vec<Disk> = results
+            .iter()
+            .filter_map(|drive| {
+                // e.g. \\.\PHYSICALDRIVE0
+                let name = match drive.get("DeviceID") {
+                    Some(Variant::String(device_id)) => device_id.to_string(),
+                    _ => return None,
+                };
+
+                // Size is a uint64, which WMI hands out as a string.
+                let size = match drive.get("Size") {
+                    Some(Variant::String(size)) => size.parse::<u64>().ok()?,
+                    Some(Variant::UI8(size)) => *size,
+                    _ => return None,
+                };
+
+                let model = match drive.get("Model") {
+                    Some(Variant::String(model)) => Some(model.trim().to_string()),
+                    _ => None,
+                };
+
+                Some(Disk {
+                    name,
+                    model,
+                    size,
+                    rotational: None,
+                })
+            })
+            .collect();
+
+        if disks.is_empty() {
+            Err(ReadoutError::MetricNotAvailable)
+        } else {
+            Ok(disks)
+        }
     }
 
     fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+        let wmi_con = wmi_connection("ROOT\\CIMV2")?;
+
+        let results: Vec<HashMap<String, Variant>> =
+            wmi_con.raw_query("SELECT Name, AdapterRAM FROM Win32_VideoController")?;
+
+        let mut gpus: Vec<String> = results
+            .iter()
+            .filter_map(|adapter| match adapter.get("Name") {
+                Some(Variant::String(name)) => Some(name.trim().to_string()),
+                _ => None,
+            })
+            .filter(|name| !name.is_empty() && name != "Microsoft Remote Display Adapter")
+            .collect();
+
+        // The basic display adapter stands in for a real GPU whenever no
+        // driver is loaded for it, e.g. during an RDP session.
+        if gpus
+            .iter()
+            .any(|name| name != "Microsoft Basic Display Adapter")
+        {
+            gpus.retain(|name| name != "Microsoft Basic Display Adapter");
+        }
+
+        if gpus.is_empty() {
+            Err(ReadoutError::MetricNotAvailable)
+        } else {
+            Ok(gpus)
+        }
+    }
+
+    fn locale(&self) -> Result<String, ReadoutError> {
+        use windows::Win32::Globalization::GetUserDefaultLocaleName;
+        use windows::Win32::System::SystemServices::LOCALE_NAME_MAX_LENGTH;
+
+        let mut locale = [0u16; LOCALE_NAME_MAX_LENGTH as usize];
+        let size = unsafe { GetUserDefaultLocaleName(&mut locale) };
+
+        if size == 0 {
+            return Err(ReadoutError::Other(String::from(
+                "Call to \"GetUserDefaultLocaleName\" failed.",
+            )));
+        }
+
+        // The returned size includes the null terminator.
+        Ok(String::from_utf16_lossy(&locale[..size as usize - 1]))
+    }
+}
+
+impl WindowsGeneralReadout {
+    /// Returns the effective DPI of every monitor, keyed by the name of the display device
+    /// driving it, _e.g._ `\\.\DISPLAY1`.
+    fn monitor_dpis() -> HashMap<String, u32> {
+        use windows::Win32::Foundation::{BOOL, LPARAM, RECT};
+        use windows::Win32::Graphics::Gdi::{
+            EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+        };
+        use windows::Win32::UI::HiDpi::{
+            GetDpiForMonitor, SetThreadDpiAwarenessContext,
+            DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, MDT_EFFECTIVE_DPI,
+        };
+
+        unsafe extern "system" fn callback(
+            monitor: HMONITOR,
+            _: HDC,
+            _: *mut RECT,
+            data: LPARAM,
+        ) -> BOOL {
+            let dpis = &mut *(data.0 as *mut HashMap<String, u32>);
+
+            let mut info = MONITORINFOEXW::default();
+            info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+            if !GetMonitorInfoW(
+                monitor,
+                &mut info as *mut MONITORINFOEXW as *mut MONITORINFO,
+            )
+            .as_bool()
+            {
+                return true.into();
+            }
+
+            let (mut dpi_x, mut dpi_y) = (0, 0);
+            if GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y).is_ok() {
+                dpis.insert(wide_to_string(&info.szDevice), dpi_x);
+            }
+
+            true.into()
+        }
+
+        let mut dpis: HashMap<String, u32> = HashMap::new();
+
+        // Unless the thread is per-monitor DPI aware, every monitor is reported with the DPI of
+        // the primary one.
+        let previous_context =
+            unsafe { SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) };
+
+        unsafe {
+            EnumDisplayMonitors(
+                HDC::default(),
+                std::ptr::null(),
+                Some(callback),
+                LPARAM(&mut dpis as *mut HashMap<String, u32> as isize),
+            )
+        };
+
+        // A null context is returned by versions of Windows that predate it.
+        if previous_context.0 != 0 {
+            unsafe { SetThreadDpiAwarenessContext(previous_context) };
+        }
+
+        dpis
+    }
+
+    /// Returns the average CPU usage since boot, as a percentage.
+    ///
+    /// Unlike `cpu_usage()`, this doesn't block while sampling the usage.
+    pub fn cpu_usage_since_boot(&self) -> Result<usize, ReadoutError> {
+        CpuTimes { busy: 0, total: 0 }
+            .usage_until(&CpuTimes::now()?)
+            .ok_or(ReadoutError::MetricNotAvailable)
+    }
+}
+
+/// The time spent by all processors since boot, as reported by `GetSystemTimes`, in
+/// 100-nanosecond intervals.
+struct CpuTimes {
+    busy: u64,
+    total: u64,
+}
+
+impl CpuTimes {
+    fn now() -> Result<Self, ReadoutError> {
+        use windows::Win32::Foundation::FILETIME;
+        use windows::Win32::System::Threading::GetSystemTimes;
+
+        let (mut idle, mut kernel, mut user) = Default::default();
+
+        if !unsafe { GetSystemTimes(&mut idle, &mut kernel, &mut user) }.as_bool() {
+            return Err(ReadoutError::Other(String::from(
+                "Call to \"GetSystemTimes\" failed.",
+            )));
+        }
+
+        let ticks = |time: FILETIME| (time.dwHighDateTime as u64) << 32 | time.dwLowDateTime as u64;
+
+        // The kernel time includes the idle time.
+        let total = ticks(kernel) + ticks(user);
+
+        Ok(CpuTimes {
+            busy: total.saturating_sub(ticks(idle)),
+            total,
+        })
+    }
+
+    /// Returns the CPU usage, as a percentage, between this snapshot and a later one.
+    fn usage_until(&self, later: &CpuTimes) -> Option<usize> {
+        let total = later.total.checked_sub(self.total)?;
+        let busy = later.busy.saturating_sub(self.busy);
+
+        if total == 0 {
+            return None;
+        }
+
+        Some(((busy as f64 / total as f64 * 100.0).round() as usize).min(100))
     }
 }
 
@@ -413,6 +1238,9 @@ impl PackageReadout for WindowsPackageReadout {
 
     /// Returns the __number of installed packages__ for the following package managers:
     /// - cargo
+    /// - scoop
+    /// - winget
+    /// - chocolatey
     fn count_pkgs(&self) -> Vec<(PackageManager, usize)> {
         let mut packages = Vec::new();
         if let Some(c) = WindowsPackageReadout::count_cargo() {
@@ -421,6 +1249,12 @@ impl PackageReadout for WindowsPackageReadout {
         if let Some(c) = WindowsPackageReadout::count_scoop() {
             packages.push((PackageManager::Scoop, c));
         }
+        if let Some(c) = WindowsPackageReadout::count_winget() {
+            packages.push((PackageManager::Winget, c));
+        }
+        if let Some(c) = WindowsPackageReadout::count_chocolatey() {
+            packages.push((PackageManager::Chocolatey, c));
+        }
         packages
     }
 }
@@ -440,29 +1274,127 @@ impl WindowsPackageReadout {
             _ => None,
         }
     }
+
+    fn count_chocolatey() -> Option<usize> {
+        use std::os::windows::fs::MetadataExt;
+        use windows::Win32::Storage::FileSystem::FILE_ATTRIBUTE_HIDDEN;
+
+        let chocolatey = match std::env::var("ChocolateyInstall") {
+            Ok(chocolatey_var) => PathBuf::from(chocolatey_var),
+            _ => PathBuf::from("C:\\ProgramData\\chocolatey"),
+        };
+
+        let count = chocolatey
+            .join("lib")
+            .read_dir()
+            .ok()?
+            .flatten()
+            .filter(|entry| match entry.metadata() {
+                Ok(metadata) => {
+                    metadata.is_dir() && metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN.0 == 0
+                }
+                _ => false,
+            })
+            // One entry belongs to chocolatey itself
+            .filter(|entry| !entry.file_name().eq_ignore_ascii_case("chocolatey"))
+            .count();
+
+        Some(count)
+    }
+
+    /// Returns the number of packages installed through `winget`.
+    ///
+    /// Listing them with `winget list` takes several seconds, so the databases that App
+    /// Installer keeps track of them in are read instead.
+    fn count_winget() -> Option<usize> {
+        let local_app_data = PathBuf::from(std::env::var_os("LOCALAPPDATA")?);
+        let app_installer = local_app_data
+            .join("Packages")
+            .read_dir()
+            .ok()?
+            .flatten()
+            .find(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("Microsoft.DesktopAppInstaller_")
+            })?
+            .path()
+            .join("LocalState");
+
+        // Packages installed from the msstore source are tracked in StoreEdgeFD, whereas those
+        // installed from the winget source are tracked by the source itself, so both add up.
+        let sources = app_installer
+            .read_dir()
+            .ok()?
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("Microsoft.Winget.Source_")
+            })
+            .map(|source| source.path().join("installed.db"));
+
+        let counts: Vec<usize> =
+            std::iter::once(app_installer.join("StoreEdgeFD").join("installed.db"))
+                .chain(sources)
+                .filter_map(|db| WindowsPackageReadout::count_winget_db(&db))
+                .collect();
+
+        if counts.is_empty() {
+            return None;
+        }
+
+        Some(counts.iter().sum())
+    }
+
+    fn count_winget_db(db: &Path) -> Option<usize> {
+        if !db.is_file() {
+            return None;
+        }
+
+        // winget keeps the database open while it runs, so it must not be written to.
+        let connection =
+            sqlite::Connection::open_with_flags(db, sqlite::OpenFlags::new().with_read_only())
+                .ok()?;
+        let mut statement = connection.prepare("SELECT COUNT(*) FROM ids").ok()?;
+        statement.next().ok()?;
+
+        match statement.read::<Option<i64>, _>(0) {
+            Ok(Some(count)) => Some(count as usize),
+            _ => None,
+        }
+    }
 }
 
-pub struct WindowsNetworkReadout;
+pub struct WindowsNetworkReadout {
+    default_interface: std::sync::OnceLock<Option<u32>>,
+}
 
 impl NetworkReadout for WindowsNetworkReadout {
     fn new() -> Self {
-        WindowsNetworkReadout
+        WindowsNetworkReadout {
+            default_interface: std::sync::OnceLock::new(),
+        }
     }
 
-    fn tx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        Ok(self.interface_row(interface)?.OutOctets as usize)
     }
 
-    fn tx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn tx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let row = self.interface_row(interface)?;
+        Ok((row.OutUcastPkts + row.OutNUcastPkts) as usize)
     }
 
-    fn rx_bytes(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        Ok(self.interface_row(interface)?.InOctets as usize)
     }
 
-    fn rx_packets(&self, _: Option<&str>) -> Result<usize, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn rx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        let row = self.interface_row(interface)?;
+        Ok((row.InUcastPkts + row.InNUcastPkts) as usize)
     }
 
     fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
@@ -486,7 +1418,449 @@ impl NetworkReadout for WindowsNetworkReadout {
         ))
     }
 
-    fn physical_address(&self, _: Option<&str>) -> Result<String, ReadoutError> {
-        Err(ReadoutError::NotImplemented)
+    fn physical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        use windows::Win32::NetworkManagement::IpHelper::{
+            GAA_FLAG_INCLUDE_ALL_INTERFACES, IP_ADAPTER_ADDRESSES_LH,
+        };
+
+        let default_index = match interface {
+            Some(_) => None,
+            None => Some(
+                self.default_interface
+                    .get_or_init(WindowsNetworkReadout::default_interface_index)
+                    .ok_or(ReadoutError::MetricNotAvailable)?,
+            ),
+        };
+
+        let buffer = WindowsNetworkReadout::adapter_addresses(GAA_FLAG_INCLUDE_ALL_INTERFACES)?;
+
+        let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES_LH;
+
+        while let Some(current) = unsafe { adapter.as_ref() } {
+            adapter = current.Next;
+
+            // Loopback adapters and some VPN adapters don't have a physical address.
+            let length = (current.PhysicalAddressLength as usize).min(8);
+            if length == 0 {
+                continue;
+            }
+
+            let is_match = match interface {
+                Some(interface) => {
+                    !current.FriendlyName.is_null()
+                        && String::from_utf16_lossy(unsafe { current.FriendlyName.as_wide() })
+                            == interface
+                }
+                None => Some(unsafe { current.Anonymous1.Anonymous.IfIndex }) == default_index,
+            };
+
+            if is_match {
+                return Ok(current.PhysicalAddress[..length]
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<Vec<_>>()
+                    .join(":"));
+            }
+        }
+
+        Err(ReadoutError::MetricNotAvailable)
+    }
+
+    fn ssid(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        let connection = WindowsNetworkReadout::wlan_connection(interface)?;
+        let ssid = connection.wlanAssociationAttributes.dot11Ssid;
+        let length = (ssid.uSSIDLength as usize).min(ssid.ucSSID.len());
+
+        Ok(String::from_utf8_lossy(&ssid.ucSSID[..length]).into_owned())
+    }
+
+    fn signal_strength(&self, interface: Option<&str>) -> Result<i32, ReadoutError> {
+        let connection = WindowsNetworkReadout::wlan_connection(interface)?;
+        let quality = connection.wlanAssociationAttributes.wlanSignalQuality as i32;
+
+        // The signal quality linearly maps 0 through 100 to -100 dBm through -50 dBm.
+        Ok(quality / 2 - 100)
+    }
+
+    fn default_gateway(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        use windows::Win32::NetworkManagement::IpHelper::{
+            ConvertInterfaceLuidToAlias, FreeMibTable, GetIpForwardTable2, MIB_IPFORWARD_TABLE2,
+        };
+        use windows::Win32::Networking::WinSock::AF_INET;
+
+        let mut table: *mut MIB_IPFORWARD_TABLE2 = std::ptr::null_mut();
+        unsafe { GetIpForwardTable2(AF_INET.0 as u16, &mut table) }
+            .map_err(|e| ReadoutError::Other(e.to_string()))?;
+
+        let rows = unsafe {
+            std::slice::from_raw_parts((*table).Table.as_ptr(), (*table).NumEntries as usize)
+        };
+
+        let gateway = rows
+            .iter()
+            .filter(|row| row.DestinationPrefix.PrefixLength == 0)
+            .filter(|row| {
+                let Some(interface) = interface else {
+                    return true;
+                };
+
+                // Interfaces are identified by their alias, e.g. "Ethernet" or "Wi-Fi".
+                let mut alias = [0u16; 257];
+                unsafe { ConvertInterfaceLuidToAlias(&row.InterfaceLuid, &mut alias) }.is_ok()
+                    && String::from_utf16_lossy(&alias).trim_end_matches('\0') == interface
+            })
+            .filter_map(|row| {
+                let next_hop = unsafe { row.NextHop.Ipv4.sin_addr.S_un.S_addr };
+                let next_hop = std::net::Ipv4Addr::from(next_hop.to_ne_bytes());
+
+                // On-link routes don't go through a gateway.
+                (!next_hop.is_unspecified()).then_some((row.Metric, next_hop))
+            })
+            .min_by_key(|(metric, _)| *metric)
+            .map(|(_, gateway)| gateway.to_string());
+
+        unsafe { FreeMibTable(table as *const std::ffi::c_void) };
+
+        gateway.ok_or(ReadoutError::MetricNotAvailable)
+    }
+
+    fn dns_servers(&self) -> Result<Vec<String>, ReadoutError> {
+        use std::net::{Ipv4Addr, Ipv6Addr};
+        use windows::Win32::NetworkManagement::IpHelper::{
+            GAA_FLAG_INCLUDE_ALL_INTERFACES, IP_ADAPTER_ADDRESSES_LH,
+        };
+        use windows::Win32::NetworkManagement::Ndis::IfOperStatusUp;
+        use windows::Win32::Networking::WinSock::{AF_INET, AF_INET6, SOCKADDR_IN, SOCKADDR_IN6};
+
+        let buffer = WindowsNetworkReadout::adapter_addresses(GAA_FLAG_INCLUDE_ALL_INTERFACES)?;
+
+        let mut servers: Vec<String> = Vec::new();
+        let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES_LH;
+
+        // Adapters are ordered by their priority, which the resolver follows.
+        while let Some(current) = unsafe { adapter.as_ref() } {
+            let mut dns_server = current.FirstDnsServerAddress;
+
+            while let Some(server) = unsafe { dns_server.as_ref() } {
+                let sockaddr = server.Address.lpSockaddr;
+                let family = unsafe { (*sockaddr).sa_family } as u32;
+
+                let address = if family == AF_INET.0 {
+                    let sockaddr = unsafe { *(sockaddr as *const SOCKADDR_IN) };
+                    let octets = unsafe { sockaddr.sin_addr.S_un.S_addr }.to_ne_bytes();
+                    Some(Ipv4Addr::from(octets).to_string())
+                } else if family == AF_INET6.0 {
+                    let sockaddr = unsafe { *(sockaddr as *const SOCKADDR_IN6) };
+                    Some(Ipv6Addr::from(unsafe { sockaddr.sin6_addr.u.Byte }).to_string())
+                } else {
+                    None
+                };
+
+                if let Some(address) = address {
+                    if current.OperStatus == IfOperStatusUp && !servers.contains(&address) {
+                        servers.push(address);
+                    }
+                }
+
+                dns_server = server.Next;
+            }
+
+            adapter = current.Next;
+        }
+
+        Ok(servers)
+    }
+
+    fn link_speed(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        use windows::core::HSTRING;
+        use windows::Win32::NetworkManagement::IpHelper::{
+            ConvertInterfaceAliasToLuid, GetIfEntry2, MIB_IF_ROW2,
+        };
+        use windows::Win32::NetworkManagement::Ndis::MediaConnectStateConnected;
+
+        let Some(interface) = interface else {
+            return Err(ReadoutError::Other(String::from(
+                "Please specify a network interface to query.",
+            )));
+        };
+
+        let mut row = MIB_IF_ROW2::default();
+
+        // Interfaces are identified by their alias, e.g. "Ethernet" or "Wi-Fi".
+        unsafe { ConvertInterfaceAliasToLuid(&HSTRING::from(interface), &mut row.InterfaceLuid) }
+            .map_err(|_| ReadoutError::Other(format!("Unknown network interface: {interface}")))?;
+
+        unsafe { GetIfEntry2(&mut row) }.map_err(|e| ReadoutError::Other(e.to_string()))?;
+
+        if row.MediaConnectState != MediaConnectStateConnected || row.TransmitLinkSpeed == 0 {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        // The speed is expressed in bits per second.
+        Ok((row.TransmitLinkSpeed / 1_000_000) as usize)
+    }
+}
+
+impl WindowsNetworkReadout {
+    /// Returns the attributes of the current connection of the wireless interface whose
+    /// alias matches the given one, _e.g._ `Wi-Fi`, or of the first connected wireless
+    /// interface.
+    fn wlan_connection(
+        interface: Option<&str>,
+    ) -> Result<WLAN_CONNECTION_ATTRIBUTES, ReadoutError> {
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::NetworkManagement::IpHelper::{
+            ConvertInterfaceGuidToLuid, ConvertInterfaceLuidToAlias,
+        };
+        use windows::Win32::NetworkManagement::Ndis::NET_LUID_LH;
+        use windows::Win32::NetworkManagement::WiFi::{
+            wlan_interface_state_connected, wlan_intf_opcode_current_connection, WlanCloseHandle,
+            WlanEnumInterfaces, WlanFreeMemory, WlanOpenHandle, WlanQueryInterface,
+            WLAN_INTERFACE_INFO_LIST,
+        };
+
+        let mut handle = HANDLE::default();
+        let mut negotiated_version = 0;
+
+        // The WLAN AutoConfig service isn't running on machines without a wireless adapter.
+        if unsafe {
+            WlanOpenHandle(
+                2,
+                std::ptr::null_mut(),
+                &mut negotiated_version,
+                &mut handle,
+            )
+        } != 0
+        {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        let mut interface_list: *mut WLAN_INTERFACE_INFO_LIST = std::ptr::null_mut();
+        let mut connection = None;
+
+        if unsafe { WlanEnumInterfaces(handle, std::ptr::null_mut(), &mut interface_list) } == 0 {
+            let interfaces = unsafe {
+                std::slice::from_raw_parts(
+                    (*interface_list).InterfaceInfo.as_ptr(),
+                    (*interface_list).dwNumberOfItems as usize,
+                )
+            };
+
+            // The WLAN API only knows about GUIDs, whereas the other readouts identify
+            // interfaces by their alias.
+            let alias_of = |guid: &windows::core::GUID| {
+                let mut luid = NET_LUID_LH::default();
+                let mut alias = [0u16; 257];
+
+                unsafe { ConvertInterfaceGuidToLuid(guid, &mut luid) }.ok()?;
+                unsafe { ConvertInterfaceLuidToAlias(&luid, &mut alias) }.ok()?;
+
+                Some(wide_to_string(&alias))
+            };
+
+            for info in interfaces {
+                if info.isState != wlan_interface_state_connected
+                    || interface.is_some_and(|interface| {
+                        alias_of(&info.InterfaceGuid).as_deref() != Some(interface)
+                    })
+                {
+                    continue;
+                }
+
+                let mut size = 0;
+                let mut data: *mut std::ffi::c_void = std::ptr::null_mut();
+
+                if unsafe {
+                    WlanQueryInterface(
+                        handle,
+                        &info.InterfaceGuid,
+                        wlan_intf_opcode_current_connection,
+                        std::ptr::null_mut(),
+                        &mut size,
+                        &mut data,
+                        std::ptr::null_mut(),
+                    )
+                } == 0
+                {
+                    connection = Some(unsafe { *(data as *const WLAN_CONNECTION_ATTRIBUTES) });
+                    unsafe { WlanFreeMemory(data) };
+                    break;
+                }
+            }
+
+            unsafe { WlanFreeMemory(interface_list as *const std::ffi::c_void) };
+        }
+
+        unsafe { WlanCloseHandle(handle, std::ptr::null_mut()) };
+
+        connection.ok_or(ReadoutError::MetricNotAvailable)
+    }
+
+    /// Returns the statistics of the interface whose alias matches the given one, _e.g._
+    /// `Ethernet` or `Wi-Fi`, or those of the interface carrying the default route.
+    fn interface_row(&self, interface: Option<&str>) -> Result<MIB_IF_ROW2, ReadoutError> {
+        use windows::Win32::NetworkManagement::IpHelper::{
+            FreeMibTable, GetIfTable2, IF_TYPE_SOFTWARE_LOOPBACK, MIB_IF_TABLE2,
+        };
+        use windows::Win32::NetworkManagement::Ndis::IfOperStatusUp;
+
+        let default_index = match interface {
+            Some(_) => None,
+            None => Some(
+                self.default_interface
+                    .get_or_init(WindowsNetworkReadout::default_interface_index)
+                    .ok_or(ReadoutError::MetricNotAvailable)?,
+            ),
+        };
+
+        let mut table: *mut MIB_IF_TABLE2 = std::ptr::null_mut();
+        unsafe { GetIfTable2(&mut table) }.map_err(|e| ReadoutError::Other(e.to_string()))?;
+
+        let rows = unsafe {
+            std::slice::from_raw_parts((*table).Table.as_ptr(), (*table).NumEntries as usize)
+        };
+
+        let row = rows
+            .iter()
+            .find(|row| match interface {
+                Some(interface) => {
+                    let alias = String::from_utf16_lossy(&row.Alias);
+                    alias.trim_end_matches('\0') == interface
+                }
+                // Loopback and disconnected adapters are never picked on their own.
+                None => {
+                    Some(row.InterfaceIndex) == default_index
+                        && row.Type != IF_TYPE_SOFTWARE_LOOPBACK
+                        && row.OperStatus == IfOperStatusUp
+                }
+            })
+            .copied();
+
+        unsafe { FreeMibTable(table as *const std::ffi::c_void) };
+
+        row.ok_or_else(|| match interface {
+            Some(interface) => {
+                ReadoutError::Other(format!("Interface \"{interface}\" does not exist."))
+            }
+            None => ReadoutError::MetricNotAvailable,
+        })
+    }
+
+    /// Returns the index of the interface carrying the IPv4 default route with the lowest
+    /// metric.
+    fn default_interface_index() -> Option<u32> {
+        use windows::Win32::NetworkManagement::IpHelper::{
+            FreeMibTable, GetIpForwardTable2, MIB_IPFORWARD_TABLE2,
+        };
+        use windows::Win32::Networking::WinSock::AF_INET;
+
+        let mut table: *mut MIB_IPFORWARD_TABLE2 = std::ptr::null_mut();
+        unsafe { GetIpForwardTable2(AF_INET.0 as u16, &mut table) }.ok()?;
+
+        let rows = unsafe {
+            std::slice::from_raw_parts((*table).Table.as_ptr(), (*table).NumEntries as usize)
+        };
+
+        let index = rows
+            .iter()
+            .filter(|row| row.DestinationPrefix.PrefixLength == 0)
+            .filter(|row| unsafe { row.NextHop.Ipv4.sin_addr.S_un.S_addr } != 0)
+            .min_by_key(|row| row.Metric)
+            .map(|row| row.InterfaceIndex);
+
+        unsafe { FreeMibTable(table as *const std::ffi::c_void) };
+
+        index
+    }
+
+    /// Returns the buffer filled in by `GetAdaptersAddresses`, which holds a linked list of
+    /// `IP_ADAPTER_ADDRESSES_LH` structures, one per adapter.
+    fn adapter_addresses(
+        flags: windows::Win32::NetworkManagement::IpHelper::GET_ADAPTERS_ADDRESSES_FLAGS,
+    ) -> Result<Vec<u64>, ReadoutError> {
+        use windows::Win32::Foundation::ERROR_BUFFER_OVERFLOW;
+        use windows::Win32::NetworkManagement::IpHelper::{
+            GetAdaptersAddresses, IP_ADAPTER_ADDRESSES_LH,
+        };
+        use windows::Win32::Networking::WinSock::AF_UNSPEC;
+
+        // The buffer is made out of u64s so that it is suitably aligned for the structures it
+        // will hold.
+        let mut size: u32 = 16 * 1024;
+
+        loop {
+            let mut buffer = vec![0u64; size as usize / 8 + 1];
+            let ret = unsafe {
+                GetAdaptersAddresses(
+                    AF_UNSPEC,
+                    flags,
+                    std::ptr::null_mut(),
+                    buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES_LH,
+                    &mut size,
+                )
+            };
+
+            match ret {
+                0 => return Ok(buffer),
+                ret if ret == ERROR_BUFFER_OVERFLOW.0 => continue,
+                ret => {
+                    return Err(ReadoutError::Other(format!(
+                        "Call to \"GetAdaptersAddresses\" failed with return code: {ret}"
+                    )))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wide_to_string() {
+        let wide = |s: &str| s.encode_utf16().collect::<Vec<u16>>();
+
+        let mut buffer = wide("Дмитрий");
+        buffer.extend([0, 0]);
+        assert_eq!(wide_to_string(&buffer), "Дмитрий");
+
+        let mut buffer = wide("田中太郎");
+        buffer.extend([0, 'x' as u16]);
+        assert_eq!(wide_to_string(&buffer), "田中太郎");
+
+        // Characters outside of the BMP are encoded as surrogate pairs.
+        assert_eq!(wide_to_string(&wide("host-𝔘")), "host-𝔘");
+        assert_eq!(wide_to_string(&[0]), "");
+    }
+
+    #[test]
+    fn test_windows_version_name() {
+        let version = WindowsVersion {
+            build: String::from("22631"),
+            display_version: Some(String::from("23H2")),
+            ubr: Some(3593),
+        };
+
+        assert_eq!(version.build_string(), "22631.3593");
+        assert_eq!(
+            version.name("Microsoft Windows 11 Pro"),
+            "Windows 11 Pro 23H2 (build 22631.3593)"
+        );
+        assert_eq!(
+            version.name("Microsoft Windows 10 Pro"),
+            "Windows 11 Pro 23H2 (build 22631.3593)"
+        );
+
+        let version = WindowsVersion {
+            build: String::from("18363"),
+            display_version: None,
+            ubr: None,
+        };
+
+        assert_eq!(
+            version.name("Microsoft Windows 10 Home"),
+            "Windows 10 Home (build 18363)"
+        );
     }
 }
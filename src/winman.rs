@@ -17,8 +17,122 @@ use std::os::fd::AsRawFd;
 
 #[cfg(target_os = "linux")]
 pub fn detect_wayland_window_manager() -> Result<String, ReadoutError> {
+    let pid = compositor_pid_from_library()
+        .or_else(compositor_pid_from_socket)
+        .ok_or(ReadoutError::MetricNotAvailable)?;
+
+    let name = extra::pop_newline(std::fs::read_to_string(format!("/proc/{}/comm", pid))?);
+
+    // Some compositors report their version over their IPC socket.
+    let version = match name.as_str() {
+        "Hyprland" => hyprland_version(),
+        "sway" => sway_version(),
+        _ => None,
+    };
+
+    match version {
+        Some(version) => Ok(format!("{name} {version}")),
+        None => Ok(name),
+    }
+}
+
+/// Queries the version of Hyprland through the socket of the running instance.
+#[cfg(target_os = "linux")]
+fn hyprland_version() -> Option<String> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+    use std::path::PathBuf;
+
+    let signature = std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE")?;
+
+    // Older releases place their sockets in /tmp.
+    let sockets = [
+        std::env::var_os("XDG_RUNTIME_DIR").map(|dir| PathBuf::from(dir).join("hypr")),
+        Some(PathBuf::from("/tmp/hypr")),
+    ];
+
+    let mut stream = sockets
+        .into_iter()
+        .flatten()
+        .find_map(|dir| UnixStream::connect(dir.join(&signature).join(".socket.sock")).ok())?;
+
+    stream.set_read_timeout(Some(IPC_TIMEOUT)).ok()?;
+    stream.write_all(b"j/version").ok()?;
+
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).ok()?;
+
+    // e.g. "v0.41.2"
+    let tag = crate::shared::parse_json_string(&reply, "tag")?;
+    Some(tag.trim_start_matches('v').to_string()).filter(|tag| !tag.is_empty())
+}
+
+/// Queries the version of sway through the i3 IPC protocol on `$SWAYSOCK`.
+#[cfg(target_os = "linux")]
+fn sway_version() -> Option<String> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    const GET_VERSION: u32 = 7;
+
+    let mut stream = UnixStream::connect(std::env::var_os("SWAYSOCK")?).ok()?;
+    stream.set_read_timeout(Some(IPC_TIMEOUT)).ok()?;
+    stream.write_all(&i3_ipc_message(GET_VERSION, &[])).ok()?;
+
+    let mut header = [0u8; I3_IPC_HEADER_LEN];
+    stream.read_exact(&mut header).ok()?;
+    let (kind, len) = parse_i3_ipc_header(&header)?;
+
+    if kind != GET_VERSION {
+        return None;
+    }
+
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload).ok()?;
+
+    // e.g. "1.9" or "1.10-dev-abcdef (Jan 01 2024, branch 'master')"
+    let version =
+        crate::shared::parse_json_string(&String::from_utf8_lossy(&payload), "human_readable")?;
+
+    version.split_whitespace().next().map(String::from)
+}
+
+#[cfg(target_os = "linux")]
+const IPC_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+#[cfg(target_os = "linux")]
+const I3_IPC_MAGIC: &[u8; 6] = b"i3-ipc";
+
+#[cfg(target_os = "linux")]
+const I3_IPC_HEADER_LEN: usize = I3_IPC_MAGIC.len() + 8;
+
+/// Frames a message as `i3-ipc<payload length><message type><payload>`, with both integers
+/// in native byte order.
+#[cfg(target_os = "linux")]
+fn i3_ipc_message(kind: u32, payload: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(I3_IPC_HEADER_LEN + payload.len());
+    message.extend_from_slice(I3_IPC_MAGIC);
+    message.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+    message.extend_from_slice(&kind.to_ne_bytes());
+    message.extend_from_slice(payload);
+    message
+}
+
+/// Returns the message type and payload length of a reply header.
+#[cfg(target_os = "linux")]
+fn parse_i3_ipc_header(header: &[u8]) -> Option<(u32, usize)> {
+    let rest = header.strip_prefix(I3_IPC_MAGIC)?;
+    let len = u32::from_ne_bytes(rest.get(0..4)?.try_into().ok()?);
+    let kind = u32::from_ne_bytes(rest.get(4..8)?.try_into().ok()?);
+
+    Some((kind, len as usize))
+}
+
+/// Returns the PID of the compositor by connecting through `libwayland-client`.
+#[cfg(target_os = "linux")]
+fn compositor_pid_from_library() -> Option<i32> {
     if !is_lib_available() {
-        return Err(ReadoutError::MetricNotAvailable);
+        return None;
     }
 
     let display_ptr = unsafe {
@@ -30,7 +144,7 @@ pub fn detect_wayland_window_manager() -> Result<String, ReadoutError> {
     };
 
     if display_ptr.is_null() {
-        return Err(ReadoutError::MetricNotAvailable);
+        return None;
     }
 
     let display_fd =
@@ -39,99 +153,49 @@ pub fn detect_wayland_window_manager() -> Result<String, ReadoutError> {
 
     let pid = sockopt::PeerCredentials
         .get(display_fd)
-        .map_err(|_| ReadoutError::MetricNotAvailable)?
-        .pid();
+        .ok()
+        .map(|c| c.pid());
+
+    unsafe { ffi_dispatch!(wayland_client_handle(), wl_display_disconnect, display_ptr) };
+
+    pid
+}
+
+/// Returns the PID of the compositor by connecting to its socket directly, which doesn't
+/// require `libwayland-client` to be installed, as no messages are exchanged.
+#[cfg(target_os = "linux")]
+fn compositor_pid_from_socket() -> Option<i32> {
+    use std::os::unix::net::UnixStream;
+    use std::path::PathBuf;
+
+    let display = std::env::var_os("WAYLAND_DISPLAY").unwrap_or_else(|| "wayland-0".into());
+    let mut socket = PathBuf::from(display);
+
+    // WAYLAND_DISPLAY may also hold an absolute path to the socket.
+    if socket.is_relative() {
+        socket = PathBuf::from(std::env::var_os("XDG_RUNTIME_DIR")?).join(socket);
+    }
 
-    Ok(extra::pop_newline(std::fs::read_to_string(format!(
-        "/proc/{}/comm",
-        pid
-    ))?))
+    let stream = UnixStream::connect(socket).ok()?;
+    let credentials = sockopt::PeerCredentials.get(stream.as_raw_fd()).ok()?;
+
+    Some(credentials.pid())
 }
 
 pub fn detect_xorg_window_manager() -> Result<String, ReadoutError> {
-    if extra::which("xprop") {
-        let xprop_id = Command::new("xprop")
-            .args(["-root", "-notype", "_NET_SUPPORTING_WM_CHECK"])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .expect("ERROR: failed to spawn \"xprop\" process");
-
-        let xprop_id_output = xprop_id
-            .wait_with_output()
-            .expect("ERROR: failed to wait for \"xprop\" process to exit");
-
-        let window_manager_id_info = String::from_utf8(xprop_id_output.stdout)
-            .expect("ERROR: \"xprop -root -notype _NET_SUPPORTING_WM_CHECK\" process stdout was not valid UTF-8");
-
-        let window_manager_id = window_manager_id_info.split(' ').last().unwrap_or_default();
-
-        let xprop_property = Command::new("xprop")
-            .args([
-                "-id",
-                window_manager_id,
-                "-notype",
-                "-len",
-                "25",
-                "-f",
-                "_NET_WM_NAME",
-                "8t",
-            ])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .expect("ERROR: failed to spawn \"xprop\" process");
-
-        let xprop_property_output = xprop_property
-            .wait_with_output()
-            .expect("ERROR: failed to wait for \"xprop\" process to exit");
-
-        let window_manager_name_info = String::from_utf8(xprop_property_output.stdout)
-            .unwrap_or_else(|_| {
-                panic!(
-                    "ERROR: \"xprop -id {window_manager_id} -notype -len 25
-                                       -f _NET_WM_NAME 8t\" process stdout was not valid UTF-8"
-                )
-            });
-
-        if let Some(line) = window_manager_name_info
-            .lines()
-            .find(|line| line.starts_with("_NET_WM_NAME"))
-        {
-            return Ok(line
-                .split_once('=')
-                .unwrap_or_default()
-                .1
-                .trim()
-                .replace(['\"', '\''], ""));
-        };
+    if let Some(window_manager) = detect_ewmh_window_manager() {
+        return Ok(window_manager);
     }
 
     if extra::which("wmctrl") {
-        let wmctrl = Command::new("wmctrl")
+        let output = Command::new("wmctrl")
             .arg("-m")
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()
-            .expect("ERROR: failed to spawn \"wmctrl\" process");
+            .output()?;
 
-        let wmctrl_out = wmctrl
-            .stdout
-            .expect("ERROR: failed to open \"wmctrl\" stdout");
-
-        let head = Command::new("head")
-            .args(["-n", "1"])
-            .stdin(Stdio::from(wmctrl_out))
-            .stdout(Stdio::piped())
-            .spawn()
-            .expect("ERROR: failed to spawn \"head\" process");
-
-        let output = head
-            .wait_with_output()
-            .expect("ERROR: failed to wait for \"head\" process to exit");
-
-        let window_manager = String::from_utf8(output.stdout)
-            .expect("ERROR: \"wmctrl -m | head -n1\" process stdout was not valid UTF-8");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let window_manager = stdout.lines().next().unwrap_or_default();
 
         let winman_name =
             extra::pop_newline(String::from(window_manager.replace("Name:", "").trim()));
@@ -149,3 +213,68 @@ pub fn detect_xorg_window_manager() -> Result<String, ReadoutError> {
         "\"wmctrl\" must be installed to display your window manager.".to_string(),
     ))
 }
+
+/// Reads the name of the window manager from the `_NET_WM_NAME` property of the window
+/// referenced by `_NET_SUPPORTING_WM_CHECK` on the root window, as described by EWMH.
+fn detect_ewmh_window_manager() -> Option<String> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt, Window};
+
+    let (conn, screen) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots.get(screen)?.root;
+
+    let atom = |name: &[u8]| -> Option<u32> {
+        Some(conn.intern_atom(false, name).ok()?.reply().ok()?.atom)
+    };
+
+    let supporting_wm_check = atom(b"_NET_SUPPORTING_WM_CHECK")?;
+    let net_wm_name = atom(b"_NET_WM_NAME")?;
+    let utf8_string = atom(b"UTF8_STRING")?;
+
+    let check_window = |window: Window| -> Option<Window> {
+        conn.get_property(false, window, supporting_wm_check, AtomEnum::WINDOW, 0, 1)
+            .ok()?
+            .reply()
+            .ok()?
+            .value32()?
+            .next()
+    };
+
+    let window = check_window(root)?;
+
+    // A window manager that exited without cleaning up leaves a stale reference behind,
+    // whereas a live one sets the property on its own window to point to itself.
+    if check_window(window)? != window {
+        return None;
+    }
+
+    let name = conn
+        .get_property(false, window, net_wm_name, utf8_string, 0, u32::MAX)
+        .ok()?
+        .reply()
+        .ok()?
+        .value;
+
+    let name = String::from_utf8_lossy(&name).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_i3_ipc_framing() {
+        let message = i3_ipc_message(7, b"");
+        assert_eq!(message.len(), I3_IPC_HEADER_LEN);
+        assert_eq!(parse_i3_ipc_header(&message), Some((7, 0)));
+
+        let message = i3_ipc_message(1, b"[]");
+        assert_eq!(parse_i3_ipc_header(&message), Some((1, 2)));
+        assert_eq!(&message[I3_IPC_HEADER_LEN..], b"[]");
+
+        assert_eq!(parse_i3_ipc_header(b"i3-ipc\0\0"), None);
+        assert_eq!(parse_i3_ipc_header(b"not-i3-ipc-header"), None);
+    }
+}